@@ -0,0 +1,157 @@
+use crate::error::ContractError;
+use crate::msg::{PendingOwnership, Role, RoleMembers, RolesResponse};
+use crate::state::{ADMIN, PENDING_OWNERSHIP, ROLES};
+use cosmwasm_std::{
+    ensure, ensure_eq, Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+};
+use cw_utils::Expiration;
+
+pub fn ensure_admin(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+    ensure_eq!(
+        *sender,
+        ADMIN.load(deps.storage)?,
+        ContractError::ForAdminOnly {}
+    );
+
+    Ok(())
+}
+
+/// Admin holds every role, other addresses need the role granted
+pub fn ensure_role(deps: Deps, sender: &Addr, role: Role) -> Result<(), ContractError> {
+    ensure!(
+        *sender == ADMIN.load(deps.storage)?
+            || ROLES.has(deps.storage, (role.key().to_string(), sender.clone())),
+        ContractError::ForRoleOnly { role }
+    );
+
+    Ok(())
+}
+
+pub fn execute_transfer_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+    expiry: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info.sender)?;
+
+    ensure!(
+        !expiry.is_some_and(|expiry| expiry.is_expired(&env.block)),
+        ContractError::InvalidExpiry {}
+    );
+
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+
+    PENDING_OWNERSHIP.save(
+        deps.storage,
+        &PendingOwnership {
+            new_owner: new_owner.clone(),
+            expiry,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_ownership")
+        .add_attribute("new_owner", new_owner))
+}
+
+pub fn execute_accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending_ownership = PENDING_OWNERSHIP
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwnership {})?;
+
+    ensure_eq!(
+        info.sender,
+        pending_ownership.new_owner,
+        ContractError::ForPendingOwnerOnly {}
+    );
+
+    ensure!(
+        !pending_ownership
+            .expiry
+            .is_some_and(|expiry| expiry.is_expired(&env.block)),
+        ContractError::OwnershipTransferExpired {}
+    );
+
+    ADMIN.save(deps.storage, &pending_ownership.new_owner)?;
+    PENDING_OWNERSHIP.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_ownership")
+        .add_attribute("admin", pending_ownership.new_owner))
+}
+
+pub fn execute_cancel_ownership_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info.sender)?;
+
+    ensure!(
+        PENDING_OWNERSHIP.exists(deps.storage),
+        ContractError::NoPendingOwnership {}
+    );
+
+    PENDING_OWNERSHIP.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_ownership_transfer"))
+}
+
+pub fn execute_update_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+    is_granted: bool,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+
+    if is_granted {
+        ROLES.save(
+            deps.storage,
+            (role.key().to_string(), address.clone()),
+            &true,
+        )?;
+    } else {
+        ROLES.remove(deps.storage, (role.key().to_string(), address.clone()));
+    }
+
+    Ok(Response::new()
+        .add_attribute(
+            "action",
+            if is_granted {
+                "grant_role"
+            } else {
+                "revoke_role"
+            },
+        )
+        .add_attribute("role", role.key())
+        .add_attribute("address", address))
+}
+
+pub fn get_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let roles = Role::ALL
+        .into_iter()
+        .map(|role| {
+            let members = ROLES
+                .prefix(role.key().to_string())
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<Addr>>>()?;
+
+            Ok(RoleMembers { role, members })
+        })
+        .collect::<StdResult<Vec<RoleMembers>>>()?;
+
+    Ok(RolesResponse {
+        admin: ADMIN.load(deps.storage)?,
+        pending_ownership: PENDING_OWNERSHIP.may_load(deps.storage)?,
+        roles,
+    })
+}
@@ -0,0 +1,335 @@
+use crate::access_control::ensure_role;
+use crate::contract::query::{
+    get_deposit, get_supported_tokens, get_token_decimal, get_total_deposited_by_token,
+    get_user_borrow_amount_with_interest, user_deposit_as_collateral,
+};
+use crate::contract::{
+    execute_repay_user_borrowing, execute_update_liquidity_index_data, generate_transfer_message,
+    get_token_asset_info, DecimalExt, INTEREST_RATE_DECIMALS,
+};
+use crate::error::ContractError;
+use crate::msg::{BadDebtWriteOff, Role};
+use crate::state::{
+    BAD_DEBT_WRITE_OFFS, INSURANCE_FUND, LIQUIDITY_INDEX_WRITE_DOWN_LN, NEXT_BAD_DEBT_WRITE_OFF_ID,
+    PROTOCOL_RESERVES, SUPPORTED_TOKENS,
+};
+use crate::term_loan::{execute_repay_term_loan, get_user_term_loans};
+use cosmwasm_std::{
+    attr, ensure, Addr, Deps, DepsMut, Env, Event, MessageInfo, Order, Response, StdResult, Uint128,
+};
+use cw_utils::{nonpayable, one_coin};
+use rust_decimal::prelude::{Decimal, MathematicalOps};
+use std::ops::Div;
+
+pub fn get_insurance_fund(deps: Deps, denom: String) -> StdResult<Uint128> {
+    Ok(INSURANCE_FUND
+        .may_load(deps.storage, denom)?
+        .unwrap_or_default())
+}
+
+pub fn get_liquidity_index_write_down_ln(deps: Deps, denom: String) -> StdResult<u128> {
+    Ok(LIQUIDITY_INDEX_WRITE_DOWN_LN
+        .may_load(deps.storage, denom)?
+        .unwrap_or_default())
+}
+
+pub fn get_bad_debt_write_offs(deps: Deps) -> StdResult<Vec<BadDebtWriteOff>> {
+    BAD_DEBT_WRITE_OFFS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, write_off)| write_off))
+        .collect()
+}
+
+pub fn has_collateral(deps: Deps, env: Env, user: String) -> StdResult<bool> {
+    for token in get_supported_tokens(deps)?.supported_tokens {
+        if user_deposit_as_collateral(deps, user.clone(), token.denom.clone())?
+            && !get_deposit(deps, env.clone(), user.clone(), token.denom)?
+                .balance
+                .is_zero()
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Writes off all debts of a user without collateral, returns an event for each write-off
+pub fn write_off_bad_debt(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+) -> Result<Vec<Event>, ContractError> {
+    let mut events = vec![];
+
+    for token in get_supported_tokens(deps.as_ref())?.supported_tokens {
+        if let Some(write_off) =
+            write_off_bad_debt_by_token(deps, env.clone(), user.clone(), token.denom)?
+        {
+            events.push(Event::new("bad_debt_write_off").add_attributes(vec![
+                attr("write_off_id", write_off.id.to_string()),
+                attr("user", write_off.user),
+                attr("denom", write_off.denom),
+                attr("amount", write_off.amount),
+                attr(
+                    "covered_by_insurance_fund",
+                    write_off.covered_by_insurance_fund,
+                ),
+                attr("covered_by_reserves", write_off.covered_by_reserves),
+                attr("socialized_amount", write_off.socialized_amount),
+            ]));
+        }
+    }
+
+    Ok(events)
+}
+
+fn write_off_bad_debt_by_token(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+) -> Result<Option<BadDebtWriteOff>, ContractError> {
+    execute_update_liquidity_index_data(deps, env.clone(), denom.clone())?;
+
+    let amount = get_user_borrow_amount_with_interest(
+        deps.as_ref(),
+        env.clone(),
+        user.clone(),
+        denom.clone(),
+    )?
+    .u128();
+
+    if amount == 0 {
+        return Ok(None);
+    }
+
+    // the debt is closed without any funds coming in
+    execute_repay_user_borrowing(deps, env.clone(), user.clone(), denom.clone(), amount)?;
+
+    for term_loan in get_user_term_loans(deps.as_ref(), user.clone())? {
+        if term_loan.denom == denom {
+            execute_repay_term_loan(deps, env.clone(), term_loan.id, u128::MAX)?;
+        }
+    }
+
+    // the loss is covered by the insurance fund first, then by the protocol reserves
+    let insurance_fund = get_insurance_fund(deps.as_ref(), denom.clone())?.u128();
+    let covered_by_insurance_fund = amount.min(insurance_fund);
+    INSURANCE_FUND.save(
+        deps.storage,
+        denom.clone(),
+        &Uint128::from(insurance_fund - covered_by_insurance_fund),
+    )?;
+
+    // reserves are up to date after the liquidity index update
+    let protocol_reserves = PROTOCOL_RESERVES
+        .may_load(deps.storage, denom.clone())?
+        .unwrap_or_default();
+    let covered_by_reserves = (amount - covered_by_insurance_fund).min(protocol_reserves);
+    PROTOCOL_RESERVES.save(
+        deps.storage,
+        denom.clone(),
+        &(protocol_reserves - covered_by_reserves),
+    )?;
+
+    let socialized_amount = execute_socialize_loss(
+        deps,
+        env.clone(),
+        denom.clone(),
+        amount - covered_by_insurance_fund - covered_by_reserves,
+    )?;
+
+    let id = NEXT_BAD_DEBT_WRITE_OFF_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    NEXT_BAD_DEBT_WRITE_OFF_ID.save(deps.storage, &(id + 1))?;
+
+    let write_off = BadDebtWriteOff {
+        id,
+        user,
+        denom,
+        amount: Uint128::from(amount),
+        covered_by_insurance_fund: Uint128::from(covered_by_insurance_fund),
+        covered_by_reserves: Uint128::from(covered_by_reserves),
+        socialized_amount: Uint128::from(socialized_amount),
+        timestamp: env.block.time,
+    };
+
+    BAD_DEBT_WRITE_OFFS.save(deps.storage, id, &write_off)?;
+
+    Ok(Some(write_off))
+}
+
+// lowers the liquidity index so the loss is shared by mmToken holders pro rata,
+// returns the amount that was actually socialized
+fn execute_socialize_loss(
+    deps: &mut DepsMut,
+    env: Env,
+    denom: String,
+    amount: u128,
+) -> Result<u128, ContractError> {
+    let total_deposited = get_total_deposited_by_token(deps.as_ref(), env, denom.clone())?.u128();
+
+    // deposits can't be written down to zero, a loss above them stays unbacked
+    let socialized_amount = amount.min(total_deposited.saturating_sub(1));
+
+    if socialized_amount == 0 {
+        return Ok(0);
+    }
+
+    let token_decimals = get_token_decimal(deps.as_ref(), denom.clone())?.u128() as u32;
+
+    let write_down_ln = Decimal::from_i128_with_scale(total_deposited as i128, token_decimals)
+        .div(Decimal::from_i128_with_scale(
+            (total_deposited - socialized_amount) as i128,
+            token_decimals,
+        ))
+        .ln()
+        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)?;
+
+    LIQUIDITY_INDEX_WRITE_DOWN_LN.update(
+        deps.storage,
+        denom,
+        |liquidity_index_write_down_ln| -> StdResult<_> {
+            Ok(liquidity_index_write_down_ln.unwrap_or_default() + write_down_ln)
+        },
+    )?;
+
+    Ok(socialized_amount)
+}
+
+pub fn execute_write_off_bad_debt(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    // the debt is bad only when there is no collateral left to liquidate
+    ensure!(
+        !has_collateral(deps.as_ref(), env.clone(), user.clone())?,
+        ContractError::NoBadDebt {}
+    );
+
+    let events = write_off_bad_debt(&mut deps, env, user.clone())?;
+
+    ensure!(!events.is_empty(), ContractError::NoBadDebt {});
+
+    Ok(Response::new().add_events(events).add_attributes(vec![
+        attr("action", "write_off_bad_debt"),
+        attr("user", user),
+    ]))
+}
+
+fn execute_fund_insurance_fund(
+    deps: DepsMut,
+    funder: Addr,
+    denom: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    ensure!(
+        amount > Uint128::zero(),
+        ContractError::InvalidFunds {
+            msg: "Cannot fund insurance fund with 0 amount".to_string()
+        }
+    );
+
+    INSURANCE_FUND.update(
+        deps.storage,
+        denom.clone(),
+        |insurance_fund| -> StdResult<_> { Ok(insurance_fund.unwrap_or_default() + amount) },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "fund_insurance_fund"),
+        attr("funder", funder.to_string()),
+        attr("denom", denom),
+        attr("amount", amount),
+    ]))
+}
+
+pub fn execute_fund_insurance_fund_native(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure!(!info.funds.is_empty(), ContractError::CoinNotFound {});
+    let fund_token = one_coin(&info)?;
+
+    execute_fund_insurance_fund(deps, info.sender, fund_token.denom, fund_token.amount)
+}
+
+pub fn execute_cw20_fund_insurance_fund(
+    deps: DepsMut,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // only cw20 tokens must be sent, not a coins
+    nonpayable(&info)?;
+
+    let denom = get_supported_tokens(deps.as_ref())?
+        .supported_tokens
+        .into_iter()
+        .find(|token| token.cw20_address == Some(info.sender.to_string()))
+        .ok_or(ContractError::TokenNotSupported {})?
+        .denom;
+
+    execute_fund_insurance_fund(deps, sender, denom, amount)
+}
+
+pub fn execute_withdraw_insurance_fund(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    amount: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    ensure_role(deps.as_ref(), &info.sender, Role::Treasury)?;
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    ensure!(
+        !amount.is_zero(),
+        ContractError::InvalidFunds {
+            msg: "Cannot withdraw 0 amount of insurance fund".to_string()
+        }
+    );
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    // the insurance fund is not lent out, so it is always held by the contract
+    let insurance_fund = get_insurance_fund(deps.as_ref(), denom.clone())?;
+
+    ensure!(
+        amount <= insurance_fund,
+        ContractError::NotEnoughInsuranceFund {}
+    );
+
+    INSURANCE_FUND.save(deps.storage, denom.clone(), &(insurance_fund - amount))?;
+
+    let asset = get_token_asset_info(deps.as_ref(), denom.clone())?;
+
+    Ok(Response::new()
+        .add_message(generate_transfer_message(
+            asset,
+            amount,
+            recipient.to_string(),
+        )?)
+        .add_attributes(vec![
+            attr("action", "withdraw_insurance_fund"),
+            attr("denom", denom),
+            attr("amount", amount),
+            attr("recipient", recipient),
+        ]))
+}
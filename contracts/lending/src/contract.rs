@@ -1,71 +1,140 @@
 use crate::contract::query::{
-    fetch_price_by_token, get_admin, get_all_users_with_borrows, get_available_liquidity_by_token,
-    get_available_to_borrow, get_available_to_redeem, get_current_liquidity_index_ln,
-    get_interest_rate, get_liquidity_index_last_update, get_liquidity_rate, get_mm_token_price,
+    fetch_e_mode_price_by_token, fetch_price_by_token, get_account_snapshot, get_admin,
+    get_all_users_with_borrows, get_available_liquidity_by_token, get_available_to_borrow,
+    get_available_to_redeem, get_contract_balance_by_token, get_current_liquidity_index_ln,
+    get_e_mode_categories, get_flash_loan_fee, get_interest_rate, get_isolated_debt,
+    get_isolation_configuration, get_liquidation_configuration, get_liquidity_index_last_update,
+    get_liquidity_rate, get_market_caps_headroom, get_market_status, get_mm_token_address,
+    get_mm_token_balance, get_mm_token_price, get_oracle_configuration, get_protocol_reserves,
     get_pyth_contract, get_pyth_price_feed_ids, get_reserve_configuration, get_supported_tokens,
-    get_token_decimal, get_tokens_interest_rate_model_params, get_total_borrow_data,
-    get_total_borrowed_by_token, get_total_deposited_by_token, get_total_reserves_by_token,
-    get_user_borrow_amount_with_interest, get_user_borrowed_usd, get_user_borrowing_info,
-    get_user_collateral_usd, get_user_deposited_usd, get_user_liquidation_threshold,
-    get_user_max_allowed_borrow_amount_usd, get_user_utilization_rate, get_users_balances,
-    get_utilization_rate_by_token, is_paused, user_deposit_as_collateral,
+    get_token_amount_usd, get_token_decimal, get_tokens_interest_rate_model_params,
+    get_total_borrow_data, get_total_borrowed_by_token, get_total_deposited_by_token,
+    get_total_mm_token_supply, get_total_reserves_by_token, get_user_borrow_amount_with_interest,
+    get_user_borrowed_usd, get_user_borrowing_info, get_user_collateral_usd,
+    get_user_deposited_usd, get_user_e_mode, get_user_e_mode_category,
+    get_user_isolated_collateral, get_user_liquidation_threshold,
+    get_user_max_allowed_borrow_amount_usd, get_user_utilization_rate,
+    get_user_variable_borrow_amount_with_interest, get_users_balances,
+    get_utilization_rate_by_token, is_e_mode_price_fresh, is_paused, user_deposit_as_collateral,
 };
 
 use crate::msg::{
-    LiquidityIndexData, ReserveConfiguration, TokenInfo, TokenInterestRateModelParams,
-    TotalBorrowData, UserBorrowingInfo,
+    EModeCategory, FlashLoanData, FlashLoanReceiverMsg, IsolationConfiguration,
+    LiquidationConfiguration, LiquidityIndexData, MarketStatus, OracleConfiguration, PriceSource,
+    PriceSources, ReserveConfiguration, StableRateConfiguration, TokenInfo,
+    TokenInterestRateModelParams, TotalBorrowData, UserBorrowingInfo,
 };
 
+use crate::oracle::{get_price_sources, query_source_price};
+
 use cw_asset::AssetInfo;
 
 use crate::state::{
-    IS_PAUSED, IS_TESTING, LIQUIDITY_INDEX_DATA, PRICES, PRICE_FEED_IDS, PRICE_UPDATER_ADDRESS,
-    PYTH_CONTRACT, TOTAL_BORROW_DATA, USER_BORROWING_INFO, USER_DEPOSIT_AS_COLLATERAL,
+    ADAPTIVE_RATE_STATE, E_MODE_CATEGORIES, FLASH_LOAN, FLASH_LOAN_FEE, INSURANCE_FUND,
+    ISOLATED_DEBT, ISOLATION_CONFIGURATION, IS_PAUSED, IS_TESTING, LIQUIDATION_CONFIGURATION,
+    LIQUIDITY_INDEX_DATA, LIQUIDITY_INDEX_WRITE_DOWN_LN, MARKET_PAUSED_ACTIONS, MARKET_STATUS,
+    MM_TOKEN_ADDRESS, ORACLE_CONFIGURATION, PRICES, PRICE_FEED_IDS, PRICE_SOURCES,
+    PRICE_TIMESTAMPS, PRICE_UPDATER_ADDRESS, PROTOCOL_RESERVES, PYTH_CONTRACT, RATE_STRATEGIES,
+    RESERVE_FACTOR, STABLE_RATE_CONFIGURATION, STORAGE_VERSION, TERM_LOANS, TOTAL_BORROW_DATA,
+    USER_BORROWING_INFO, USER_DEPOSIT_AS_COLLATERAL, USER_E_MODE, USER_STABLE_BORROWING_INFO,
 };
 
 use rust_decimal::prelude::{Decimal, MathematicalOps};
 
 use cosmwasm_std::{
     attr, coin, ensure, ensure_eq, ensure_ne, from_json, to_json_binary, wasm_execute, Addr,
-    CosmosMsg, SubMsg,
+    CosmosMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use std::ops::{Add, Div, Mul};
 
-use pyth_sdk_cw::{query_price_feed, PriceFeedResponse};
+use pyth_sdk_cw::{query_price_feed, Price, PriceFeedResponse};
 
 use cw_utils::{nonpayable, one_coin};
 
 use {
     crate::contract::query::get_deposit,
     crate::{
+        access_control::{
+            ensure_admin, ensure_role, execute_accept_ownership, execute_cancel_ownership_transfer,
+            execute_transfer_ownership, execute_update_role, get_roles,
+        },
+        bad_debt::{
+            execute_cw20_fund_insurance_fund, execute_fund_insurance_fund_native,
+            execute_withdraw_insurance_fund, execute_write_off_bad_debt, get_bad_debt_write_offs,
+            get_insurance_fund, get_liquidity_index_write_down_ln, has_collateral,
+            write_off_bad_debt,
+        },
+        delegation::{
+            execute_approve_delegation, execute_revoke_delegation, get_borrow_allowance,
+            use_borrow_allowance,
+        },
         error::ContractError,
+        interest_rate::{
+            calculate_interest_rate, execute_set_rate_strategy, get_rate_strategy,
+            update_adaptive_rate,
+        },
+        migrations::{migrate_storage, CURRENT_STORAGE_VERSION},
         msg::InstantiateMsg,
-        msg::{Cw20HookMsg, ExecuteMsg, QueryMsg},
+        msg::MigrateMsg,
+        msg::{Cw20HookMsg, ExecuteMsg, PauseAction, QueryMsg, RateMode, RewardSide, Role},
+        pause::{ensure_action_not_paused, execute_set_action_pause, get_pause_state},
+        rewards::{
+            execute_add_emission_schedule, execute_claim_rewards,
+            execute_sweep_undistributed_rewards, get_emission_schedules, get_pending_rewards,
+            get_undistributed_rewards, remove_market_emission_schedules, update_user_rewards,
+        },
+        stable_rate::{
+            execute_rebalance_stable_rate, execute_repay_stable_borrowing,
+            execute_set_stable_rate_configuration, execute_stable_borrow, get_stable_interest_rate,
+            get_stable_rate_configuration, get_user_stable_borrow_amount_with_interest,
+            get_user_stable_borrowing_info,
+        },
         state::{
             ADMIN, RESERVE_CONFIGURATION, SUPPORTED_TOKENS, TOKENS_INTEREST_RATE_MODEL_PARAMS,
             USER_MM_TOKEN_BALANCE,
         },
+        term_loan::{
+            execute_cw20_repay_term_loan, execute_repay_term_loan, execute_repay_term_loan_native,
+            execute_term_borrow, get_overdue_term_loans, get_term_loan_amount_with_interest,
+            get_term_loan_borrowers, get_user_term_borrow_amount_with_interest,
+            get_user_term_loans, is_term_loan_overdue, load_term_loan,
+        },
+        timelock::{
+            execute_cancel_queued_action, execute_queue_action, execute_set_timelock_delay,
+            get_pending_actions, get_timelock_delay, is_timelocked, take_ready_action,
+        },
     },
     cosmwasm_std::{
-        coins, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Timestamp,
-        Uint128,
+        coins, BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
+        StdError, StdResult, SubMsg, Timestamp, Uint128,
     },
-    cw2::set_contract_version,
+    cw2::{get_contract_version, set_contract_version},
+    semver::Version,
 };
 
 const CONTRACT_NAME: &str = "crates.io:lending";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const PERCENT_DECIMALS: u32 = 5;
-const HUNDRED_PERCENT: u128 = 100 * 10u128.pow(PERCENT_DECIMALS);
+pub(crate) const HUNDRED_PERCENT: u128 = 100 * 10u128.pow(PERCENT_DECIMALS);
 
-const INTEREST_RATE_DECIMALS: u32 = 18;
+pub(crate) const INTEREST_RATE_DECIMALS: u32 = 18;
 const INTEREST_RATE_MULTIPLIER: u128 = 10u128.pow(INTEREST_RATE_DECIMALS);
-const HUNDRED: u128 = 100;
-const YEAR_IN_SECONDS: u128 = 31536000; // 365 days
+pub(crate) const HUNDRED: u128 = 100;
+pub(crate) const YEAR_IN_SECONDS: u128 = 31536000; // 365 days
+
+pub(crate) const USD_DECIMALS: u32 = 8;
+
+const DEFAULT_CLOSE_FACTOR: u128 = 50 * 10u128.pow(PERCENT_DECIMALS); // 50%
+const DEFAULT_LIQUIDATION_BONUS: u128 = 5 * 10u128.pow(PERCENT_DECIMALS); // 5%
+
+const DEFAULT_MAX_CONFIDENCE_RATIO: u128 = 5 * 10u128.pow(PERCENT_DECIMALS); // 5%
+const DEFAULT_MAX_PRICE_AGE: u64 = 60; // 1 minute
 
-const USD_DECIMALS: u32 = 8;
+const DEFAULT_FLASH_LOAN_FEE: u128 = 9 * 10u128.pow(PERCENT_DECIMALS - 2); // 0.09%
+
+const FLASH_LOAN_REPLY_ID: u64 = 1;
 
 pub trait DecimalExt {
     fn to_u128_with_decimals(&self, decimals: u32) -> StdResult<u128>;
@@ -98,10 +167,12 @@ pub fn instantiate(
     // initializing contract with a given parameters
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    STORAGE_VERSION.save(deps.storage, &CURRENT_STORAGE_VERSION)?;
+
     IS_TESTING.save(deps.storage, &msg.is_testing)?;
     IS_PAUSED.save(deps.storage, &false)?;
     PRICE_UPDATER_ADDRESS.save(deps.storage, &msg.price_updater_addr)?;
-    ADMIN.save(deps.storage, &msg.admin)?;
+    ADMIN.save(deps.storage, &deps.api.addr_validate(&msg.admin)?)?;
     PYTH_CONTRACT.save(
         deps.storage,
         &deps.api.addr_validate(msg.pyth_contract_addr.as_ref())?,
@@ -130,9 +201,13 @@ pub fn instantiate(
             token.0.clone(),
             &TotalBorrowData {
                 denom: token.0.clone(),
-                total_borrowed_amount: 0u128,
-                expected_annual_interest_income: 0u128,
-                average_interest_rate: 0u128,
+                total_borrowed_amount: Uint128::zero(),
+                expected_annual_interest_income: Uint128::zero(),
+                average_interest_rate: Uint128::zero(),
+                variable_borrowed_amount: Uint128::zero(),
+                stable_borrowed_amount: Uint128::zero(),
+                stable_average_interest_rate: Uint128::zero(),
+                term_borrowed_amount: Uint128::zero(),
                 timestamp: env.block.time,
             },
         )?;
@@ -156,6 +231,8 @@ pub fn instantiate(
                 denom: params.0,
                 loan_to_value_ratio: params.1,
                 liquidation_threshold: params.2,
+                supply_cap: None,
+                borrow_cap: None,
             },
         )?;
     }
@@ -178,36 +255,159 @@ pub fn instantiate(
 }
 
 pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // the pool can't be used until the flash loan is repaid
+    ensure!(
+        !FLASH_LOAN.exists(deps.storage),
+        ContractError::FlashLoanInProgress {}
+    );
+
+    if is_timelocked(&msg) && get_timelock_delay(deps.as_ref())? > 0 {
+        return execute_queue_action(deps, env, info, msg);
+    }
+
+    execute_msg(deps, env, info, msg)
+}
+
+fn execute_msg(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Deposit {} => execute_deposit_native(deps, env, info),
+        ExecuteMsg::Deposit { on_behalf_of } => {
+            execute_deposit_native(deps, env, info, on_behalf_of)
+        }
         ExecuteMsg::Receive(cw20msg) => execute_cw20_receive(deps, env, info, cw20msg),
-        ExecuteMsg::Redeem { denom, amount } => execute_redeem(deps, env, info, amount, denom),
+        ExecuteMsg::Redeem {
+            denom,
+            amount,
+            recipient,
+        } => execute_redeem(deps, env, info, amount, denom, recipient),
         ExecuteMsg::RemovePriceFeedId { denom } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
-            );
+            ensure_role(deps.as_ref(), &info.sender, Role::OracleManager)?;
 
             PRICE_FEED_IDS.remove(deps.storage, denom.clone());
 
             Ok(Response::default())
         }
+        ExecuteMsg::SetMarketStatus { denom, status } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
+
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
+
+            // wind-down is final, the market can only be removed afterwards
+            let current_status = get_market_status(deps.as_ref(), denom.clone())?;
+            ensure!(
+                matches!(
+                    (&current_status, &status),
+                    (MarketStatus::Active, MarketStatus::Frozen)
+                        | (MarketStatus::Frozen, MarketStatus::Active)
+                        | (MarketStatus::Frozen, MarketStatus::WindDown)
+                ),
+                ContractError::InvalidMarketStatusTransition {}
+            );
+
+            MARKET_STATUS.save(deps.storage, denom.clone(), &status)?;
+
+            Ok(Response::new()
+                .add_attribute("method", "set-market-status")
+                .add_attribute("denom", denom)
+                .add_attribute("status", format!("{:?}", status)))
+        }
         ExecuteMsg::RemoveSupportedToken { denom } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
+            ensure_admin(deps.as_ref(), &info.sender)?;
+
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
+
+            ensure!(
+                get_market_status(deps.as_ref(), denom.clone())? == MarketStatus::WindDown,
+                ContractError::MarketNotWoundDown {}
+            );
+
+            ensure!(
+                get_total_deposited_by_token(deps.as_ref(), env.clone(), denom.clone())?.is_zero()
+                    && get_total_borrowed_by_token(deps.as_ref(), env.clone(), denom.clone())?
+                        .is_zero()
+                    && get_protocol_reserves(deps.as_ref(), env.clone(), denom.clone())?
+                        .accrued_reserves
+                        .is_zero()
+                    && get_insurance_fund(deps.as_ref(), denom.clone())?.is_zero(),
+                ContractError::MarketHasOutstandingBalances {
+                    denom: denom.clone()
+                }
             );
 
+            // price feed ids are kept, they are managed by the oracle manager
             SUPPORTED_TOKENS.remove(deps.storage, denom.clone());
+            MARKET_STATUS.remove(deps.storage, denom.clone());
+            MM_TOKEN_ADDRESS.remove(deps.storage, denom.clone());
+            RESERVE_CONFIGURATION.remove(deps.storage, denom.clone());
+            LIQUIDATION_CONFIGURATION.remove(deps.storage, denom.clone());
+            TOKENS_INTEREST_RATE_MODEL_PARAMS.remove(deps.storage, denom.clone());
+            RATE_STRATEGIES.remove(deps.storage, denom.clone());
+            STABLE_RATE_CONFIGURATION.remove(deps.storage, denom.clone());
+            ADAPTIVE_RATE_STATE.remove(deps.storage, denom.clone());
+            LIQUIDITY_INDEX_DATA.remove(deps.storage, denom.clone());
+            TOTAL_BORROW_DATA.remove(deps.storage, denom.clone());
+            PROTOCOL_RESERVES.remove(deps.storage, denom.clone());
+            INSURANCE_FUND.remove(deps.storage, denom.clone());
+            LIQUIDITY_INDEX_WRITE_DOWN_LN.remove(deps.storage, denom.clone());
+            RESERVE_FACTOR.remove(deps.storage, denom.clone());
+            FLASH_LOAN_FEE.remove(deps.storage, denom.clone());
+            ISOLATION_CONFIGURATION.remove(deps.storage, denom.clone());
+            ORACLE_CONFIGURATION.remove(deps.storage, denom.clone());
+            PRICE_SOURCES.remove(deps.storage, denom.clone());
+
+            // categories stay for the users in them, only the removed asset leaves them
+            let e_mode_categories = E_MODE_CATEGORIES
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            for (id, mut e_mode_category) in e_mode_categories {
+                if e_mode_category.denoms.contains(&denom) {
+                    e_mode_category
+                        .denoms
+                        .retain(|category_denom| *category_denom != denom);
+                    E_MODE_CATEGORIES.save(deps.storage, id, &e_mode_category)?;
+                }
+            }
 
-            Ok(Response::default())
+            let isolated_debt_keys = ISOLATED_DEBT
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+
+            for (isolated_collateral, borrowed_denom) in isolated_debt_keys {
+                if isolated_collateral == denom || borrowed_denom == denom {
+                    ISOLATED_DEBT.remove(deps.storage, (isolated_collateral, borrowed_denom));
+                }
+            }
+
+            remove_market_emission_schedules(&mut deps, env, denom.clone())?;
+
+            let paused_actions = MARKET_PAUSED_ACTIONS
+                .prefix(denom.clone())
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<String>>>()?;
+
+            for action in paused_actions {
+                MARKET_PAUSED_ACTIONS.remove(deps.storage, (denom.clone(), action));
+            }
+
+            Ok(Response::new()
+                .add_attribute("method", "remove-supported-token")
+                .add_attribute("denom", denom))
         }
         ExecuteMsg::AddMarkets {
             denom,
@@ -222,10 +422,12 @@ pub fn execute(
             rate_growth_factor,
             optimal_utilisation_ratio,
         } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
+            ensure_admin(deps.as_ref(), &info.sender)?;
+
+            // listing the market again would reset the state of its deposits and borrows
+            ensure!(
+                !SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::MarketAlreadyExists { denom }
             );
 
             SUPPORTED_TOKENS.save(
@@ -247,6 +449,8 @@ pub fn execute(
                     denom: denom.clone(),
                     loan_to_value_ratio,
                     liquidation_threshold,
+                    supply_cap: None,
+                    borrow_cap: None,
                 },
             )?;
 
@@ -267,9 +471,13 @@ pub fn execute(
                 denom.clone(),
                 &TotalBorrowData {
                     denom: denom.clone(),
-                    total_borrowed_amount: 0u128,
-                    expected_annual_interest_income: 0u128,
-                    average_interest_rate: 0u128,
+                    total_borrowed_amount: Uint128::zero(),
+                    expected_annual_interest_income: Uint128::zero(),
+                    average_interest_rate: Uint128::zero(),
+                    variable_borrowed_amount: Uint128::zero(),
+                    stable_borrowed_amount: Uint128::zero(),
+                    stable_average_interest_rate: Uint128::zero(),
+                    term_borrowed_amount: Uint128::zero(),
                     timestamp: env.block.time,
                 },
             )?;
@@ -286,23 +494,35 @@ pub fn execute(
 
             Ok(Response::default())
         }
-        ExecuteMsg::Borrow { denom, amount } => execute_borrow(deps, env, info, amount, denom),
+        ExecuteMsg::Borrow {
+            denom,
+            amount,
+            rate_mode,
+            recipient,
+            on_behalf_of,
+        } => execute_borrow(
+            deps,
+            env,
+            info,
+            amount,
+            denom,
+            rate_mode.unwrap_or_default(),
+            recipient,
+            on_behalf_of,
+        ),
         ExecuteMsg::UpdatePrice { denom, price } => {
             // if Testing mode, directly set prices for asset
             // if not Testing mode and price argument passed as 0, PRICE_UPDATER_ADDRESS fetching price from Pyth contract, if not available, leaving as is
             if IS_TESTING.load(deps.storage).unwrap() {
-                ensure_eq!(
-                    info.sender.to_string(),
-                    ADMIN.load(deps.storage).unwrap(),
-                    ContractError::ForAdminOnly {}
-                );
+                ensure_role(deps.as_ref(), &info.sender, Role::OracleManager)?;
 
                 ensure!(
                     SUPPORTED_TOKENS.has(deps.storage, denom.clone().unwrap()),
                     ContractError::TokenNotSupported {}
                 );
 
-                PRICES.save(deps.storage, denom.unwrap().clone(), &price.unwrap())?;
+                PRICES.save(deps.storage, denom.clone().unwrap(), &price.unwrap())?;
+                PRICE_TIMESTAMPS.save(deps.storage, denom.unwrap(), &env.block.time)?;
             } else {
                 ensure_eq!(
                     info.sender.to_string(),
@@ -322,21 +542,40 @@ pub fn execute(
                     {
                         let pyth_contract = PYTH_CONTRACT.load(deps.storage)?;
 
-                        let price_identifier =
-                            PRICE_FEED_IDS.load(deps.storage, token.denom.clone())?;
+                        // markets priced by other sources may have no Pyth feed
+                        let Some(price_identifier) =
+                            PRICE_FEED_IDS.may_load(deps.storage, token.denom.clone())?
+                        else {
+                            continue;
+                        };
 
                         let price_feed_response: PriceFeedResponse =
                             query_price_feed(&deps.querier, pyth_contract, price_identifier)?;
                         let price_feed = price_feed_response.price_feed;
 
-                        let pyth_current_price =
-                            price_feed.get_price_no_older_than(env.block.time.seconds() as i64, 60);
+                        let oracle_configuration =
+                            get_oracle_configuration(deps.as_ref(), token.denom.clone())?;
 
-                        if pyth_current_price.is_some() {
+                        let pyth_current_price = price_feed.get_price_no_older_than(
+                            env.block.time.seconds() as i64,
+                            oracle_configuration.max_price_age,
+                        );
+
+                        if let Some(pyth_current_price) = pyth_current_price {
                             PRICES.save(
+                                deps.storage,
+                                token.denom.clone(),
+                                &normalize_pyth_price(
+                                    pyth_current_price,
+                                    oracle_configuration.max_confidence_ratio,
+                                )?,
+                            )?;
+
+                            // cached price is as fresh as its Pyth publish time
+                            PRICE_TIMESTAMPS.save(
                                 deps.storage,
                                 token.denom,
-                                &(pyth_current_price.unwrap().price as u128),
+                                &Timestamp::from_seconds(pyth_current_price.publish_time as u64),
                             )?;
                         }
                     }
@@ -349,12 +588,10 @@ pub fn execute(
             denom,
             loan_to_value_ratio,
             liquidation_threshold,
+            supply_cap,
+            borrow_cap,
         } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
-            );
+            ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
 
             ensure!(
                 SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
@@ -368,314 +605,506 @@ pub fn execute(
                     denom: denom.clone(),
                     loan_to_value_ratio,
                     liquidation_threshold,
+                    supply_cap,
+                    borrow_cap,
                 },
             )?;
 
             Ok(Response::default())
         }
-        ExecuteMsg::SetTokenInterestRateModelParams {
+        ExecuteMsg::SetLiquidationConfiguration {
             denom,
-            min_interest_rate,
-            safe_borrow_max_rate,
-            rate_growth_factor,
-            optimal_utilisation_ratio,
+            close_factor,
+            liquidation_bonus,
         } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
-            );
+            ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
 
             ensure!(
                 SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
                 ContractError::TokenNotSupported {}
             );
 
-            TOKENS_INTEREST_RATE_MODEL_PARAMS.save(
+            ensure!(
+                close_factor > 0
+                    && close_factor <= HUNDRED_PERCENT
+                    && liquidation_bonus < HUNDRED_PERCENT,
+                ContractError::InvalidLiquidationConfiguration {}
+            );
+
+            LIQUIDATION_CONFIGURATION.save(
                 deps.storage,
                 denom.clone(),
-                &TokenInterestRateModelParams {
+                &LiquidationConfiguration {
                     denom: denom.clone(),
-                    min_interest_rate,
-                    safe_borrow_max_rate,
-                    rate_growth_factor,
-                    optimal_utilisation_ratio,
+                    close_factor,
+                    liquidation_bonus,
                 },
             )?;
 
             Ok(Response::default())
         }
-        ExecuteMsg::ToggleCollateralSetting { denom } => {
-            ensure_ne!(
-                true,
-                is_paused(deps.as_ref())?,
-                ContractError::ProtocolIsPaused {}
-            );
-
-            let use_user_deposit_as_collateral =
-                user_deposit_as_collateral(deps.as_ref(), info.sender.to_string(), denom.clone())
-                    .unwrap();
-
-            if use_user_deposit_as_collateral {
-                let user_token_balance = get_deposit(
-                    deps.as_ref(),
-                    env.clone(),
-                    info.sender.to_string(),
-                    denom.clone(),
-                )
-                    .unwrap()
-                    .balance
-                    .u128();
+        ExecuteMsg::SetOracleConfiguration {
+            denom,
+            max_confidence_ratio,
+            max_price_age,
+        } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::OracleManager)?;
 
-                if user_token_balance != 0 {
-                    let token_decimals = get_token_decimal(deps.as_ref(), denom.clone())
-                        .unwrap()
-                        .u128() as u32;
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
 
-                    let price = fetch_price_by_token(deps.as_ref(), env.clone(), denom.clone())
-                        .unwrap()
-                        .u128();
+            ensure!(
+                max_confidence_ratio > 0
+                    && max_confidence_ratio <= HUNDRED_PERCENT
+                    && max_price_age > 0,
+                ContractError::InvalidOracleConfiguration {}
+            );
 
-                    let user_token_balance_usd =
-                        Decimal::from_i128_with_scale(user_token_balance as i128, token_decimals)
-                            .mul(Decimal::from_i128_with_scale(price as i128, USD_DECIMALS))
-                            .to_u128_with_decimals(USD_DECIMALS)
-                            .unwrap();
+            ORACLE_CONFIGURATION.save(
+                deps.storage,
+                denom.clone(),
+                &OracleConfiguration {
+                    denom: denom.clone(),
+                    max_confidence_ratio,
+                    max_price_age,
+                },
+            )?;
 
-                    let sum_collateral_balance_usd = get_user_collateral_usd(
-                        deps.as_ref(),
-                        env.clone(),
-                        info.sender.to_string(),
-                    )
-                        .unwrap()
-                        .u128();
+            Ok(Response::default())
+        }
+        ExecuteMsg::SetPriceSources {
+            denom,
+            primary,
+            secondary,
+            max_deviation,
+        } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::OracleManager)?;
 
-                    let sum_borrow_balance_usd =
-                        get_user_borrowed_usd(deps.as_ref(), env.clone(), info.sender.to_string())
-                            .unwrap()
-                            .u128();
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
 
-                    let user_liquidation_threshold = get_user_liquidation_threshold(
-                        deps.as_ref(),
-                        env.clone(),
-                        info.sender.to_string(),
-                    )
-                        .unwrap()
-                        .u128();
+            ensure!(
+                max_deviation <= HUNDRED_PERCENT,
+                ContractError::InvalidPriceSources {}
+            );
 
-                    assert!(
-                        sum_borrow_balance_usd * HUNDRED_PERCENT / user_liquidation_threshold < sum_collateral_balance_usd - user_token_balance_usd,
-                        "The collateral has already using to collateralise the borrowing. Not enough available balance"
-                    );
-                }
+            for source in std::iter::once(&primary).chain(secondary.iter()) {
+                validate_price_source(deps.as_ref(), denom.clone(), source)?;
             }
 
-            USER_DEPOSIT_AS_COLLATERAL.save(
+            PRICE_SOURCES.save(
                 deps.storage,
-                (info.sender.to_string(), denom.clone()),
-                &!use_user_deposit_as_collateral,
+                denom.clone(),
+                &PriceSources {
+                    denom: denom.clone(),
+                    primary,
+                    secondary,
+                    max_deviation,
+                },
             )?;
 
-            Ok(Response::new())
+            Ok(Response::default())
         }
-        ExecuteMsg::Liquidation { user } => {
-            for token in get_supported_tokens(deps.as_ref())
-                .unwrap()
-                .supported_tokens
-            {
-                let liquidator_borrow_amount_with_interest = get_user_borrow_amount_with_interest(
-                    deps.as_ref(),
-                    env.clone(),
-                    info.sender.to_string(),
-                    token.denom.clone(),
-                )
-                    .unwrap()
-                    .u128();
-
-                assert_eq!(
-                    liquidator_borrow_amount_with_interest, 0,
-                    "Liquidator can't have any borrows"
-                );
-            }
-
-            let user_utilization_rate =
-                get_user_utilization_rate(deps.as_ref(), env.clone(), user.clone()).unwrap();
+        ExecuteMsg::FlashLoan {
+            assets,
+            recipient,
+            msg,
+        } => execute_flash_loan(deps, env, info, assets, recipient, msg),
+        ExecuteMsg::SetFlashLoanFee {
+            denom,
+            flash_loan_fee,
+        } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
 
-            let user_liquidation_threshold =
-                get_user_liquidation_threshold(deps.as_ref(), env.clone(), user.clone()).unwrap();
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
 
-            assert!(
-                user_utilization_rate >= user_liquidation_threshold,
-                "User borrowing has not reached the threshold of liquidation"
+            ensure!(
+                flash_loan_fee < HUNDRED_PERCENT,
+                ContractError::InvalidFlashLoanFee {}
             );
 
-            for token in get_supported_tokens(deps.as_ref())
-                .unwrap()
-                .supported_tokens
-            {
-                execute_update_liquidity_index_data(&mut deps, env.clone(), token.denom.clone())?;
+            FLASH_LOAN_FEE.save(deps.storage, denom, &flash_loan_fee)?;
 
-                let use_user_deposit_as_collateral =
-                    user_deposit_as_collateral(deps.as_ref(), user.clone(), token.denom.clone())
-                        .unwrap();
+            Ok(Response::default())
+        }
+        ExecuteMsg::SetEModeCategory {
+            id,
+            label,
+            loan_to_value_ratio,
+            liquidation_threshold,
+            price_source,
+            denoms,
+        } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
 
-                let mut user_token_balance = 0u128;
-                if use_user_deposit_as_collateral {
-                    user_token_balance = get_deposit(
-                        deps.as_ref(),
-                        env.clone(),
-                        user.clone(),
-                        token.denom.clone(),
-                    )
-                        .unwrap()
-                        .balance
-                        .u128();
+            ensure!(
+                !denoms.is_empty()
+                    && loan_to_value_ratio <= liquidation_threshold
+                    && liquidation_threshold < HUNDRED_PERCENT,
+                ContractError::InvalidEModeCategory {}
+            );
 
-                    USER_MM_TOKEN_BALANCE.save(
-                        deps.storage,
-                        (user.clone(), token.denom.clone()),
-                        &Uint128::from(0u128),
-                    )?;
+            for denom in denoms.iter() {
+                ensure!(
+                    SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                    ContractError::TokenNotSupported {}
+                );
+
+                if let Some(price_source) = &price_source {
+                    validate_price_source(deps.as_ref(), denom.clone(), price_source)?;
                 }
+            }
 
-                let user_borrow_amount_with_interest = get_user_borrow_amount_with_interest(
-                    deps.as_ref(),
-                    env.clone(),
-                    user.clone(),
-                    token.denom.clone(),
-                )
-                    .unwrap()
-                    .u128();
+            E_MODE_CATEGORIES.save(
+                deps.storage,
+                id,
+                &EModeCategory {
+                    id,
+                    label,
+                    loan_to_value_ratio,
+                    liquidation_threshold,
+                    price_source,
+                    denoms,
+                },
+            )?;
 
-                if user_borrow_amount_with_interest > 0 || user_token_balance > 0 {
-                    let liquidator_balance = get_deposit(
-                        deps.as_ref(),
-                        env.clone(),
-                        info.sender.to_string(),
-                        token.denom.clone(),
-                    )
-                        .unwrap()
-                        .balance
-                        .u128();
+            Ok(Response::default())
+        }
+        ExecuteMsg::SetUserEMode { category_id } => {
+            ensure_ne!(
+                true,
+                is_paused(deps.as_ref())?,
+                ContractError::ProtocolIsPaused {}
+            );
 
-                    let token_decimals = get_token_decimal(deps.as_ref(), token.denom.clone())
-                        .unwrap()
-                        .u128() as u32;
+            match category_id {
+                Some(category_id) => {
+                    ensure!(
+                        E_MODE_CATEGORIES.has(deps.storage, category_id),
+                        ContractError::EModeCategoryNotFound {}
+                    );
 
-                    if user_borrow_amount_with_interest > 0 {
-                        assert!(
-                            liquidator_balance >= user_borrow_amount_with_interest,
-                            "The liquidator does not have enough deposit balance for liquidation"
-                        );
+                    USER_E_MODE.save(deps.storage, info.sender.to_string(), &category_id)?;
+                }
+                None => USER_E_MODE.remove(deps.storage, info.sender.to_string()),
+            }
 
-                        let user_borrowing_info = get_user_borrowing_info(
+            // position must stay healthy with the parameters the user switches to
+            let user_borrowed_usd =
+                get_user_borrowed_usd(deps.as_ref(), env.clone(), info.sender.to_string())?;
+
+            ensure!(
+                user_borrowed_usd.is_zero()
+                    || user_borrowed_usd
+                        <= get_user_max_allowed_borrow_amount_usd(
                             deps.as_ref(),
                             env.clone(),
-                            user.clone(),
-                            token.denom.clone(),
-                        )
-                            .unwrap();
-
-                        let new_user_borrowing_info = UserBorrowingInfo {
-                            borrowed_amount: Uint128::from(0u128),
-                            average_interest_rate: Uint128::zero(),
-                            timestamp: env.block.time,
-                        };
-
-                        let total_borrow_data =
-                            get_total_borrow_data(deps.as_ref(), token.denom.clone())
-                                .unwrap_or_default();
-
-                        let expected_annual_interest_income = total_borrow_data
-                            .expected_annual_interest_income
-                            - Decimal::from_i128_with_scale(
-                            (user_borrowing_info.borrowed_amount.u128()) as i128,
-                            token_decimals,
-                        )
-                            .mul(Decimal::from_i128_with_scale(
-                                (user_borrowing_info.average_interest_rate.u128() / HUNDRED)
-                                    as i128,
-                                INTEREST_RATE_DECIMALS,
-                            ))
-                            .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-                            .unwrap();
-
-                        let total_borrowed_amount = total_borrow_data.total_borrowed_amount
-                            - user_borrowing_info.borrowed_amount.u128();
-
-                        let mut total_average_interest_rate = 0u128;
-                        if total_borrowed_amount != 0u128 {
-                            total_average_interest_rate = HUNDRED
-                                * Decimal::from_i128_with_scale(
-                                expected_annual_interest_income as i128,
-                                INTEREST_RATE_DECIMALS,
-                            )
-                                .div(Decimal::from_i128_with_scale(
-                                    total_borrowed_amount as i128,
-                                    token_decimals,
-                                ))
-                                .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-                                .unwrap();
-                        }
+                            info.sender.to_string(),
+                        )?,
+                ContractError::EModeChangeUndercollateralized {}
+            );
 
-                        let new_total_borrow_data = TotalBorrowData {
-                            denom: token.denom.clone(),
-                            total_borrowed_amount: total_borrowed_amount,
-                            expected_annual_interest_income: expected_annual_interest_income,
-                            average_interest_rate: total_average_interest_rate,
-                            timestamp: env.block.time,
-                        };
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "set_user_e_mode"),
+                attr("user", info.sender.to_string()),
+                attr(
+                    "category_id",
+                    category_id.map_or("none".to_string(), |id| id.to_string()),
+                ),
+            ]))
+        }
+        ExecuteMsg::SetIsolationConfiguration {
+            denom,
+            is_isolated,
+            debt_ceiling,
+            borrowable_in_isolation,
+        } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
 
-                        USER_BORROWING_INFO.save(
-                            deps.storage,
-                            (user.clone(), token.denom.clone()),
-                            &new_user_borrowing_info,
-                        )?;
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
 
-                        TOTAL_BORROW_DATA.save(
-                            deps.storage,
-                            token.denom.clone(),
-                            &new_total_borrow_data,
-                        )?;
-                    }
+            ISOLATION_CONFIGURATION.save(
+                deps.storage,
+                denom.clone(),
+                &IsolationConfiguration {
+                    denom: denom.clone(),
+                    is_isolated,
+                    debt_ceiling,
+                    borrowable_in_isolation,
+                },
+            )?;
+
+            Ok(Response::default())
+        }
+        ExecuteMsg::SetReserveFactor {
+            denom,
+            reserve_factor,
+        } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
 
-                    let new_liquidator_token_balance =
-                        liquidator_balance + user_token_balance - user_borrow_amount_with_interest;
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
 
-                    let mm_token_price =
-                        get_mm_token_price(deps.as_ref(), env.clone(), token.denom.clone())
-                            .unwrap()
-                            .u128();
+            ensure!(
+                reserve_factor < HUNDRED_PERCENT,
+                ContractError::InvalidReserveFactor {}
+            );
 
-                    let new_liquidator_mm_token_balance = Decimal::from_i128_with_scale(
-                        new_liquidator_token_balance as i128,
-                        token_decimals,
-                    )
-                        .div(Decimal::from_i128_with_scale(
-                            mm_token_price as i128,
-                            token_decimals,
-                        ))
-                        .to_u128_with_decimals(token_decimals)
-                        .unwrap();
+            // interest accrued so far is split with the previous reserve factor
+            execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+
+            RESERVE_FACTOR.save(deps.storage, denom, &reserve_factor)?;
+
+            Ok(Response::default())
+        }
+        ExecuteMsg::WithdrawReserves {
+            denom,
+            amount,
+            recipient,
+        } => execute_withdraw_reserves(deps, env, info, denom, amount, recipient),
+        ExecuteMsg::SetTokenInterestRateModelParams {
+            denom,
+            min_interest_rate,
+            safe_borrow_max_rate,
+            rate_growth_factor,
+            optimal_utilisation_ratio,
+        } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
+
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
+
+            TOKENS_INTEREST_RATE_MODEL_PARAMS.save(
+                deps.storage,
+                denom.clone(),
+                &TokenInterestRateModelParams {
+                    denom: denom.clone(),
+                    min_interest_rate,
+                    safe_borrow_max_rate,
+                    rate_growth_factor,
+                    optimal_utilisation_ratio,
+                },
+            )?;
+
+            Ok(Response::default())
+        }
+        ExecuteMsg::SetRateStrategy { denom, strategy } => {
+            execute_set_rate_strategy(deps, env, info, denom, strategy)
+        }
+        ExecuteMsg::SetStableRateConfiguration {
+            denom,
+            min_interest_rate,
+            safe_borrow_max_rate,
+            rate_growth_factor,
+            optimal_utilisation_ratio,
+            rebalance_utilisation_ratio,
+        } => execute_set_stable_rate_configuration(
+            deps,
+            info,
+            StableRateConfiguration {
+                denom,
+                min_interest_rate,
+                safe_borrow_max_rate,
+                rate_growth_factor,
+                optimal_utilisation_ratio,
+                rebalance_utilisation_ratio,
+            },
+        ),
+        ExecuteMsg::RebalanceStableRate { user, denom } => {
+            execute_rebalance_stable_rate(deps, env, user, denom)
+        }
+        ExecuteMsg::AddEmissionSchedule {
+            denom,
+            side,
+            reward_denom,
+            emission_per_second,
+            start,
+            end,
+        } => execute_add_emission_schedule(
+            deps,
+            env,
+            info,
+            denom,
+            side,
+            reward_denom,
+            emission_per_second,
+            start,
+            end,
+        ),
+        ExecuteMsg::ClaimRewards {} => execute_claim_rewards(deps, env, info),
+        ExecuteMsg::SweepUndistributedRewards { recipient } => {
+            execute_sweep_undistributed_rewards(deps, env, info, recipient)
+        }
+        ExecuteMsg::FundInsuranceFund {} => execute_fund_insurance_fund_native(deps, info),
+        ExecuteMsg::WriteOffBadDebt { user } => execute_write_off_bad_debt(deps, env, info, user),
+        ExecuteMsg::WithdrawInsuranceFund {
+            denom,
+            amount,
+            recipient,
+        } => execute_withdraw_insurance_fund(deps, info, denom, amount, recipient),
+        ExecuteMsg::ApproveDelegation {
+            delegatee,
+            denom,
+            amount,
+            expiry,
+        } => execute_approve_delegation(deps, env, info, delegatee, denom, amount, expiry),
+        ExecuteMsg::RevokeDelegation { delegatee, denom } => {
+            execute_revoke_delegation(deps, info, delegatee, denom)
+        }
+        ExecuteMsg::RepayTermLoan { loan_id } => {
+            execute_repay_term_loan_native(deps, env, info, loan_id)
+        }
+        ExecuteMsg::LiquidateTermLoan {
+            loan_id,
+            collateral_denom,
+            receive_mm_token,
+        } => {
+            let borrower = load_term_loan(deps.as_ref(), loan_id)?.borrower;
+
+            execute_liquidation_native(
+                deps,
+                env,
+                info,
+                borrower,
+                collateral_denom,
+                receive_mm_token,
+                Some(loan_id),
+            )
+        }
+        ExecuteMsg::ToggleCollateralSetting { denom } => {
+            ensure_ne!(
+                true,
+                is_paused(deps.as_ref())?,
+                ContractError::ProtocolIsPaused {}
+            );
+
+            let use_user_deposit_as_collateral =
+                user_deposit_as_collateral(deps.as_ref(), info.sender.to_string(), denom.clone())
+                    .unwrap();
+
+            if !use_user_deposit_as_collateral {
+                // isolated collateral is the only collateral of the user
+                let is_isolated =
+                    get_isolation_configuration(deps.as_ref(), denom.clone())?.is_isolated;
+
+                for token in get_supported_tokens(deps.as_ref())?.supported_tokens {
+                    if token.denom != denom
+                        && user_deposit_as_collateral(
+                            deps.as_ref(),
+                            info.sender.to_string(),
+                            token.denom.clone(),
+                        )?
+                    {
+                        ensure!(
+                            !is_isolated
+                                && !get_isolation_configuration(deps.as_ref(), token.denom)?
+                                    .is_isolated,
+                            ContractError::MixedIsolatedCollateral {}
+                        );
+                    }
+                }
+            }
 
-                    USER_MM_TOKEN_BALANCE.save(
-                        deps.storage,
-                        (info.sender.to_string(), token.denom.clone()),
-                        &Uint128::from(new_liquidator_mm_token_balance),
+            if use_user_deposit_as_collateral {
+                // disabling the collateral changes the health of the borrowing
+                if !get_user_borrowed_usd(deps.as_ref(), env.clone(), info.sender.to_string())?
+                    .is_zero()
+                {
+                    ensure_fresh_prices(
+                        deps.as_ref(),
+                        env.clone(),
+                        info.sender.to_string(),
+                        denom.clone(),
                     )?;
                 }
+
+                let user_token_balance = get_deposit(
+                    deps.as_ref(),
+                    env.clone(),
+                    info.sender.to_string(),
+                    denom.clone(),
+                )
+                    .unwrap()
+                    .balance
+                    .u128();
+
+                if user_token_balance != 0 {
+                    assert!(
+                        is_collateral_release_allowed(
+                            deps.as_ref(),
+                            env.clone(),
+                            info.sender.to_string(),
+                            denom.clone(),
+                            user_token_balance,
+                        )?,
+                        "The collateral has already using to collateralise the borrowing. Not enough available balance"
+                    );
+                }
             }
 
+            USER_DEPOSIT_AS_COLLATERAL.save(
+                deps.storage,
+                (info.sender.to_string(), denom.clone()),
+                &!use_user_deposit_as_collateral,
+            )?;
+
             Ok(Response::new())
         }
-        ExecuteMsg::Repay {} => execute_repay_native(deps, env, info),
-        ExecuteMsg::UpdatePythContract { pyth_contract_addr } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
+        ExecuteMsg::Liquidation {
+            user,
+            collateral_denom,
+            receive_mm_token,
+        } => execute_liquidation_native(
+            deps,
+            env,
+            info,
+            user,
+            collateral_denom,
+            receive_mm_token,
+            None,
+        ),
+        ExecuteMsg::Repay { on_behalf_of } => execute_repay_native(deps, env, info, on_behalf_of),
+        ExecuteMsg::RegisterMmToken {
+            denom,
+            mm_token_address,
+        } => {
+            ensure_admin(deps.as_ref(), &info.sender)?;
+
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
             );
 
+            MM_TOKEN_ADDRESS.save(
+                deps.storage,
+                denom,
+                &deps.api.addr_validate(mm_token_address.as_ref())?,
+            )?;
+
+            Ok(Response::default())
+        }
+        ExecuteMsg::TransferMmToken {
+            sender,
+            recipient,
+            amount,
+        } => execute_transfer_mm_token(deps, env, info, sender, recipient, amount),
+        ExecuteMsg::UpdatePythContract { pyth_contract_addr } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::OracleManager)?;
+
             PYTH_CONTRACT.save(
                 deps.storage,
                 &deps.api.addr_validate(pyth_contract_addr.as_ref())?,
@@ -684,11 +1113,7 @@ pub fn execute(
             Ok(Response::default())
         }
         ExecuteMsg::AddPriceFeedIds { price_ids } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
-            );
+            ensure_role(deps.as_ref(), &info.sender, Role::OracleManager)?;
 
             assert!(!price_ids.is_empty(), "Couldn't pass empty parameters");
 
@@ -700,34 +1125,57 @@ pub fn execute(
             Ok(Response::default())
         }
         ExecuteMsg::UpdatePriceUpdaterAddr { price_updater_addr } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
-            );
+            ensure_role(deps.as_ref(), &info.sender, Role::OracleManager)?;
 
             PRICE_UPDATER_ADDRESS.save(deps.storage, &price_updater_addr)?;
 
             Ok(Response::default())
         }
-        ExecuteMsg::UpdateAdmin { admin } => {
-            ensure_eq!(
-                info.sender.to_string(),
-                ADMIN.load(deps.storage).unwrap(),
-                ContractError::ForAdminOnly {}
-            );
-
-            ADMIN.save(deps.storage, &admin)?;
-
-            Ok(Response::default())
+        ExecuteMsg::TransferOwnership { new_owner, expiry } => {
+            execute_transfer_ownership(deps, env, info, new_owner, expiry)
+        }
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipTransfer {} => execute_cancel_ownership_transfer(deps, info),
+        ExecuteMsg::GrantRole { role, address } => {
+            execute_update_role(deps, info, role, address, true)
+        }
+        ExecuteMsg::RevokeRole { role, address } => {
+            execute_update_role(deps, info, role, address, false)
         }
         ExecuteMsg::SetPause { value } => {
+            ensure_role(deps.as_ref(), &info.sender, Role::PauseGuardian)?;
+
             IS_PAUSED.save(deps.storage, &value)?;
 
             Ok(Response::new()
                 .add_attribute("method", "set-pause")
                 .add_attribute("is_paused", format!("{}", value)))
         }
+        ExecuteMsg::SetActionPause {
+            denom,
+            action,
+            value,
+        } => execute_set_action_pause(deps, info, denom, action, value),
+        ExecuteMsg::SetTimelockDelay { delay } => execute_set_timelock_delay(deps, info, delay),
+        ExecuteMsg::ExecuteQueuedAction { id } => {
+            let queued_action = take_ready_action(deps.branch(), env.clone(), id)?;
+
+            // the action runs with the permissions of the proposer
+            let resp = execute_msg(
+                deps,
+                env,
+                MessageInfo {
+                    sender: queued_action.proposer,
+                    funds: vec![],
+                },
+                queued_action.msg,
+            )?;
+
+            Ok(resp
+                .add_attribute("method", "execute-queued-action")
+                .add_attribute("id", id.to_string()))
+        }
+        ExecuteMsg::CancelQueuedAction { id } => execute_cancel_queued_action(deps, info, id),
     }
 }
 
@@ -736,11 +1184,21 @@ pub fn execute_update_liquidity_index_data(
     env: Env,
     denom: String,
 ) -> StdResult<Response> {
+    // the adaptive rate drifts with the utilisation before the balances change
+    update_adaptive_rate(deps, env.clone(), denom.clone())?;
+
     let current_liquidity_index_ln =
         get_current_liquidity_index_ln(deps.as_ref(), env.clone(), denom.clone())
             .unwrap()
             .u128();
 
+    // protocol reserves accrue over the same interval as the liquidity index
+    let accrued_reserves = get_protocol_reserves(deps.as_ref(), env.clone(), denom.clone())?
+        .accrued_reserves
+        .u128();
+
+    PROTOCOL_RESERVES.save(deps.storage, denom.clone(), &accrued_reserves)?;
+
     let new_liquidity_index_data = LiquidityIndexData {
         denom: denom.clone(),
         liquidity_index_ln: current_liquidity_index_ln,
@@ -752,6 +1210,90 @@ pub fn execute_update_liquidity_index_data(
     Ok(Response::new().add_attribute("liquidity_index", format!("{}", env.block.time)))
 }
 
+pub fn execute_distribute_to_depositors(
+    deps: &mut DepsMut,
+    env: Env,
+    denom: String,
+    amount: u128,
+) -> StdResult<()> {
+    // raises the liquidity index so the amount is shared by mmToken holders pro rata
+    execute_update_liquidity_index_data(deps, env.clone(), denom.clone())?;
+
+    let total_deposited = get_total_deposited_by_token(deps.as_ref(), env, denom.clone())?.u128();
+
+    // with no depositors the amount is kept as protocol reserves
+    if total_deposited == 0 {
+        PROTOCOL_RESERVES.update(deps.storage, denom, |protocol_reserves| -> StdResult<_> {
+            Ok(protocol_reserves.unwrap_or_default() + amount)
+        })?;
+
+        return Ok(());
+    }
+
+    let token_decimals = get_token_decimal(deps.as_ref(), denom.clone())?.u128() as u32;
+
+    let liquidity_index_increase_ln =
+        Decimal::from_i128_with_scale((total_deposited + amount) as i128, token_decimals)
+            .div(Decimal::from_i128_with_scale(
+                total_deposited as i128,
+                token_decimals,
+            ))
+            .ln()
+            .to_u128_with_decimals(INTEREST_RATE_DECIMALS)?;
+
+    let mut liquidity_index_data = LIQUIDITY_INDEX_DATA.load(deps.storage, denom.clone())?;
+    liquidity_index_data.liquidity_index_ln += liquidity_index_increase_ln;
+
+    LIQUIDITY_INDEX_DATA.save(deps.storage, denom, &liquidity_index_data)?;
+
+    Ok(())
+}
+
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+
+    ensure_eq!(
+        stored.contract,
+        CONTRACT_NAME,
+        ContractError::InvalidContractName {
+            contract: stored.contract
+        }
+    );
+
+    let stored_version: Version = stored
+        .version
+        .parse()
+        .map_err(|_| ContractError::InvalidContractVersion {})?;
+    let new_version: Version = CONTRACT_VERSION
+        .parse()
+        .map_err(|_| ContractError::InvalidContractVersion {})?;
+
+    ensure!(
+        stored_version <= new_version,
+        ContractError::ContractDowngradeNotAllowed {
+            from_version: stored.version
+        }
+    );
+
+    let from_storage_version = migrate_storage(deps.storage)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("from_storage_version", from_storage_version.to_string())
+        .add_attribute("to_storage_version", CURRENT_STORAGE_VERSION.to_string()))
+}
+
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        FLASH_LOAN_REPLY_ID => execute_flash_loan_repayment_check(deps, env),
+        id => Err(StdError::generic_err(format!("Unknown reply id: {}", id)).into()),
+    }
+}
+
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetDeposit { address, denom } => {
@@ -767,14 +1309,86 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetUserBorrowingInfo { address, denom } => {
             to_json_binary(&query::get_user_borrowing_info(deps, env, address, denom)?)
         }
+        QueryMsg::GetUserStableBorrowingInfo { address, denom } => {
+            to_json_binary(&get_user_stable_borrowing_info(deps, env, address, denom)?)
+        }
         QueryMsg::GetTotalBorrowData { denom } => {
             to_json_binary(&query::get_total_borrow_data(deps, denom)?)
         }
         QueryMsg::GetSupportedTokens {} => to_json_binary(&get_supported_tokens(deps)?),
         QueryMsg::GetReserveConfiguration {} => to_json_binary(&get_reserve_configuration(deps)?),
+        QueryMsg::GetMarketCapsHeadroom { denom } => {
+            to_json_binary(&get_market_caps_headroom(deps, env, denom)?)
+        }
+        QueryMsg::GetLiquidationConfiguration { denom } => {
+            to_json_binary(&get_liquidation_configuration(deps, denom)?)
+        }
+        QueryMsg::GetMmTokenAddress { denom } => {
+            to_json_binary(&get_mm_token_address(deps, denom)?)
+        }
+        QueryMsg::GetMmTokenBalance { address, denom } => {
+            to_json_binary(&get_mm_token_balance(deps, address, denom)?)
+        }
+        QueryMsg::GetTotalMmTokenSupply { denom } => {
+            to_json_binary(&get_total_mm_token_supply(deps, denom)?)
+        }
+        QueryMsg::GetOracleConfiguration { denom } => {
+            to_json_binary(&get_oracle_configuration(deps, denom)?)
+        }
+        QueryMsg::GetPriceSources { denom } => {
+            to_json_binary(&get_price_sources(deps, env, denom)?)
+        }
+        QueryMsg::GetFlashLoanFee { denom } => to_json_binary(&get_flash_loan_fee(deps, denom)?),
+        QueryMsg::GetEModeCategories {} => to_json_binary(&get_e_mode_categories(deps)?),
+        QueryMsg::GetUserEMode { address } => to_json_binary(&get_user_e_mode(deps, address)?),
+        QueryMsg::GetIsolationConfiguration { denom } => {
+            to_json_binary(&get_isolation_configuration(deps, denom)?)
+        }
+        QueryMsg::GetMarketStatus { denom } => to_json_binary(&get_market_status(deps, denom)?),
+        QueryMsg::GetIsolatedDebt { denom } => {
+            to_json_binary(&get_isolated_debt(deps, env, denom)?)
+        }
+        QueryMsg::GetProtocolReserves { denom } => {
+            to_json_binary(&get_protocol_reserves(deps, env, denom)?)
+        }
+        QueryMsg::GetInsuranceFund { denom } => to_json_binary(&get_insurance_fund(deps, denom)?),
+        QueryMsg::GetBadDebtWriteOffs {} => to_json_binary(&get_bad_debt_write_offs(deps)?),
+        QueryMsg::GetBorrowAllowance {
+            delegator,
+            delegatee,
+            denom,
+        } => to_json_binary(&get_borrow_allowance(
+            deps, env, delegator, delegatee, denom,
+        )?),
         QueryMsg::GetTokensInterestRateModelParams {} => {
             to_json_binary(&get_tokens_interest_rate_model_params(deps)?)
         }
+        QueryMsg::GetRateStrategy { denom } => to_json_binary(&get_rate_strategy(deps, denom)?),
+        QueryMsg::GetStableInterestRate { denom } => {
+            to_json_binary(&get_stable_interest_rate(deps, env, denom)?)
+        }
+        QueryMsg::GetStableRateConfiguration { denom } => {
+            to_json_binary(&get_stable_rate_configuration(deps, denom)?)
+        }
+        QueryMsg::GetTermLoan { loan_id } => {
+            to_json_binary(&TERM_LOANS.load(deps.storage, loan_id)?)
+        }
+        QueryMsg::GetTermLoanAmountWithInterest { loan_id } => {
+            to_json_binary(&get_term_loan_amount_with_interest(
+                deps,
+                env.clone(),
+                &TERM_LOANS.load(deps.storage, loan_id)?,
+            )?)
+        }
+        QueryMsg::GetUserTermLoans { address } => {
+            to_json_binary(&get_user_term_loans(deps, address)?)
+        }
+        QueryMsg::GetOverdueTermLoans {} => to_json_binary(&get_overdue_term_loans(deps, env)?),
+        QueryMsg::GetEmissionSchedules {} => to_json_binary(&get_emission_schedules(deps)?),
+        QueryMsg::GetPendingRewards { address } => {
+            to_json_binary(&get_pending_rewards(deps, env, address)?)
+        }
+        QueryMsg::GetUndistributedRewards {} => to_json_binary(&get_undistributed_rewards(deps)?),
         QueryMsg::GetInterestRate { denom } => {
             to_json_binary(&get_interest_rate(deps, env, denom)?)
         }
@@ -829,16 +1443,23 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetUserMaxAllowedBorrowAmountUsd { address } => {
             to_json_binary(&get_user_max_allowed_borrow_amount_usd(deps, env, address)?)
         }
+        QueryMsg::GetAccountSnapshot { address } => {
+            to_json_binary(&get_account_snapshot(deps, env, address)?)
+        }
         QueryMsg::GetAllUsersWithBorrows {} => {
             to_json_binary(&get_all_users_with_borrows(deps, env)?)
         }
         QueryMsg::GetPythContract {} => to_json_binary(&get_pyth_contract(deps)?),
         QueryMsg::GetPriceFeedIds {} => to_json_binary(&get_pyth_price_feed_ids(deps)?),
+        QueryMsg::GetRoles {} => to_json_binary(&get_roles(deps)?),
         QueryMsg::GetAdmin {} => to_json_binary(&get_admin(deps)?),
         QueryMsg::GetUserBalances { address } => {
             to_json_binary(&get_users_balances(deps, env, address)?)
         }
         QueryMsg::IsPaused {} => to_json_binary(&is_paused(deps)?),
+        QueryMsg::GetPauseState {} => to_json_binary(&get_pause_state(deps)?),
+        QueryMsg::GetTimelockDelay {} => to_json_binary(&get_timelock_delay(deps)?),
+        QueryMsg::GetPendingActions {} => to_json_binary(&get_pending_actions(deps)?),
     }
 }
 
@@ -847,18 +1468,19 @@ pub mod query {
     use std::ops::Mul;
 
     use crate::msg::{
-        GetBalanceResponse, GetReserveConfigurationResponse, GetSupportedTokensResponse,
-        GetTokensInterestRateModelParamsResponse, TotalBorrowData, UserBorrowingInfo,
-        UserDataByToken,
+        AccountMarketSnapshot, AccountSnapshot, GetBalanceResponse,
+        GetReserveConfigurationResponse, GetSupportedTokensResponse,
+        GetTokensInterestRateModelParamsResponse, MarketCapsHeadroom, ProtocolReserves,
+        TotalBorrowData, UserBorrowingInfo, UserDataByToken, UserEMode,
     };
     use cosmwasm_std::Order::Ascending;
-    use cosmwasm_std::{BalanceResponse, Coin, Order};
+    use cosmwasm_std::{Coin, Order};
     use cw20::BalanceResponse as BalanceResponseCw20;
     use cw20::Cw20QueryMsg;
-    use pyth_sdk_cw::{query_price_feed, PriceFeedResponse, PriceIdentifier};
+    use pyth_sdk_cw::PriceIdentifier;
 
     pub fn is_paused(deps: Deps) -> StdResult<bool> {
-        Ok(IS_PAUSED.load(deps.storage)?)
+        IS_PAUSED.load(deps.storage)
     }
 
     pub fn get_deposit(
@@ -874,7 +1496,7 @@ pub mod query {
             .load(deps.storage, (user, denom.clone()))
             .unwrap_or_else(|_| Uint128::zero());
 
-        let mm_token_price = get_mm_token_price(deps.clone(), env.clone(), denom.clone())
+        let mm_token_price = get_mm_token_price(deps, env.clone(), denom.clone())
             .unwrap()
             .u128();
 
@@ -900,6 +1522,33 @@ pub mod query {
         Ok(use_user_deposit_as_collateral)
     }
 
+    pub fn get_mm_token_address(deps: Deps, denom: String) -> StdResult<Option<String>> {
+        Ok(MM_TOKEN_ADDRESS
+            .may_load(deps.storage, denom)?
+            .map(|mm_token_address| mm_token_address.to_string()))
+    }
+
+    pub fn get_mm_token_balance(deps: Deps, user: String, denom: String) -> StdResult<Uint128> {
+        Ok(USER_MM_TOKEN_BALANCE
+            .load(deps.storage, (user, denom))
+            .unwrap_or_default())
+    }
+
+    pub fn get_total_mm_token_supply(deps: Deps, denom: String) -> StdResult<Uint128> {
+        let users_mm_token_balances_iter: StdResult<Vec<_>> = USER_MM_TOKEN_BALANCE
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect();
+
+        let mut sum_mm_token_balance = 0u128;
+        for users_mm_token_balances in users_mm_token_balances_iter? {
+            if users_mm_token_balances.0 .1 == denom {
+                sum_mm_token_balance += users_mm_token_balances.1.u128();
+            }
+        }
+
+        Ok(Uint128::from(sum_mm_token_balance))
+    }
+
     pub fn get_pyth_contract(deps: Deps) -> StdResult<String> {
         Ok(PYTH_CONTRACT.load(deps.storage)?.to_string())
     }
@@ -953,10 +1602,29 @@ pub mod query {
         env: Env,
         user: String,
         denom: String,
+    ) -> StdResult<Uint128> {
+        // variable, stable and term debt together
+        Ok(get_user_variable_borrow_amount_with_interest(
+            deps,
+            env.clone(),
+            user.clone(),
+            denom.clone(),
+        )? + get_user_stable_borrow_amount_with_interest(
+            deps,
+            env.clone(),
+            user.clone(),
+            denom.clone(),
+        )? + get_user_term_borrow_amount_with_interest(deps, env, user, denom)?)
+    }
+
+    pub fn get_user_variable_borrow_amount_with_interest(
+        deps: Deps,
+        env: Env,
+        user: String,
+        denom: String,
     ) -> StdResult<Uint128> {
         let current_borrowing_info =
-            get_user_borrowing_info(deps.clone(), env.clone(), user.clone(), denom.clone())
-                .unwrap();
+            get_user_borrowing_info(deps, env.clone(), user.clone(), denom.clone()).unwrap();
 
         let token_decimals = get_token_decimal(deps, denom.clone()).unwrap().u128() as u32;
 
@@ -976,7 +1644,8 @@ pub mod query {
         let expected_annual_interest_income = TOTAL_BORROW_DATA
             .load(deps.storage, denom.clone())
             .unwrap_or_default()
-            .expected_annual_interest_income;
+            .expected_annual_interest_income
+            .u128();
 
         let reserves_by_token = get_total_reserves_by_token(deps, env.clone(), denom.clone())
             .unwrap()
@@ -984,6 +1653,9 @@ pub mod query {
 
         let token_decimals = get_token_decimal(deps, denom.clone()).unwrap().u128() as u32;
 
+        // depositors receive only the part of the interest income that is not kept by the protocol
+        let reserve_factor = get_reserve_factor(deps, denom.clone())?;
+
         if reserves_by_token == 0 {
             Ok(Uint128::from(0u128))
         } else {
@@ -991,19 +1663,27 @@ pub mod query {
                 expected_annual_interest_income as i128,
                 INTEREST_RATE_DECIMALS,
             )
-                .mul(Decimal::from_i128_with_scale(HUNDRED as i128, 0u32))
-                .div(Decimal::from_i128_with_scale(
-                    reserves_by_token as i128,
-                    token_decimals,
-                ))
-                .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-                .unwrap();
-
-            Ok(Uint128::from(liquidity_rate))
-        }
-    }
-
-    pub fn get_current_liquidity_index_ln(
+            .mul(Decimal::from_i128_with_scale(
+                (HUNDRED_PERCENT - reserve_factor) as i128,
+                PERCENT_DECIMALS,
+            ))
+            .div(Decimal::from_i128_with_scale(
+                HUNDRED_PERCENT as i128,
+                PERCENT_DECIMALS,
+            ))
+            .mul(Decimal::from_i128_with_scale(HUNDRED as i128, 0u32))
+            .div(Decimal::from_i128_with_scale(
+                reserves_by_token as i128,
+                token_decimals,
+            ))
+            .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
+            .unwrap();
+
+            Ok(Uint128::from(liquidity_rate))
+        }
+    }
+
+    pub fn get_current_liquidity_index_ln(
         deps: Deps,
         env: Env,
         denom: String,
@@ -1022,21 +1702,20 @@ pub mod query {
             .unwrap()
             .liquidity_index_ln;
 
-        let new_liquidity_index_ln = (env
-            .block
-            .time
-            .seconds()
-            .checked_sub(liquidity_index_last_update.seconds())
-            .unwrap_or_default()) as u128
-            * Decimal::from_i128_with_scale(
-            (liquidity_rate / HUNDRED + INTEREST_RATE_MULTIPLIER) as i128,
-            INTEREST_RATE_DECIMALS,
-        )
-            .ln()
-            .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-            .unwrap()
-            / YEAR_IN_SECONDS
-            + liquidity_index_ln;
+        let new_liquidity_index_ln =
+            env.block
+                .time
+                .seconds()
+                .saturating_sub(liquidity_index_last_update.seconds()) as u128
+                * Decimal::from_i128_with_scale(
+                    (liquidity_rate / HUNDRED + INTEREST_RATE_MULTIPLIER) as i128,
+                    INTEREST_RATE_DECIMALS,
+                )
+                .ln()
+                .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
+                .unwrap()
+                / YEAR_IN_SECONDS
+                + liquidity_index_ln;
 
         Ok(Uint128::from(new_liquidity_index_ln))
     }
@@ -1059,8 +1738,11 @@ pub mod query {
                 .unwrap()
                 .u128();
 
+        // socialized bad debt lowers the index below its accrued value
+        let liquidity_index_write_down_ln = get_liquidity_index_write_down_ln(deps, denom.clone())?;
+
         let mm_token_price = Decimal::from_i128_with_scale(
-            current_liquidity_index_ln as i128,
+            current_liquidity_index_ln as i128 - liquidity_index_write_down_ln as i128,
             INTEREST_RATE_DECIMALS,
         )
             .exp()
@@ -1071,33 +1753,65 @@ pub mod query {
     }
 
     pub fn fetch_price_by_token(deps: Deps, env: Env, denom: String) -> StdResult<Uint128> {
-        // if testing mode pulling price from a contract, otherwise fetching from pyth contract
-        if IS_TESTING.load(deps.storage)? {
-            Ok(Uint128::from(
-                PRICES.load(deps.storage, denom).unwrap_or(0u128),
-            ))
-        } else {
-            let pyth_contract = PYTH_CONTRACT.load(deps.storage)?;
+        Ok(fetch_price_with_timestamp_by_token(deps, env, denom)?.0)
+    }
 
-            let price_identifier = PRICE_FEED_IDS.load(deps.storage, denom.clone())?;
+    pub fn fetch_price_with_timestamp_by_token(
+        deps: Deps,
+        env: Env,
+        denom: String,
+    ) -> StdResult<(Uint128, Timestamp)> {
+        let price_sources = get_price_sources(deps, env, denom.clone())?;
+
+        // both sources are fresh but disagree, neither price can be trusted
+        if price_sources
+            .deviation
+            .is_some_and(|deviation| deviation > price_sources.max_deviation)
+        {
+            return Err(StdError::generic_err(
+                ContractError::PriceDeviationTooHigh { denom }.to_string(),
+            ));
+        }
 
-            let price_feed_response: PriceFeedResponse =
-                query_price_feed(&deps.querier, pyth_contract, price_identifier)?;
-            let price_feed = price_feed_response.price_feed;
+        Ok((price_sources.price, price_sources.timestamp))
+    }
 
-            let mut current_price =
-                Uint128::from(PRICES.load(deps.storage, denom).unwrap_or(0u128));
+    pub fn is_price_fresh(deps: Deps, env: Env, denom: String) -> StdResult<bool> {
+        is_e_mode_price_fresh(deps, env, &None, denom)
+    }
 
-            // if Pyth price is available getting most recent price if not - just load from a contract
-            let pyth_current_price =
-                price_feed.get_price_no_older_than(env.block.time.seconds() as i64, 60);
+    pub fn is_e_mode_price_fresh(
+        deps: Deps,
+        env: Env,
+        e_mode_category: &Option<EModeCategory>,
+        denom: String,
+    ) -> StdResult<bool> {
+        let (price, price_timestamp) = fetch_e_mode_price_with_timestamp_by_token(
+            deps,
+            env.clone(),
+            e_mode_category,
+            denom.clone(),
+        )?;
 
-            if pyth_current_price.is_some() {
-                current_price = Uint128::from(pyth_current_price.unwrap().price as u128)
-            }
+        is_source_price_fresh(deps, &env, denom, price, price_timestamp)
+    }
 
-            Ok(current_price)
-        }
+    fn is_source_price_fresh(
+        deps: Deps,
+        env: &Env,
+        denom: String,
+        price: Uint128,
+        price_timestamp: Timestamp,
+    ) -> StdResult<bool> {
+        let max_price_age = get_oracle_configuration(deps, denom)?.max_price_age;
+
+        Ok(!price.is_zero()
+            && env
+                .block
+                .time
+                .seconds()
+                .saturating_sub(price_timestamp.seconds())
+                <= max_price_age)
     }
 
     pub fn get_supported_tokens(deps: Deps) -> StdResult<GetSupportedTokensResponse> {
@@ -1130,6 +1844,287 @@ pub mod query {
         })
     }
 
+    pub fn get_liquidation_configuration(
+        deps: Deps,
+        denom: String,
+    ) -> StdResult<LiquidationConfiguration> {
+        // markets without explicit configuration use the default close factor and bonus
+        Ok(LIQUIDATION_CONFIGURATION
+            .load(deps.storage, denom.clone())
+            .unwrap_or(LiquidationConfiguration {
+                denom,
+                close_factor: DEFAULT_CLOSE_FACTOR,
+                liquidation_bonus: DEFAULT_LIQUIDATION_BONUS,
+            }))
+    }
+
+    pub fn get_oracle_configuration(deps: Deps, denom: String) -> StdResult<OracleConfiguration> {
+        // markets without explicit configuration use the default confidence ratio and price age
+        Ok(ORACLE_CONFIGURATION
+            .load(deps.storage, denom.clone())
+            .unwrap_or(OracleConfiguration {
+                denom,
+                max_confidence_ratio: DEFAULT_MAX_CONFIDENCE_RATIO,
+                max_price_age: DEFAULT_MAX_PRICE_AGE,
+            }))
+    }
+
+    pub fn get_flash_loan_fee(deps: Deps, denom: String) -> StdResult<u128> {
+        Ok(FLASH_LOAN_FEE
+            .load(deps.storage, denom)
+            .unwrap_or(DEFAULT_FLASH_LOAN_FEE))
+    }
+
+    pub fn get_e_mode_categories(deps: Deps) -> StdResult<Vec<EModeCategory>> {
+        E_MODE_CATEGORIES
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, e_mode_category)| e_mode_category))
+            .collect()
+    }
+
+    pub fn get_user_e_mode(deps: Deps, user: String) -> StdResult<UserEMode> {
+        Ok(UserEMode {
+            category_id: USER_E_MODE.may_load(deps.storage, user.clone())?,
+            is_active: get_user_e_mode_category(deps, user.clone())?.is_some(),
+            address: user,
+        })
+    }
+
+    pub fn get_user_e_mode_category(deps: Deps, user: String) -> StdResult<Option<EModeCategory>> {
+        let Some(category_id) = USER_E_MODE.may_load(deps.storage, user.clone())? else {
+            return Ok(None);
+        };
+
+        let e_mode_category = E_MODE_CATEGORIES.load(deps.storage, category_id)?;
+
+        let user_term_loans = get_user_term_loans(deps, user.clone())?;
+
+        // category parameters apply only while all borrows of the user are inside the category
+        for token in get_supported_tokens(deps)?.supported_tokens {
+            if !e_mode_category.denoms.contains(&token.denom)
+                && (!USER_BORROWING_INFO
+                    .load(deps.storage, (user.clone(), token.denom.clone()))
+                    .unwrap_or_default()
+                    .borrowed_amount
+                    .is_zero()
+                    || USER_STABLE_BORROWING_INFO
+                        .has(deps.storage, (user.clone(), token.denom.clone()))
+                    || user_term_loans
+                        .iter()
+                        .any(|term_loan| term_loan.denom == token.denom))
+            {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(e_mode_category))
+    }
+
+    pub fn get_e_mode_reserve_configuration(
+        deps: Deps,
+        e_mode_category: &Option<EModeCategory>,
+        denom: String,
+    ) -> StdResult<ReserveConfiguration> {
+        let mut reserve_configuration = RESERVE_CONFIGURATION.load(deps.storage, denom.clone())?;
+
+        if let Some(e_mode_category) = e_mode_category
+            .as_ref()
+            .filter(|e_mode_category| e_mode_category.denoms.contains(&denom))
+        {
+            reserve_configuration.loan_to_value_ratio = e_mode_category.loan_to_value_ratio;
+            reserve_configuration.liquidation_threshold = e_mode_category.liquidation_threshold;
+        }
+
+        // collateral of a market in wind-down doesn't back any borrow
+        if get_market_status(deps, denom)? == MarketStatus::WindDown {
+            reserve_configuration.loan_to_value_ratio = 0;
+            reserve_configuration.liquidation_threshold = 0;
+        }
+
+        Ok(reserve_configuration)
+    }
+
+    pub fn fetch_e_mode_price_by_token(
+        deps: Deps,
+        env: Env,
+        e_mode_category: &Option<EModeCategory>,
+        denom: String,
+    ) -> StdResult<Uint128> {
+        let (price, price_timestamp) = fetch_e_mode_price_with_timestamp_by_token(
+            deps,
+            env.clone(),
+            e_mode_category,
+            denom.clone(),
+        )?;
+
+        // the category oracle has no fallback, so its stale price is never used for the valuation
+        if get_e_mode_price_source(e_mode_category, &denom).is_some()
+            && !is_source_price_fresh(deps, &env, denom.clone(), price, price_timestamp)?
+        {
+            return Err(StdError::generic_err(
+                ContractError::StalePrice { denom }.to_string(),
+            ));
+        }
+
+        Ok(price)
+    }
+
+    pub fn fetch_e_mode_price_with_timestamp_by_token(
+        deps: Deps,
+        env: Env,
+        e_mode_category: &Option<EModeCategory>,
+        denom: String,
+    ) -> StdResult<(Uint128, Timestamp)> {
+        match get_e_mode_price_source(e_mode_category, &denom) {
+            Some(price_source) => {
+                let (price, timestamp) = query_source_price(deps, env, denom, price_source)?;
+
+                Ok((Uint128::from(price), timestamp))
+            }
+            None => fetch_price_with_timestamp_by_token(deps, env, denom),
+        }
+    }
+
+    fn get_e_mode_price_source<'a>(
+        e_mode_category: &'a Option<EModeCategory>,
+        denom: &str,
+    ) -> Option<&'a PriceSource> {
+        // assets of the category share the category oracle if it's set
+        match e_mode_category {
+            Some(EModeCategory {
+                price_source: Some(price_source),
+                denoms,
+                ..
+            }) if denoms.iter().any(|category_denom| category_denom == denom) => Some(price_source),
+            _ => None,
+        }
+    }
+
+    pub fn get_market_status(deps: Deps, denom: String) -> StdResult<MarketStatus> {
+        Ok(MARKET_STATUS
+            .may_load(deps.storage, denom)?
+            .unwrap_or_default())
+    }
+
+    pub fn get_isolation_configuration(
+        deps: Deps,
+        denom: String,
+    ) -> StdResult<IsolationConfiguration> {
+        // markets are not isolated by default
+        Ok(ISOLATION_CONFIGURATION
+            .load(deps.storage, denom.clone())
+            .unwrap_or(IsolationConfiguration {
+                denom,
+                is_isolated: false,
+                debt_ceiling: Uint128::zero(),
+                borrowable_in_isolation: false,
+            }))
+    }
+
+    // debt is kept in borrowed tokens and valued in USD with the current prices
+    pub fn get_isolated_debt(deps: Deps, env: Env, denom: String) -> StdResult<Uint128> {
+        let mut isolated_debt = Uint128::zero();
+
+        for item in ISOLATED_DEBT
+            .prefix(denom)
+            .range(deps.storage, None, None, Order::Ascending)
+        {
+            let (borrowed_denom, amount) = item?;
+
+            isolated_debt +=
+                get_token_amount_usd(deps, env.clone(), borrowed_denom, amount.u128())?;
+        }
+
+        Ok(isolated_debt)
+    }
+
+    pub fn get_user_isolated_collateral(deps: Deps, user: String) -> StdResult<Option<String>> {
+        for token in get_supported_tokens(deps)?.supported_tokens {
+            if user_deposit_as_collateral(deps, user.clone(), token.denom.clone())?
+                && get_isolation_configuration(deps, token.denom.clone())?.is_isolated
+            {
+                return Ok(Some(token.denom));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn get_token_amount_usd(
+        deps: Deps,
+        env: Env,
+        denom: String,
+        amount: u128,
+    ) -> StdResult<Uint128> {
+        let token_decimals = get_token_decimal(deps, denom.clone())?.u128() as u32;
+
+        let price = fetch_price_by_token(deps, env, denom)?.u128();
+
+        Ok(Uint128::from(
+            Decimal::from_i128_with_scale(amount as i128, token_decimals)
+                .mul(Decimal::from_i128_with_scale(price as i128, USD_DECIMALS))
+                .to_u128_with_decimals(USD_DECIMALS)
+                .unwrap_or_default(),
+        ))
+    }
+
+    pub fn get_reserve_factor(deps: Deps, denom: String) -> StdResult<u128> {
+        Ok(RESERVE_FACTOR.load(deps.storage, denom).unwrap_or_default())
+    }
+
+    pub fn get_protocol_reserves(
+        deps: Deps,
+        env: Env,
+        denom: String,
+    ) -> StdResult<ProtocolReserves> {
+        let reserve_factor = get_reserve_factor(deps, denom.clone())?;
+
+        let stored_reserves = PROTOCOL_RESERVES
+            .load(deps.storage, denom.clone())
+            .unwrap_or_default();
+
+        let expected_annual_interest_income = TOTAL_BORROW_DATA
+            .load(deps.storage, denom.clone())
+            .unwrap_or_default()
+            .expected_annual_interest_income
+            .u128();
+
+        let liquidity_index_last_update = LIQUIDITY_INDEX_DATA
+            .load(deps.storage, denom.clone())?
+            .timestamp;
+
+        let token_decimals = get_token_decimal(deps, denom.clone())?.u128() as u32;
+
+        // protocol share of the interest income since the last liquidity index update
+        let pending_reserves = Decimal::from_i128_with_scale(
+            expected_annual_interest_income as i128,
+            INTEREST_RATE_DECIMALS,
+        )
+        .mul(Decimal::from_i128_with_scale(
+            reserve_factor as i128,
+            PERCENT_DECIMALS,
+        ))
+        .div(Decimal::from_i128_with_scale(
+            HUNDRED_PERCENT as i128,
+            PERCENT_DECIMALS,
+        ))
+        .mul(Decimal::from_i128_with_scale(
+            env.block
+                .time
+                .seconds()
+                .saturating_sub(liquidity_index_last_update.seconds()) as i128,
+            0u32,
+        ))
+        .div(Decimal::from_i128_with_scale(YEAR_IN_SECONDS as i128, 0u32))
+        .to_u128_with_decimals(token_decimals)?;
+
+        Ok(ProtocolReserves {
+            denom,
+            reserve_factor,
+            accrued_reserves: Uint128::from(stored_reserves + pending_reserves),
+        })
+    }
+
     pub fn get_tokens_interest_rate_model_params(
         deps: Deps,
     ) -> StdResult<GetTokensInterestRateModelParamsResponse> {
@@ -1152,36 +2147,12 @@ pub mod query {
             .unwrap()
             .u128();
 
-        let min_interest_rate = TOKENS_INTEREST_RATE_MODEL_PARAMS
-            .load(deps.storage, denom.clone())
-            .unwrap()
-            .min_interest_rate;
-        let safe_borrow_max_rate = TOKENS_INTEREST_RATE_MODEL_PARAMS
-            .load(deps.storage, denom.clone())
-            .unwrap()
-            .safe_borrow_max_rate;
-        let rate_growth_factor = TOKENS_INTEREST_RATE_MODEL_PARAMS
-            .load(deps.storage, denom.clone())
-            .unwrap()
-            .rate_growth_factor;
-        let optimal_utilisation_ratio = TOKENS_INTEREST_RATE_MODEL_PARAMS
-            .load(deps.storage, denom.clone())
-            .unwrap()
-            .optimal_utilisation_ratio;
-
-        if utilization_rate <= optimal_utilisation_ratio {
-            Ok(Uint128::from(
-                min_interest_rate
-                    + utilization_rate * (safe_borrow_max_rate - min_interest_rate)
-                    / optimal_utilisation_ratio,
-            ))
-        } else {
-            Ok(Uint128::from(
-                safe_borrow_max_rate
-                    + rate_growth_factor * (utilization_rate - optimal_utilisation_ratio)
-                    / (HUNDRED_PERCENT - optimal_utilisation_ratio),
-            ))
-        }
+        Ok(Uint128::from(calculate_interest_rate(
+            deps,
+            env,
+            denom,
+            utilization_rate,
+        )?))
     }
 
     pub fn get_token_decimal(deps: Deps, denom: String) -> StdResult<Uint128> {
@@ -1213,9 +2184,10 @@ pub mod query {
         }
 
         Ok(UserBorrowingInfo {
+            denom,
             borrowed_amount: user_borrowing_info.borrowed_amount,
-            average_interest_rate: average_interest_rate,
-            timestamp: timestamp,
+            average_interest_rate,
+            timestamp,
         })
     }
 
@@ -1237,9 +2209,7 @@ pub mod query {
             let token_decimals =
                 get_token_decimal(deps, token.denom.clone()).unwrap().u128() as u32;
 
-            let price = fetch_price_by_token(deps, env.clone(), token.denom.clone())
-                .unwrap()
-                .u128();
+            let price = fetch_price_by_token(deps, env.clone(), token.denom.clone())?.u128();
 
             user_deposited_usd +=
                 Decimal::from_i128_with_scale(user_deposit as i128, token_decimals)
@@ -1254,6 +2224,8 @@ pub mod query {
     pub fn get_user_collateral_usd(deps: Deps, env: Env, user: String) -> StdResult<Uint128> {
         let mut user_collateral_usd = 0u128;
 
+        let e_mode_category = get_user_e_mode_category(deps, user.clone())?;
+
         for token in get_supported_tokens(deps).unwrap().supported_tokens {
             let use_user_deposit_as_collateral =
                 user_deposit_as_collateral(deps, user.clone(), token.denom.clone()).unwrap();
@@ -1268,9 +2240,13 @@ pub mod query {
                 let token_decimals =
                     get_token_decimal(deps, token.denom.clone()).unwrap().u128() as u32;
 
-                let price = fetch_price_by_token(deps, env.clone(), token.denom.clone())
-                    .unwrap()
-                    .u128();
+                let price = fetch_e_mode_price_by_token(
+                    deps,
+                    env.clone(),
+                    &e_mode_category,
+                    token.denom.clone(),
+                )?
+                .u128();
 
                 user_collateral_usd +=
                     Decimal::from_i128_with_scale(user_deposit as i128, token_decimals)
@@ -1285,6 +2261,9 @@ pub mod query {
 
     pub fn get_user_borrowed_usd(deps: Deps, env: Env, user: String) -> StdResult<Uint128> {
         let mut user_borrowed_usd = 0u128;
+
+        let e_mode_category = get_user_e_mode_category(deps, user.clone())?;
+
         for token in get_supported_tokens(deps).unwrap().supported_tokens {
             let user_borrow_amount_with_interest = get_user_borrow_amount_with_interest(
                 deps,
@@ -1295,12 +2274,22 @@ pub mod query {
                 .unwrap()
                 .u128();
 
+            // a market without debt doesn't need a price
+            if user_borrow_amount_with_interest == 0 {
+                continue;
+            }
+
             let token_decimals =
                 get_token_decimal(deps, token.denom.clone()).unwrap().u128() as u32;
 
-            let price = fetch_price_by_token(deps, env.clone(), token.denom.clone())
-                .unwrap_or_default()
-                .u128();
+            // the debt is never valued at zero when the price can't be fetched
+            let price = fetch_e_mode_price_by_token(
+                deps,
+                env.clone(),
+                &e_mode_category,
+                token.denom.clone(),
+            )?
+            .u128();
 
             user_borrowed_usd += Decimal::from_i128_with_scale(
                 user_borrow_amount_with_interest as i128,
@@ -1318,18 +2307,32 @@ pub mod query {
         deps: Deps,
         env: Env,
         denom: String,
+    ) -> StdResult<Uint128> {
+        let contract_balance = get_contract_balance_by_token(deps, env.clone(), denom.clone())?;
+
+        // accrued protocol reserves and the insurance fund can't be borrowed or redeemed by users
+        let accrued_reserves = get_protocol_reserves(deps, env, denom.clone())?.accrued_reserves;
+        let insurance_fund = get_insurance_fund(deps, denom)?;
+
+        Ok(contract_balance.saturating_sub(accrued_reserves + insurance_fund))
+    }
+
+    pub fn get_contract_balance_by_token(
+        deps: Deps,
+        env: Env,
+        denom: String,
     ) -> StdResult<Uint128> {
         let cw20_address = SUPPORTED_TOKENS
             .load(deps.storage, denom.clone())
             .unwrap()
             .cw20_address;
 
-        if cw20_address.is_some() {
+        if let Some(cw20_address) = cw20_address {
             // for CW20 tokens query balance from token contract
             let liquidity: BalanceResponseCw20 = deps
                 .querier
                 .query_wasm_smart(
-                    cw20_address.unwrap(),
+                    cw20_address,
                     &Cw20QueryMsg::Balance {
                         address: env.contract.address.to_string(),
                     },
@@ -1362,6 +2365,8 @@ pub mod query {
         let mut liquidation_threshold_borrow_amount_usd = 0u128;
         let mut user_collateral_usd = 0u128;
 
+        let e_mode_category = get_user_e_mode_category(deps, user.clone())?;
+
         for token in get_supported_tokens(deps).unwrap().supported_tokens {
             let use_user_deposit_as_collateral =
                 user_deposit_as_collateral(deps, user.clone(), token.denom.clone()).unwrap();
@@ -1373,17 +2378,21 @@ pub mod query {
                         .balance
                         .u128();
 
-                let liquidation_threshold = RESERVE_CONFIGURATION
-                    .load(deps.storage, token.denom.clone())
-                    .unwrap()
-                    .liquidation_threshold;
+                let liquidation_threshold =
+                    get_e_mode_reserve_configuration(deps, &e_mode_category, token.denom.clone())
+                        .unwrap()
+                        .liquidation_threshold;
 
                 let token_decimals =
                     get_token_decimal(deps, token.denom.clone()).unwrap().u128() as u32;
 
-                let price = fetch_price_by_token(deps, env.clone(), token.denom.clone())
-                    .unwrap()
-                    .u128();
+                let price = fetch_e_mode_price_by_token(
+                    deps,
+                    env.clone(),
+                    &e_mode_category,
+                    token.denom.clone(),
+                )?
+                .u128();
 
                 let user_deposit_usd =
                     Decimal::from_i128_with_scale(user_deposit as i128, token_decimals)
@@ -1410,6 +2419,8 @@ pub mod query {
         // the maximum amount in USD that a user can borrow
         let mut max_allowed_borrow_amount_usd = 0u128;
 
+        let e_mode_category = get_user_e_mode_category(deps, user.clone())?;
+
         for token in get_supported_tokens(deps).unwrap().supported_tokens {
             let use_user_deposit_as_collateral =
                 user_deposit_as_collateral(deps, user.clone(), token.denom.clone()).unwrap();
@@ -1421,17 +2432,21 @@ pub mod query {
                         .balance
                         .u128();
 
-                let loan_to_value_ratio = RESERVE_CONFIGURATION
-                    .load(deps.storage, token.denom.clone())
-                    .unwrap()
-                    .loan_to_value_ratio;
+                let loan_to_value_ratio =
+                    get_e_mode_reserve_configuration(deps, &e_mode_category, token.denom.clone())
+                        .unwrap()
+                        .loan_to_value_ratio;
 
                 let token_decimals =
                     get_token_decimal(deps, token.denom.clone()).unwrap().u128() as u32;
 
-                let price = fetch_price_by_token(deps, env.clone(), token.denom.clone())
-                    .unwrap()
-                    .u128();
+                let price = fetch_e_mode_price_by_token(
+                    deps,
+                    env.clone(),
+                    &e_mode_category,
+                    token.denom.clone(),
+                )?
+                .u128();
 
                 let user_deposit_usd =
                     Decimal::from_i128_with_scale(user_deposit as i128, token_decimals)
@@ -1457,20 +2472,15 @@ pub mod query {
 
         // maximum amount allowed for borrowing
         let max_allowed_borrow_amount_usd =
-            get_user_max_allowed_borrow_amount_usd(deps, env.clone(), user.clone())
-                .unwrap()
-                .u128();
+            get_user_max_allowed_borrow_amount_usd(deps, env.clone(), user.clone())?.u128();
 
-        let sum_user_borrow_balance_usd = get_user_borrowed_usd(deps, env.clone(), user.clone())
-            .unwrap()
-            .u128();
+        let sum_user_borrow_balance_usd =
+            get_user_borrowed_usd(deps, env.clone(), user.clone())?.u128();
 
         if max_allowed_borrow_amount_usd > sum_user_borrow_balance_usd {
             let token_decimals = get_token_decimal(deps, denom.clone()).unwrap().u128() as u32;
 
-            let price = fetch_price_by_token(deps, env.clone(), denom.clone())
-                .unwrap()
-                .u128();
+            let price = fetch_price_by_token(deps, env.clone(), denom.clone())?.u128();
 
             available_to_borrow = Decimal::from_i128_with_scale(
                 (max_allowed_borrow_amount_usd - sum_user_borrow_balance_usd) as i128,
@@ -1509,21 +2519,18 @@ pub mod query {
         if user_deposit_as_collateral(deps, user.clone(), denom.clone()).unwrap() {
             if user_token_balance != 0 {
                 let sum_collateral_balance_usd =
-                    get_user_collateral_usd(deps, env.clone(), user.clone())
-                        .unwrap()
-                        .u128();
+                    get_user_collateral_usd(deps, env.clone(), user.clone())?.u128();
 
-                let sum_borrow_balance_usd = get_user_borrowed_usd(deps, env.clone(), user.clone())
-                    .unwrap()
-                    .u128();
+                let sum_borrow_balance_usd =
+                    get_user_borrowed_usd(deps, env.clone(), user.clone())?.u128();
 
                 let user_liquidation_threshold =
-                    get_user_liquidation_threshold(deps, env.clone(), user.clone())
-                        .unwrap()
-                        .u128();
+                    get_user_liquidation_threshold(deps, env.clone(), user.clone())?.u128();
 
-                let required_collateral_balance_usd =
-                    sum_borrow_balance_usd * HUNDRED_PERCENT / user_liquidation_threshold;
+                // collateral in wind-down only doesn't cover any borrow
+                let required_collateral_balance_usd = (sum_borrow_balance_usd * HUNDRED_PERCENT)
+                    .checked_div(user_liquidation_threshold)
+                    .unwrap_or(u128::MAX);
 
                 let token_liquidity =
                     get_available_liquidity_by_token(deps, env.clone(), denom.clone())
@@ -1534,9 +2541,7 @@ pub mod query {
                     let token_decimals =
                         get_token_decimal(deps, denom.clone()).unwrap().u128() as u32;
 
-                    let price = fetch_price_by_token(deps, env.clone(), denom.clone())
-                        .unwrap()
-                        .u128();
+                    let price = fetch_price_by_token(deps, env.clone(), denom.clone())?.u128();
 
                     available_to_redeem = Decimal::from_i128_with_scale(
                         (sum_collateral_balance_usd - required_collateral_balance_usd) as i128,
@@ -1563,18 +2568,9 @@ pub mod query {
     }
 
     pub fn get_total_deposited_by_token(deps: Deps, env: Env, denom: String) -> StdResult<Uint128> {
-        let users_mm_token_balances_iter: StdResult<Vec<_>> = USER_MM_TOKEN_BALANCE
-            .range(deps.storage, None, None, Order::Ascending)
-            .collect();
-
-        let mut sum_mm_token_balance = 0u128;
-        for users_mm_token_balances in users_mm_token_balances_iter.unwrap() {
-            if users_mm_token_balances.0.1 == denom {
-                sum_mm_token_balance += users_mm_token_balances.1.u128();
-            }
-        }
+        let sum_mm_token_balance = get_total_mm_token_supply(deps, denom.clone())?.u128();
 
-        let mm_token_price = get_mm_token_price(deps.clone(), env.clone(), denom.clone())
+        let mm_token_price = get_mm_token_price(deps, env.clone(), denom.clone())
             .unwrap()
             .u128();
 
@@ -1598,8 +2594,8 @@ pub mod query {
         let token_decimals = get_token_decimal(deps, denom.clone()).unwrap().u128() as u32;
 
         let total_borrowed_amount_with_interest = calc_borrow_amount_with_interest(
-            total_borrow_data.total_borrowed_amount,
-            total_borrow_data.average_interest_rate,
+            total_borrow_data.total_borrowed_amount.u128(),
+            total_borrow_data.average_interest_rate.u128(),
             (env.block.time.seconds() - total_borrow_data.timestamp.seconds()) as u128,
             token_decimals,
         )
@@ -1609,6 +2605,31 @@ pub mod query {
         Ok(Uint128::from(total_borrowed_amount_with_interest))
     }
 
+    pub fn get_market_caps_headroom(
+        deps: Deps,
+        env: Env,
+        denom: String,
+    ) -> StdResult<MarketCapsHeadroom> {
+        let reserve_configuration = RESERVE_CONFIGURATION.load(deps.storage, denom.clone())?;
+
+        let total_deposited = get_total_deposited_by_token(deps, env.clone(), denom.clone())?;
+        let total_borrowed = get_total_borrowed_by_token(deps, env, denom.clone())?;
+
+        Ok(MarketCapsHeadroom {
+            denom,
+            supply_cap: reserve_configuration.supply_cap,
+            borrow_cap: reserve_configuration.borrow_cap,
+            total_deposited,
+            total_borrowed,
+            supply_headroom: reserve_configuration
+                .supply_cap
+                .map(|supply_cap| supply_cap.saturating_sub(total_deposited)),
+            borrow_headroom: reserve_configuration
+                .borrow_cap
+                .map(|borrow_cap| borrow_cap.saturating_sub(total_borrowed)),
+        })
+    }
+
     pub fn get_total_reserves_by_token(deps: Deps, env: Env, denom: String) -> StdResult<Uint128> {
         let token_liquidity = get_available_liquidity_by_token(deps, env.clone(), denom.clone())
             .unwrap()
@@ -1629,47 +2650,150 @@ pub mod query {
             .unwrap()
             .u128();
 
-        if reserves_by_token != 0 {
-            let borrowed_by_token = get_total_borrowed_by_token(deps, env, denom.clone())
-                .unwrap()
-                .u128();
-
-            Ok(Uint128::from(
-                borrowed_by_token * HUNDRED_PERCENT / reserves_by_token,
-            ))
-        } else {
-            Ok(Uint128::from(0u128))
+        if reserves_by_token == 0 {
+            return Ok(Uint128::from(0u128));
         }
-    }
 
-    pub fn get_user_utilization_rate(deps: Deps, env: Env, user: String) -> StdResult<Uint128> {
-        let sum_collateral_balance_usd = get_user_collateral_usd(deps, env.clone(), user.clone())
+        let borrowed_by_token = get_total_borrowed_by_token(deps, env, denom.clone())
             .unwrap()
             .u128();
 
-        if sum_collateral_balance_usd != 0 {
-            let sum_user_borrow_balance_usd =
-                get_user_borrowed_usd(deps, env.clone(), user.clone())
-                    .unwrap()
-                    .u128();
+        Ok(Uint128::from(
+            borrowed_by_token * HUNDRED_PERCENT / reserves_by_token,
+        ))
+    }
 
-            Ok(Uint128::from(
-                sum_user_borrow_balance_usd * HUNDRED_PERCENT / sum_collateral_balance_usd,
-            ))
-        } else {
-            Ok(Uint128::from(0u128))
+    pub fn get_user_utilization_rate(deps: Deps, env: Env, user: String) -> StdResult<Uint128> {
+        let sum_collateral_balance_usd =
+            get_user_collateral_usd(deps, env.clone(), user.clone())?.u128();
+
+        if sum_collateral_balance_usd == 0 {
+            return Ok(Uint128::from(0u128));
+        }
+
+        let sum_user_borrow_balance_usd =
+            get_user_borrowed_usd(deps, env.clone(), user.clone())?.u128();
+
+        Ok(Uint128::from(
+            sum_user_borrow_balance_usd * HUNDRED_PERCENT / sum_collateral_balance_usd,
+        ))
+    }
+
+    pub fn get_account_snapshot(deps: Deps, env: Env, user: String) -> StdResult<AccountSnapshot> {
+        // every market and price is loaded only once
+        let mut markets = vec![];
+        let mut deposited_usd = 0u128;
+        let mut collateral_usd = 0u128;
+        let mut borrowed_usd = 0u128;
+        let mut borrow_capacity_usd = 0u128;
+        let mut liquidation_threshold_usd = 0u128;
+
+        let e_mode_category = get_user_e_mode_category(deps, user.clone())?;
+
+        for token in get_supported_tokens(deps)?.supported_tokens {
+            let deposited = get_deposit(deps, env.clone(), user.clone(), token.denom.clone())?
+                .balance
+                .u128();
+
+            let borrowed = get_user_borrow_amount_with_interest(
+                deps,
+                env.clone(),
+                user.clone(),
+                token.denom.clone(),
+            )?
+            .u128();
+
+            let use_as_collateral =
+                user_deposit_as_collateral(deps, user.clone(), token.denom.clone())?;
+
+            let reserve_configuration =
+                get_e_mode_reserve_configuration(deps, &e_mode_category, token.denom.clone())?;
+
+            let token_decimals = token.decimals as u32;
+
+            let price = fetch_e_mode_price_by_token(
+                deps,
+                env.clone(),
+                &e_mode_category,
+                token.denom.clone(),
+            )?
+            .u128();
+
+            let market_deposited_usd =
+                Decimal::from_i128_with_scale(deposited as i128, token_decimals)
+                    .mul(Decimal::from_i128_with_scale(price as i128, USD_DECIMALS))
+                    .to_u128_with_decimals(USD_DECIMALS)?;
+
+            let market_borrowed_usd =
+                Decimal::from_i128_with_scale(borrowed as i128, token_decimals)
+                    .mul(Decimal::from_i128_with_scale(price as i128, USD_DECIMALS))
+                    .to_u128_with_decimals(USD_DECIMALS)?;
+
+            deposited_usd += market_deposited_usd;
+            borrowed_usd += market_borrowed_usd;
+
+            if use_as_collateral {
+                collateral_usd += market_deposited_usd;
+                borrow_capacity_usd += market_deposited_usd
+                    * reserve_configuration.loan_to_value_ratio
+                    / HUNDRED_PERCENT;
+                liquidation_threshold_usd += market_deposited_usd
+                    * reserve_configuration.liquidation_threshold
+                    / HUNDRED_PERCENT;
+            }
+
+            markets.push(AccountMarketSnapshot {
+                denom: token.denom,
+                deposited: Uint128::from(deposited),
+                deposited_usd: Uint128::from(market_deposited_usd),
+                use_as_collateral,
+                borrowed: Uint128::from(borrowed),
+                borrowed_usd: Uint128::from(market_borrowed_usd),
+                price: Uint128::from(price),
+                loan_to_value_ratio: reserve_configuration.loan_to_value_ratio,
+                liquidation_threshold: reserve_configuration.liquidation_threshold,
+            });
         }
+
+        let liquidation_threshold = (liquidation_threshold_usd * HUNDRED_PERCENT)
+            .checked_div(collateral_usd)
+            .unwrap_or_default();
+
+        let utilization_rate = (borrowed_usd * HUNDRED_PERCENT)
+            .checked_div(collateral_usd)
+            .unwrap_or_default();
+
+        let health_factor = (liquidation_threshold_usd * HUNDRED_PERCENT)
+            .checked_div(borrowed_usd)
+            .map(Uint128::from);
+
+        Ok(AccountSnapshot {
+            markets,
+            deposited_usd: Uint128::from(deposited_usd),
+            collateral_usd: Uint128::from(collateral_usd),
+            borrowed_usd: Uint128::from(borrowed_usd),
+            borrow_capacity_usd: Uint128::from(borrow_capacity_usd),
+            liquidation_threshold_usd: Uint128::from(liquidation_threshold_usd),
+            liquidation_threshold: Uint128::from(liquidation_threshold),
+            utilization_rate: Uint128::from(utilization_rate),
+            health_factor,
+            distance_to_liquidation_usd: Uint128::from(
+                liquidation_threshold_usd.saturating_sub(borrowed_usd),
+            ),
+        })
     }
 
-    pub fn get_all_users_with_borrows(deps: Deps, env: Env) -> StdResult<Vec<String>> {
+    pub fn get_all_users_with_borrows(deps: Deps, _env: Env) -> StdResult<Vec<String>> {
         let user_borrowed_data: StdResult<Vec<_>> = USER_BORROWING_INFO
             .range(deps.storage, None, None, Order::Ascending)
+            .chain(USER_STABLE_BORROWING_INFO.range(deps.storage, None, None, Order::Ascending))
             .collect();
 
         let mut uniq = user_borrowed_data
             .unwrap()
             .into_iter()
             .map(|((user, _), _)| user)
+            .chain(get_term_loan_borrowers(deps)?)
             .collect::<Vec<String>>();
         uniq.sort();
         uniq.dedup();
@@ -1718,10 +2842,54 @@ pub fn execute_cw20_receive(
     let amount = msg.amount;
 
     match from_json::<Cw20HookMsg>(&msg.msg)? {
-        Cw20HookMsg::Deposit { denom } => {
-            execute_cw20_deposit(deps, env, info, sender, amount, denom)
+        Cw20HookMsg::Deposit {
+            denom,
+            on_behalf_of,
+        } => execute_cw20_deposit(deps, env, info, sender, amount, denom, on_behalf_of),
+        Cw20HookMsg::Repay {
+            denom,
+            on_behalf_of,
+        } => execute_cw20_repay(deps, env, info, sender, amount, denom, on_behalf_of),
+        Cw20HookMsg::Liquidate {
+            user,
+            collateral_denom,
+            receive_mm_token,
+        } => execute_cw20_liquidation(
+            deps,
+            env,
+            info,
+            sender,
+            amount,
+            user,
+            collateral_denom,
+            receive_mm_token,
+            None,
+        ),
+        Cw20HookMsg::RepayTermLoan { loan_id } => {
+            execute_cw20_repay_term_loan(deps, env, info, sender, amount, loan_id)
+        }
+        Cw20HookMsg::FundInsuranceFund {} => {
+            execute_cw20_fund_insurance_fund(deps, info, sender, amount)
+        }
+        Cw20HookMsg::LiquidateTermLoan {
+            loan_id,
+            collateral_denom,
+            receive_mm_token,
+        } => {
+            let borrower = load_term_loan(deps.as_ref(), loan_id)?.borrower;
+
+            execute_cw20_liquidation(
+                deps,
+                env,
+                info,
+                sender,
+                amount,
+                borrower,
+                collateral_denom,
+                receive_mm_token,
+                Some(loan_id),
+            )
         }
-        Cw20HookMsg::Repay { denom } => execute_cw20_repay(deps, env, info, sender, amount, denom),
     }
 }
 
@@ -1731,10 +2899,13 @@ pub fn execute_redeem(
     info: MessageInfo,
     amount: Uint128,
     denom: String,
+    recipient: Option<String>,
 ) -> Result<Response, ContractError> {
     let amount = amount.u128();
     let mut resp = Response::default();
 
+    let recipient = get_account_or_sender(deps.as_ref(), &info.sender, recipient)?;
+
     ensure!(
         amount > 0,
         ContractError::InvalidFunds {
@@ -1747,6 +2918,20 @@ pub fn execute_redeem(
         ContractError::TokenNotSupported {}
     );
 
+    ensure_action_not_paused(deps.as_ref(), &denom, PauseAction::Redeem)?;
+
+    // redeem of the collateral changes the health of the borrowing
+    if user_deposit_as_collateral(deps.as_ref(), info.sender.to_string(), denom.clone())?
+        && !get_user_borrowed_usd(deps.as_ref(), env.clone(), info.sender.to_string())?.is_zero()
+    {
+        ensure_fresh_prices(
+            deps.as_ref(),
+            env.clone(),
+            info.sender.to_string(),
+            denom.clone(),
+        )?;
+    }
+
     execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
 
     let current_balance = get_deposit(
@@ -1760,11 +2945,24 @@ pub fn execute_redeem(
         .u128();
 
     ensure!(
-        current_balance >= amount.clone(),
+        current_balance >= amount,
         ContractError::NotEnoughBalanceToDoRedeem {}
     );
 
-    let remaining = current_balance - amount.clone();
+    if user_deposit_as_collateral(deps.as_ref(), info.sender.to_string(), denom.clone())? {
+        ensure!(
+            is_collateral_release_allowed(
+                deps.as_ref(),
+                env.clone(),
+                info.sender.to_string(),
+                denom.clone(),
+                amount,
+            )?,
+            ContractError::NotEnoughCollateralToRedeem {}
+        );
+    }
+
+    let remaining = current_balance - amount;
 
     let token_decimals = get_token_decimal(deps.as_ref(), denom.clone())
         .unwrap()
@@ -1775,14 +2973,22 @@ pub fn execute_redeem(
         .u128();
 
     let new_user_mm_token_balance =
-        Decimal::from_i128_with_scale(remaining as i128, token_decimals.clone())
+        Decimal::from_i128_with_scale(remaining as i128, token_decimals)
             .div(Decimal::from_i128_with_scale(
                 mm_token_price as i128,
-                token_decimals.clone(),
+                token_decimals,
             ))
             .to_u128_with_decimals(token_decimals)
             .unwrap();
 
+    update_user_rewards(
+        &mut deps,
+        env.clone(),
+        info.sender.to_string(),
+        denom.clone(),
+        RewardSide::Supply,
+    )?;
+
     USER_MM_TOKEN_BALANCE.save(
         deps.storage,
         (info.sender.to_string(), denom.clone()),
@@ -1794,8 +3000,8 @@ pub fn execute_redeem(
         .unwrap()
         .cw20_address;
 
-    let asset = if cw20_address.is_some() {
-        AssetInfo::Cw20(Addr::unchecked(cw20_address.unwrap()))
+    let asset = if let Some(cw20_address) = cw20_address {
+        AssetInfo::Cw20(Addr::unchecked(cw20_address))
     } else {
         AssetInfo::Native(denom.clone())
     };
@@ -1803,13 +3009,14 @@ pub fn execute_redeem(
     resp = resp.add_message(generate_transfer_message(
         asset.clone(),
         Uint128::from(amount),
-        info.sender.to_string(),
+        recipient.clone(),
     )?);
 
     Ok(resp.add_attributes(vec![
         attr("action", "redeem"),
         attr("amount", Uint128::from(amount)),
         attr("user", info.sender.clone().to_string()),
+        attr("recipient", recipient),
         attr("denom", denom.clone().to_string()),
     ]))
 }
@@ -1821,12 +3028,12 @@ pub fn execute_cw20_deposit(
     sender: Addr,
     amount: Uint128,
     denom: String,
+    on_behalf_of: Option<String>,
 ) -> Result<Response, ContractError> {
-    ensure_ne!(
-        true,
-        is_paused(deps.as_ref())?,
-        ContractError::ProtocolIsPaused {}
-    );
+    ensure_action_not_paused(deps.as_ref(), &denom, PauseAction::Deposit)?;
+    ensure_market_active(deps.as_ref(), denom.clone())?;
+
+    let depositee = get_account_or_sender(deps.as_ref(), &sender, on_behalf_of)?;
 
     // only cw20 tokens must be sent, not a coins
     nonpayable(&info)?;
@@ -1855,6 +3062,13 @@ pub fn execute_cw20_deposit(
 
     execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
 
+    ensure!(
+        get_market_caps_headroom(deps.as_ref(), env.clone(), denom.clone())?
+            .supply_headroom
+            .is_none_or(|supply_headroom| amount <= supply_headroom),
+        ContractError::SupplyCapExceeded {}
+    );
+
     let token_decimals = get_token_decimal(deps.as_ref(), denom.clone())
         .unwrap()
         .u128() as u32;
@@ -1873,24 +3087,30 @@ pub fn execute_cw20_deposit(
             .unwrap();
 
     let user_current_mm_token_balance = USER_MM_TOKEN_BALANCE
-        .load(
-            deps.storage,
-            (sender.to_string(), String::from(denom.clone())),
-        )
+        .load(deps.storage, (depositee.clone(), denom.clone()))
         .unwrap_or_else(|_| Uint128::zero());
 
     let new_user_mm_token_balance =
         user_current_mm_token_balance.u128() + deposited_mm_token_amount;
 
+    update_user_rewards(
+        &mut deps,
+        env.clone(),
+        depositee.clone(),
+        denom.clone(),
+        RewardSide::Supply,
+    )?;
+
     USER_MM_TOKEN_BALANCE.save(
         deps.storage,
-        (sender.to_string(), String::from(denom.clone())),
+        (depositee.clone(), denom.clone()),
         &Uint128::from(new_user_mm_token_balance),
     )?;
 
     let resp = Response::default().add_attributes(vec![
         attr("action", "deposit"),
         attr("sender", sender.to_string()),
+        attr("depositee", depositee),
         attr("amount", amount.to_string()),
         attr("token_address", denom.clone()),
     ]);
@@ -1898,37 +3118,63 @@ pub fn execute_cw20_deposit(
     Ok(resp)
 }
 
-/// Generates a transfer message given an asset and an amount
-fn generate_transfer_message(
-    asset: AssetInfo,
-    amount: Uint128,
-    recipient: String,
-) -> Result<CosmosMsg, ContractError> {
-    match asset.clone() {
-        AssetInfo::Native(denom) => {
-            let bank_msg = BankMsg::Send {
-                to_address: recipient,
-                amount: vec![coin(amount.u128(), denom)],
-            };
+/// Returns cw20 asset for tokens with cw20 address and native asset otherwise
+pub(crate) fn get_token_asset_info(deps: Deps, denom: String) -> StdResult<AssetInfo> {
+    let cw20_address = SUPPORTED_TOKENS
+        .load(deps.storage, denom.clone())?
+        .cw20_address;
 
-            Ok(CosmosMsg::Bank(bank_msg))
-        }
-        AssetInfo::Cw20(addr) => {
-            let transfer_msg = Cw20ExecuteMsg::Transfer { recipient, amount };
-            let wasm_msg = wasm_execute(addr, &transfer_msg, vec![])?;
-            Ok(CosmosMsg::Wasm(wasm_msg))
-        }
-        // Does not support 1155 currently
-        _ => Err(ContractError::InvalidAsset {
-            asset: asset.to_string(),
-        }),
+    Ok(match cw20_address {
+        Some(cw20_address) => AssetInfo::Cw20(Addr::unchecked(cw20_address)),
+        None => AssetInfo::Native(denom),
+    })
+}
+
+pub fn is_collateral_release_allowed(
+    deps: Deps,
+    env: Env,
+    user: String,
+    denom: String,
+    token_amount: u128,
+) -> StdResult<bool> {
+    let sum_borrow_balance_usd = get_user_borrowed_usd(deps, env.clone(), user.clone())?.u128();
+
+    if sum_borrow_balance_usd == 0 {
+        return Ok(true);
+    }
+
+    let token_decimals = get_token_decimal(deps, denom.clone())?.u128() as u32;
+
+    let e_mode_category = get_user_e_mode_category(deps, user.clone())?;
+
+    let price = fetch_e_mode_price_by_token(deps, env.clone(), &e_mode_category, denom)?.u128();
+
+    let token_amount_usd = Decimal::from_i128_with_scale(token_amount as i128, token_decimals)
+        .mul(Decimal::from_i128_with_scale(price as i128, USD_DECIMALS))
+        .to_u128_with_decimals(USD_DECIMALS)?;
+
+    let sum_collateral_balance_usd =
+        get_user_collateral_usd(deps, env.clone(), user.clone())?.u128();
+
+    let user_liquidation_threshold = get_user_liquidation_threshold(deps, env, user)?.u128();
+
+    if user_liquidation_threshold == 0 {
+        return Ok(false);
     }
+
+    Ok(
+        sum_borrow_balance_usd * HUNDRED_PERCENT / user_liquidation_threshold
+            < sum_collateral_balance_usd.saturating_sub(token_amount_usd),
+    )
 }
 
-pub fn execute_deposit_native(
+pub fn execute_transfer_mm_token(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    sender: String,
+    recipient: String,
+    amount: Uint128,
 ) -> Result<Response, ContractError> {
     ensure_ne!(
         true,
@@ -1936,169 +3182,732 @@ pub fn execute_deposit_native(
         ContractError::ProtocolIsPaused {}
     );
 
-    ensure!(!info.funds.is_empty(), ContractError::CoinNotFound {});
-    one_coin(&info)?;
-
-    let deposited_token = info.funds.first().unwrap();
-    let deposited_token_amount = deposited_token.amount.u128();
+    // transfers are accepted only from the receipt token registered for the market
+    let denom = MM_TOKEN_ADDRESS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .find(|(_, mm_token_address)| *mm_token_address == info.sender)
+        .map(|(denom, _)| denom)
+        .ok_or(ContractError::ForMmTokenContractOnly {})?;
 
     ensure!(
-        deposited_token_amount > 0,
+        !amount.is_zero(),
         ContractError::InvalidFunds {
-            msg: "Cannot send 0 amount to deposit".to_string()
+            msg: "Cannot transfer 0 amount of mmTokens".to_string()
         }
     );
 
+    let sender = deps.api.addr_validate(&sender)?.to_string();
+    let recipient = deps.api.addr_validate(&recipient)?.to_string();
+
+    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+
+    let sender_mm_token_balance = USER_MM_TOKEN_BALANCE
+        .load(deps.storage, (sender.clone(), denom.clone()))
+        .unwrap_or_default();
+
     ensure!(
-        SUPPORTED_TOKENS.has(deps.storage, deposited_token.denom.clone()),
-        ContractError::TokenNotSupported {}
+        sender_mm_token_balance >= amount,
+        ContractError::NotEnoughMmTokenBalance {}
     );
 
-    execute_update_liquidity_index_data(&mut deps, env.clone(), deposited_token.denom.clone())?;
+    // the same health check as for disabling the collateral
+    if user_deposit_as_collateral(deps.as_ref(), sender.clone(), denom.clone())? {
+        if !get_user_borrowed_usd(deps.as_ref(), env.clone(), sender.clone())?.is_zero() {
+            ensure_fresh_prices(deps.as_ref(), env.clone(), sender.clone(), denom.clone())?;
+        }
 
-    let token_decimals = get_token_decimal(deps.as_ref(), deposited_token.denom.clone())
-        .unwrap()
-        .u128() as u32;
+        let token_decimals = get_token_decimal(deps.as_ref(), denom.clone())?.u128() as u32;
 
-    let mm_token_price =
-        get_mm_token_price(deps.as_ref(), env.clone(), deposited_token.denom.clone())
-            .unwrap()
-            .u128();
+        let mm_token_price = get_mm_token_price(deps.as_ref(), env.clone(), denom.clone())?.u128();
 
-    let deposited_mm_token_amount =
-        Decimal::from_i128_with_scale(deposited_token_amount as i128, token_decimals)
-            .div(Decimal::from_i128_with_scale(
+        let token_amount = Decimal::from_i128_with_scale(amount.u128() as i128, token_decimals)
+            .mul(Decimal::from_i128_with_scale(
                 mm_token_price as i128,
                 token_decimals,
             ))
-            .to_u128_with_decimals(token_decimals)
-            .unwrap();
+            .to_u128_with_decimals(token_decimals)?;
 
-    let user_current_mm_token_balance = USER_MM_TOKEN_BALANCE
-        .load(
-            deps.storage,
-            (info.sender.to_string(), deposited_token.denom.clone()),
-        )
-        .unwrap_or_else(|_| Uint128::zero());
+        ensure!(
+            is_collateral_release_allowed(
+                deps.as_ref(),
+                env.clone(),
+                sender.clone(),
+                denom.clone(),
+                token_amount,
+            )?,
+            ContractError::NotEnoughCollateralToTransfer {}
+        );
+    }
 
-    let new_user_mm_token_balance =
-        user_current_mm_token_balance.u128() + deposited_mm_token_amount;
+    for user in [sender.clone(), recipient.clone()] {
+        update_user_rewards(
+            &mut deps,
+            env.clone(),
+            user,
+            denom.clone(),
+            RewardSide::Supply,
+        )?;
+    }
 
     USER_MM_TOKEN_BALANCE.save(
         deps.storage,
-        (info.sender.to_string(), deposited_token.denom.clone()),
-        &Uint128::from(new_user_mm_token_balance),
+        (sender.clone(), denom.clone()),
+        &(sender_mm_token_balance - amount),
     )?;
 
-    let mut resp = Response::default().add_attributes(vec![
-        attr("action", "deposit"),
-        attr("depositee", info.sender.to_string()),
-        attr("amount", Uint128::from(deposited_token_amount)),
-    ]);
+    USER_MM_TOKEN_BALANCE.update(
+        deps.storage,
+        (recipient.clone(), denom.clone()),
+        |balance| -> StdResult<Uint128> { Ok(balance.unwrap_or_default() + amount) },
+    )?;
 
-    Ok(resp)
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "transfer_mm_token"),
+        attr("denom", denom),
+        attr("sender", sender),
+        attr("recipient", recipient),
+        attr("amount", amount),
+    ]))
 }
 
-pub fn execute_borrow(
+pub fn execute_withdraw_reserves(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    amount: Uint128,
     denom: String,
+    amount: Uint128,
+    recipient: String,
 ) -> Result<Response, ContractError> {
-    ensure_ne!(
-        true,
-        is_paused(deps.as_ref())?,
-        ContractError::ProtocolIsPaused {}
-    );
+    ensure_role(deps.as_ref(), &info.sender, Role::Treasury)?;
 
     ensure!(
         SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
         ContractError::TokenNotSupported {}
     );
-    let mut resp = Response::default();
-
-    let available_to_borrow_amount = get_available_to_borrow(
-        deps.as_ref(),
-        env.clone(),
-        info.sender.to_string(),
-        denom.clone(),
-    )
-        .unwrap()
-        .u128();
 
     ensure!(
-        available_to_borrow_amount >= amount.u128(),
-        ContractError::AmountToBeBorrowedIsNotAvailable {}
+        !amount.is_zero(),
+        ContractError::InvalidFunds {
+            msg: "Cannot withdraw 0 amount of reserves".to_string()
+        }
     );
 
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+
+    let accrued_reserves = PROTOCOL_RESERVES
+        .load(deps.storage, denom.clone())
+        .unwrap_or_default();
+
     ensure!(
-        get_available_liquidity_by_token(deps.as_ref(), env.clone(), denom.clone())
-            .unwrap()
-            .u128()
-            >= amount.u128(),
-        ContractError::NotEnoughLiquidityToBorrow {}
+        amount.u128() <= accrued_reserves,
+        ContractError::NotEnoughProtocolReserves {}
     );
 
-    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+    // reserves that are lent out at the moment can't be withdrawn
+    let contract_balance =
+        get_contract_balance_by_token(deps.as_ref(), env.clone(), denom.clone())?;
 
-    let user_borrow_amount_with_interest = get_user_borrow_amount_with_interest(
-        deps.as_ref(),
-        env.clone(),
-        info.sender.to_string(),
-        denom.clone(),
-    )
-        .unwrap()
-        .u128();
+    ensure!(
+        amount <= contract_balance,
+        ContractError::NotEnoughProtocolReserves {}
+    );
 
-    let user_borrowing_info = get_user_borrowing_info(
-        deps.as_ref(),
-        env.clone(),
-        info.sender.to_string().clone(),
+    PROTOCOL_RESERVES.save(
+        deps.storage,
         denom.clone(),
-    )
-        .unwrap();
+        &(accrued_reserves - amount.u128()),
+    )?;
 
-    let new_user_borrow_amount: u128 = user_borrow_amount_with_interest + amount.u128();
+    let asset = get_token_asset_info(deps.as_ref(), denom.clone())?;
+
+    Ok(Response::new()
+        .add_message(generate_transfer_message(
+            asset,
+            amount,
+            recipient.to_string(),
+        )?)
+        .add_attributes(vec![
+            attr("action", "withdraw_reserves"),
+            attr("denom", denom),
+            attr("amount", amount),
+            attr("recipient", recipient),
+        ]))
+}
 
-    let current_interest_rate = get_interest_rate(deps.as_ref(), env.clone(), denom.clone())
-        .unwrap()
-        .u128();
+pub fn ensure_market_active(deps: Deps, denom: String) -> Result<(), ContractError> {
+    ensure!(
+        get_market_status(deps, denom.clone())? == MarketStatus::Active,
+        ContractError::MarketNotActive { denom }
+    );
 
-    let borrowed_token_decimals = get_token_decimal(deps.as_ref(), denom.clone())
-        .unwrap()
-        .u128() as u32;
+    Ok(())
+}
 
-    let average_interest_rate = (Decimal::from_i128_with_scale(
-        user_borrow_amount_with_interest as i128,
-        borrowed_token_decimals,
-    )
-        .mul(Decimal::from_i128_with_scale(
-            user_borrowing_info.average_interest_rate.u128() as i128,
-            INTEREST_RATE_DECIMALS,
-        ))
-        .add(
-            Decimal::from_i128_with_scale(amount.u128() as i128, borrowed_token_decimals).mul(
-                Decimal::from_i128_with_scale(current_interest_rate as i128, INTEREST_RATE_DECIMALS),
-            ),
-        ))
-        .div(Decimal::from_i128_with_scale(
-            new_user_borrow_amount as i128,
-            borrowed_token_decimals,
-        ))
-        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-        .unwrap();
+/// Fails if any price that the user position depends on is outdated
+pub fn ensure_fresh_prices(
+    deps: Deps,
+    env: Env,
+    user: String,
+    denom: String,
+) -> Result<(), ContractError> {
+    let e_mode_category = get_user_e_mode_category(deps, user.clone())?;
+
+    for token in get_supported_tokens(deps)?.supported_tokens {
+        let is_position_token = token.denom == denom
+            || user_deposit_as_collateral(deps, user.clone(), token.denom.clone())?
+            || !get_user_borrow_amount_with_interest(
+                deps,
+                env.clone(),
+                user.clone(),
+                token.denom.clone(),
+            )?
+            .is_zero();
+
+        // the position is valued with the category oracle if the user is in e-mode
+        if is_position_token {
+            ensure!(
+                is_e_mode_price_fresh(deps, env.clone(), &e_mode_category, token.denom.clone())?,
+                ContractError::StalePrice { denom: token.denom }
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts Pyth price with its own exponent into the price with USD_DECIMALS
+pub fn normalize_pyth_price(
+    price: Price,
+    max_confidence_ratio: u128,
+) -> Result<u128, ContractError> {
+    ensure!(price.price > 0, ContractError::NonPositivePrice {});
+
+    let price_value = price.price as u128;
+
+    ensure!(
+        (price.conf as u128) * HUNDRED_PERCENT <= price_value * max_confidence_ratio,
+        ContractError::PriceConfidenceTooWide {}
+    );
+
+    let exponent = price.expo + USD_DECIMALS as i32;
+
+    let normalized_price = if exponent >= 0 {
+        10u128
+            .checked_pow(exponent as u32)
+            .and_then(|multiplier| price_value.checked_mul(multiplier))
+            .ok_or(ContractError::InvalidPriceExponent {})?
+    } else {
+        10u128
+            .checked_pow(exponent.unsigned_abs())
+            .map_or(0u128, |divider| price_value / divider)
+    };
+
+    // a price below the USD_DECIMALS precision would value the asset at zero
+    ensure!(normalized_price > 0, ContractError::NonPositivePrice {});
+
+    Ok(normalized_price)
+}
+
+fn validate_price_source(
+    deps: Deps,
+    denom: String,
+    source: &PriceSource,
+) -> Result<(), ContractError> {
+    match source {
+        PriceSource::Pyth {} => ensure!(
+            PRICE_FEED_IDS.has(deps.storage, denom),
+            ContractError::InvalidPriceSources {}
+        ),
+        PriceSource::Manual {} => {}
+        PriceSource::Band { contract_addr, .. } | PriceSource::Twap { contract_addr, .. } => {
+            deps.api.addr_validate(contract_addr)?;
+        }
+        PriceSource::Fixed { price } => {
+            ensure!(!price.is_zero(), ContractError::InvalidPriceSources {})
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends the assets to the recipient and checks in a reply that they are returned with the fee
+pub fn execute_flash_loan(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<Coin>,
+    recipient: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    ensure!(!assets.is_empty(), ContractError::InvalidFlashLoanAssets {});
+
+    let mut flash_loan = vec![];
+    let mut fees = vec![];
+    let mut resp = Response::new();
+
+    for asset in assets.iter() {
+        ensure!(
+            !asset.amount.is_zero()
+                && !flash_loan
+                    .iter()
+                    .any(|flash_loan_data: &FlashLoanData| flash_loan_data.denom == asset.denom),
+            ContractError::InvalidFlashLoanAssets {}
+        );
+
+        ensure!(
+            SUPPORTED_TOKENS.has(deps.storage, asset.denom.clone()),
+            ContractError::TokenNotSupported {}
+        );
+
+        ensure_action_not_paused(deps.as_ref(), &asset.denom, PauseAction::FlashLoan)?;
+        ensure_market_active(deps.as_ref(), asset.denom.clone())?;
+
+        ensure!(
+            get_available_liquidity_by_token(deps.as_ref(), env.clone(), asset.denom.clone())?
+                >= asset.amount,
+            ContractError::NotEnoughLiquidityToBorrow {}
+        );
+
+        execute_update_liquidity_index_data(&mut deps, env.clone(), asset.denom.clone())?;
+
+        let fee = asset.amount.multiply_ratio(
+            get_flash_loan_fee(deps.as_ref(), asset.denom.clone())?,
+            HUNDRED_PERCENT,
+        );
+
+        flash_loan.push(FlashLoanData {
+            denom: asset.denom.clone(),
+            amount: asset.amount,
+            fee,
+            balance_before: get_contract_balance_by_token(
+                deps.as_ref(),
+                env.clone(),
+                asset.denom.clone(),
+            )?,
+        });
+
+        fees.push(coin(fee.u128(), asset.denom.clone()));
+
+        resp = resp.add_message(generate_transfer_message(
+            get_token_asset_info(deps.as_ref(), asset.denom.clone())?,
+            asset.amount,
+            recipient.to_string(),
+        )?);
+    }
+
+    FLASH_LOAN.save(deps.storage, &flash_loan)?;
+
+    // the repayment is checked in the reply once the recipient callback is finished
+    let callback = wasm_execute(
+        recipient.clone(),
+        &FlashLoanReceiverMsg::ExecuteOperation {
+            initiator: info.sender.to_string(),
+            assets,
+            fees,
+            msg,
+        },
+        vec![],
+    )?;
+
+    Ok(resp
+        .add_submessage(SubMsg::reply_on_success(callback, FLASH_LOAN_REPLY_ID))
+        .add_attributes(vec![
+            attr("action", "flash_loan"),
+            attr("initiator", info.sender.to_string()),
+            attr("recipient", recipient.to_string()),
+        ]))
+}
+
+fn execute_flash_loan_repayment_check(
+    mut deps: DepsMut,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let flash_loan = FLASH_LOAN.load(deps.storage)?;
+
+    FLASH_LOAN.remove(deps.storage);
+
+    let mut resp = Response::new().add_attribute("action", "flash_loan_repaid");
+
+    for flash_loan_data in flash_loan {
+        let contract_balance = get_contract_balance_by_token(
+            deps.as_ref(),
+            env.clone(),
+            flash_loan_data.denom.clone(),
+        )?;
+
+        ensure!(
+            contract_balance >= flash_loan_data.balance_before + flash_loan_data.fee,
+            ContractError::FlashLoanNotRepaid {
+                denom: flash_loan_data.denom
+            }
+        );
+
+        execute_distribute_to_depositors(
+            &mut deps,
+            env.clone(),
+            flash_loan_data.denom.clone(),
+            flash_loan_data.fee.u128(),
+        )?;
+
+        resp = resp.add_attribute(
+            "fee",
+            coin(flash_loan_data.fee.u128(), flash_loan_data.denom).to_string(),
+        );
+    }
+
+    Ok(resp)
+}
+
+/// Generates a transfer message given an asset and an amount
+pub(crate) fn generate_transfer_message(
+    asset: AssetInfo,
+    amount: Uint128,
+    recipient: String,
+) -> Result<CosmosMsg, ContractError> {
+    match asset.clone() {
+        AssetInfo::Native(denom) => {
+            let bank_msg = BankMsg::Send {
+                to_address: recipient,
+                amount: vec![coin(amount.u128(), denom)],
+            };
+
+            Ok(CosmosMsg::Bank(bank_msg))
+        }
+        AssetInfo::Cw20(addr) => {
+            let transfer_msg = Cw20ExecuteMsg::Transfer { recipient, amount };
+            let wasm_msg = wasm_execute(addr, &transfer_msg, vec![])?;
+            Ok(CosmosMsg::Wasm(wasm_msg))
+        }
+        // Does not support 1155 currently
+        _ => Err(ContractError::InvalidAsset {
+            asset: asset.to_string(),
+        }),
+    }
+}
+
+pub fn execute_deposit_native(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    on_behalf_of: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure!(!info.funds.is_empty(), ContractError::CoinNotFound {});
+    one_coin(&info)?;
+
+    let depositee = get_account_or_sender(deps.as_ref(), &info.sender, on_behalf_of)?;
+
+    let deposited_token = info.funds.first().unwrap();
+    let deposited_token_amount = deposited_token.amount.u128();
+
+    ensure!(
+        deposited_token_amount > 0,
+        ContractError::InvalidFunds {
+            msg: "Cannot send 0 amount to deposit".to_string()
+        }
+    );
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, deposited_token.denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    ensure_action_not_paused(deps.as_ref(), &deposited_token.denom, PauseAction::Deposit)?;
+    ensure_market_active(deps.as_ref(), deposited_token.denom.clone())?;
+
+    execute_update_liquidity_index_data(&mut deps, env.clone(), deposited_token.denom.clone())?;
+
+    ensure!(
+        get_market_caps_headroom(deps.as_ref(), env.clone(), deposited_token.denom.clone())?
+            .supply_headroom
+            .is_none_or(|supply_headroom| deposited_token.amount <= supply_headroom),
+        ContractError::SupplyCapExceeded {}
+    );
+
+    let token_decimals = get_token_decimal(deps.as_ref(), deposited_token.denom.clone())
+        .unwrap()
+        .u128() as u32;
+
+    let mm_token_price =
+        get_mm_token_price(deps.as_ref(), env.clone(), deposited_token.denom.clone())
+            .unwrap()
+            .u128();
+
+    let deposited_mm_token_amount =
+        Decimal::from_i128_with_scale(deposited_token_amount as i128, token_decimals)
+            .div(Decimal::from_i128_with_scale(
+                mm_token_price as i128,
+                token_decimals,
+            ))
+            .to_u128_with_decimals(token_decimals)
+            .unwrap();
+
+    let user_current_mm_token_balance = USER_MM_TOKEN_BALANCE
+        .load(
+            deps.storage,
+            (depositee.clone(), deposited_token.denom.clone()),
+        )
+        .unwrap_or_else(|_| Uint128::zero());
+
+    let new_user_mm_token_balance =
+        user_current_mm_token_balance.u128() + deposited_mm_token_amount;
+
+    update_user_rewards(
+        &mut deps,
+        env.clone(),
+        depositee.clone(),
+        deposited_token.denom.clone(),
+        RewardSide::Supply,
+    )?;
+
+    USER_MM_TOKEN_BALANCE.save(
+        deps.storage,
+        (depositee.clone(), deposited_token.denom.clone()),
+        &Uint128::from(new_user_mm_token_balance),
+    )?;
+
+    let resp = Response::default().add_attributes(vec![
+        attr("action", "deposit"),
+        attr("sender", info.sender.to_string()),
+        attr("depositee", depositee),
+        attr("amount", Uint128::from(deposited_token_amount)),
+    ]);
+
+    Ok(resp)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_borrow(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    denom: String,
+    rate_mode: RateMode,
+    recipient: Option<String>,
+    on_behalf_of: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure_action_not_paused(deps.as_ref(), &denom, PauseAction::Borrow)?;
+    ensure_market_active(deps.as_ref(), denom.clone())?;
+
+    let recipient = get_account_or_sender(deps.as_ref(), &info.sender, recipient)?;
+    let borrower = get_account_or_sender(deps.as_ref(), &info.sender, on_behalf_of)?;
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    ensure_fresh_prices(deps.as_ref(), env.clone(), borrower.clone(), denom.clone())?;
+
+    let mut resp = Response::default();
+
+    let available_to_borrow_amount =
+        get_available_to_borrow(deps.as_ref(), env.clone(), borrower.clone(), denom.clone())?
+            .u128();
+
+    ensure!(
+        available_to_borrow_amount >= amount.u128(),
+        ContractError::AmountToBeBorrowedIsNotAvailable {}
+    );
+
+    // the delegatee borrows against the collateral of the delegator within its allowance
+    if borrower != info.sender {
+        use_borrow_allowance(
+            &mut deps,
+            env.clone(),
+            borrower.clone(),
+            info.sender.to_string(),
+            denom.clone(),
+            amount,
+        )?;
+    }
+
+    ensure!(
+        get_available_liquidity_by_token(deps.as_ref(), env.clone(), denom.clone())
+            .unwrap()
+            .u128()
+            >= amount.u128(),
+        ContractError::NotEnoughLiquidityToBorrow {}
+    );
+
+    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+
+    ensure!(
+        get_market_caps_headroom(deps.as_ref(), env.clone(), denom.clone())?
+            .borrow_headroom
+            .is_none_or(|borrow_headroom| amount <= borrow_headroom),
+        ContractError::BorrowCapExceeded {}
+    );
+
+    if let Some(category_id) = USER_E_MODE.may_load(deps.storage, borrower.clone())? {
+        ensure!(
+            E_MODE_CATEGORIES
+                .load(deps.storage, category_id)?
+                .denoms
+                .contains(&denom),
+            ContractError::NotInEModeCategory {}
+        );
+    }
+
+    // borrows against isolated collateral are limited to assets borrowable in isolation
+    if let Some(isolated_collateral) =
+        get_user_isolated_collateral(deps.as_ref(), borrower.clone())?
+    {
+        ensure!(
+            get_isolation_configuration(deps.as_ref(), denom.clone())?.borrowable_in_isolation,
+            ContractError::NotBorrowableInIsolation {}
+        );
+
+        let isolated_debt =
+            get_isolated_debt(deps.as_ref(), env.clone(), isolated_collateral.clone())?
+                + get_token_amount_usd(deps.as_ref(), env.clone(), denom.clone(), amount.u128())?;
+
+        ensure!(
+            isolated_debt
+                <= get_isolation_configuration(deps.as_ref(), isolated_collateral.clone())?
+                    .debt_ceiling,
+            ContractError::IsolationDebtCeilingExceeded {}
+        );
+
+        ISOLATED_DEBT.update(
+            deps.storage,
+            (isolated_collateral, denom.clone()),
+            |isolated_debt| -> StdResult<_> { Ok(isolated_debt.unwrap_or_default() + amount) },
+        )?;
+    }
+
+    match rate_mode {
+        RateMode::Variable => execute_variable_borrow(
+            &mut deps,
+            env.clone(),
+            borrower.clone(),
+            denom.clone(),
+            amount.u128(),
+        )?,
+        RateMode::Stable => execute_stable_borrow(
+            &mut deps,
+            env.clone(),
+            borrower.clone(),
+            denom.clone(),
+            amount.u128(),
+        )?,
+        RateMode::Term { maturity } => {
+            let loan_id = execute_term_borrow(
+                &mut deps,
+                env.clone(),
+                borrower.clone(),
+                denom.clone(),
+                amount.u128(),
+                maturity,
+            )?;
+
+            resp = resp.add_attribute("term_loan_id", loan_id.to_string());
+        }
+    }
+
+    let cw20_address = SUPPORTED_TOKENS
+        .load(deps.storage, denom.clone())
+        .unwrap()
+        .cw20_address;
+
+    let asset = if let Some(cw20_address) = cw20_address {
+        AssetInfo::Cw20(Addr::unchecked(cw20_address))
+    } else {
+        AssetInfo::Native(denom.clone())
+    };
+
+    resp = resp.add_message(generate_transfer_message(
+        asset.clone(),
+        amount,
+        recipient.clone(),
+    )?);
+
+    Ok(resp.add_attributes(vec![
+        attr("action", "borrow"),
+        attr("amount", amount),
+        attr("user", borrower.clone()),
+        attr("sender", info.sender.to_string()),
+        attr("recipient", recipient),
+        attr("denom", denom.clone().to_string()),
+    ]))
+}
+
+// adds the amount to the variable position of the user, its rate is averaged with the current one
+fn execute_variable_borrow(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+    amount: u128,
+) -> Result<(), ContractError> {
+    update_user_rewards(
+        deps,
+        env.clone(),
+        user.clone(),
+        denom.clone(),
+        RewardSide::Borrow,
+    )?;
+
+    let user_borrow_amount_with_interest = get_user_variable_borrow_amount_with_interest(
+        deps.as_ref(),
+        env.clone(),
+        user.clone(),
+        denom.clone(),
+    )
+    .unwrap()
+    .u128();
+
+    let user_borrowing_info =
+        get_user_borrowing_info(deps.as_ref(), env.clone(), user.clone(), denom.clone()).unwrap();
+
+    let new_user_borrow_amount: u128 = user_borrow_amount_with_interest + amount;
+
+    let current_interest_rate = get_interest_rate(deps.as_ref(), env.clone(), denom.clone())
+        .unwrap()
+        .u128();
+
+    let borrowed_token_decimals = get_token_decimal(deps.as_ref(), denom.clone())
+        .unwrap()
+        .u128() as u32;
+
+    let average_interest_rate = (Decimal::from_i128_with_scale(
+        user_borrow_amount_with_interest as i128,
+        borrowed_token_decimals,
+    )
+    .mul(Decimal::from_i128_with_scale(
+        user_borrowing_info.average_interest_rate.u128() as i128,
+        INTEREST_RATE_DECIMALS,
+    ))
+    .add(
+        Decimal::from_i128_with_scale(amount as i128, borrowed_token_decimals).mul(
+            Decimal::from_i128_with_scale(current_interest_rate as i128, INTEREST_RATE_DECIMALS),
+        ),
+    ))
+    .div(Decimal::from_i128_with_scale(
+        new_user_borrow_amount as i128,
+        borrowed_token_decimals,
+    ))
+    .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
+    .unwrap();
 
     // updating user borrowing info
     let new_user_borrowing_info = UserBorrowingInfo {
-        borrowed_amount: Uint128::from(new_user_borrow_amount.clone()),
+        denom: denom.clone(),
+        borrowed_amount: Uint128::from(new_user_borrow_amount),
         average_interest_rate: Uint128::from(average_interest_rate),
         timestamp: env.block.time,
     };
 
     let total_borrow_data = get_total_borrow_data(deps.as_ref(), denom.clone()).unwrap_or_default();
 
-    let expected_annual_interest_income = total_borrow_data.expected_annual_interest_income
+    let expected_annual_interest_income = total_borrow_data.expected_annual_interest_income.u128()
         - Decimal::from_i128_with_scale(
         user_borrowing_info.borrowed_amount.u128() as i128,
         borrowed_token_decimals,
@@ -2117,7 +3926,7 @@ pub fn execute_borrow(
         .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
         .unwrap();
 
-    let total_borrowed_amount = total_borrow_data.total_borrowed_amount
+    let total_borrowed_amount = total_borrow_data.total_borrowed_amount.u128()
         - user_borrowing_info.borrowed_amount.u128()
         + new_user_borrow_amount;
 
@@ -2135,121 +3944,148 @@ pub fn execute_borrow(
 
     let new_total_borrow_data = TotalBorrowData {
         denom: denom.clone(),
-        total_borrowed_amount: total_borrowed_amount,
-        expected_annual_interest_income: expected_annual_interest_income,
-        average_interest_rate: total_average_interest_rate,
+        total_borrowed_amount: Uint128::from(total_borrowed_amount),
+        expected_annual_interest_income: Uint128::from(expected_annual_interest_income),
+        average_interest_rate: Uint128::from(total_average_interest_rate),
+        variable_borrowed_amount: Uint128::from(
+            total_borrowed_amount
+                .saturating_sub(total_borrow_data.stable_borrowed_amount.u128())
+                .saturating_sub(total_borrow_data.term_borrowed_amount.u128()),
+        ),
+        stable_borrowed_amount: total_borrow_data.stable_borrowed_amount,
+        stable_average_interest_rate: total_borrow_data.stable_average_interest_rate,
+        term_borrowed_amount: total_borrow_data.term_borrowed_amount,
         timestamp: env.block.time,
     };
 
     USER_BORROWING_INFO.save(
         deps.storage,
-        (info.sender.to_string(), denom.clone()),
+        (user, denom.clone()),
         &new_user_borrowing_info,
     )?;
 
     TOTAL_BORROW_DATA.save(deps.storage, denom.clone(), &new_total_borrow_data)?;
 
-    let cw20_address = SUPPORTED_TOKENS
-        .load(deps.storage, denom.clone())
-        .unwrap()
-        .cw20_address;
+    Ok(())
+}
 
-    let asset = if cw20_address.is_some() {
-        AssetInfo::Cw20(Addr::unchecked(cw20_address.unwrap()))
-    } else {
-        AssetInfo::Native(denom.clone())
-    };
+pub fn execute_repay_user_borrowing(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+    amount: u128,
+) -> Result<u128, ContractError> {
+    // decreases user borrow by the given amount (capped at the borrow with interest)
+    // and returns the amount that was actually repaid, stable debt is repaid first
+    let stable_repay_amount =
+        execute_repay_stable_borrowing(deps, env.clone(), user.clone(), denom.clone(), amount)?;
+
+    let mut repay_amount = stable_repay_amount;
+    if amount > stable_repay_amount {
+        repay_amount += execute_repay_variable_borrowing(
+            deps,
+            env.clone(),
+            user.clone(),
+            denom.clone(),
+            amount - stable_repay_amount,
+        )?;
+    }
 
-    resp = resp.add_message(generate_transfer_message(
-        asset.clone(),
-        Uint128::from(amount),
-        info.sender.to_string(),
-    )?);
+    release_isolated_debt(deps, user, denom, repay_amount)?;
 
-    Ok(resp.add_attributes(vec![
-        attr("action", "borrow"),
-        attr("amount", Uint128::from(amount)),
-        attr("user", info.sender.clone().to_string()),
-        attr("denom", denom.clone().to_string()),
-    ]))
+    Ok(repay_amount)
 }
 
-pub fn execute_repay_native(
-    mut deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-) -> Result<Response, ContractError> {
-    ensure!(!info.funds.is_empty(), ContractError::CoinNotFound {});
-    one_coin(&info)?;
+// repaid debt frees the debt ceiling of the isolated collateral, repay works without a price
+pub(crate) fn release_isolated_debt(
+    deps: &mut DepsMut,
+    user: String,
+    denom: String,
+    repay_amount: u128,
+) -> StdResult<()> {
+    if let Some(isolated_collateral) = get_user_isolated_collateral(deps.as_ref(), user)? {
+        let key = (isolated_collateral, denom);
+
+        // repaid interest is not a part of the tracked debt
+        let isolated_debt = ISOLATED_DEBT
+            .may_load(deps.storage, key.clone())?
+            .unwrap_or_default()
+            .u128()
+            .saturating_sub(repay_amount);
 
-    let repay_token = info.funds.first().unwrap();
-    let mut repay_amount = repay_token.amount.u128();
+        if isolated_debt == 0 {
+            ISOLATED_DEBT.remove(deps.storage, key);
+        } else {
+            ISOLATED_DEBT.save(deps.storage, key, &Uint128::from(isolated_debt))?;
+        }
+    }
 
-    ensure!(
-        SUPPORTED_TOKENS.has(deps.storage, repay_token.denom.clone()),
-        ContractError::TokenNotSupported {}
-    );
+    Ok(())
+}
 
-    let user_borrowing_info = get_user_borrowing_info(
-        deps.as_ref(),
+fn execute_repay_variable_borrowing(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+    amount: u128,
+) -> Result<u128, ContractError> {
+    update_user_rewards(
+        deps,
         env.clone(),
-        info.sender.to_string().clone(),
-        repay_token.denom.clone(),
-    )
-        .unwrap();
+        user.clone(),
+        denom.clone(),
+        RewardSide::Borrow,
+    )?;
 
-    execute_update_liquidity_index_data(&mut deps, env.clone(), repay_token.denom.clone())?;
+    let user_borrowing_info =
+        get_user_borrowing_info(deps.as_ref(), env.clone(), user.clone(), denom.clone())?;
 
-    let user_borrow_amount_with_interest = get_user_borrow_amount_with_interest(
+    let user_borrow_amount_with_interest = get_user_variable_borrow_amount_with_interest(
         deps.as_ref(),
         env.clone(),
-        info.sender.to_string(),
-        repay_token.denom.clone(),
-    )
-        .unwrap()
-        .u128();
+        user.clone(),
+        denom.clone(),
+    )?
+    .u128();
 
-    let mut remaining_amount = 0u128;
+    let mut repay_amount = amount;
     let mut average_interest_rate = user_borrowing_info.average_interest_rate;
     if repay_amount >= user_borrow_amount_with_interest {
-        remaining_amount = repay_amount - user_borrow_amount_with_interest;
         repay_amount = user_borrow_amount_with_interest;
         average_interest_rate = Uint128::zero();
     }
 
     let new_user_borrowing_info = UserBorrowingInfo {
+        denom: denom.clone(),
         borrowed_amount: Uint128::from(user_borrow_amount_with_interest - repay_amount),
-        average_interest_rate: average_interest_rate,
+        average_interest_rate,
         timestamp: env.block.time,
     };
 
-    let total_borrow_data =
-        get_total_borrow_data(deps.as_ref(), repay_token.denom.clone()).unwrap_or_default();
+    let total_borrow_data = get_total_borrow_data(deps.as_ref(), denom.clone()).unwrap_or_default();
 
-    let repay_token_decimals = get_token_decimal(deps.as_ref(), repay_token.denom.clone())
-        .unwrap()
-        .u128() as u32;
+    let repay_token_decimals = get_token_decimal(deps.as_ref(), denom.clone())?.u128() as u32;
 
-    let expected_annual_interest_income = total_borrow_data.expected_annual_interest_income
+    let expected_annual_interest_income = total_borrow_data.expected_annual_interest_income.u128()
         + Decimal::from_i128_with_scale(
-        (user_borrow_amount_with_interest - user_borrowing_info.borrowed_amount.u128()) as i128,
-        repay_token_decimals,
-    )
-        .mul(Decimal::from_i128_with_scale(
-            (user_borrowing_info.average_interest_rate.u128() / HUNDRED) as i128,
-            INTEREST_RATE_DECIMALS,
-        ))
-        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-        .unwrap()
-        - Decimal::from_i128_with_scale((repay_amount) as i128, repay_token_decimals)
+            (user_borrow_amount_with_interest - user_borrowing_info.borrowed_amount.u128()) as i128,
+            repay_token_decimals,
+        )
         .mul(Decimal::from_i128_with_scale(
             (user_borrowing_info.average_interest_rate.u128() / HUNDRED) as i128,
             INTEREST_RATE_DECIMALS,
         ))
-        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-        .unwrap();
+        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)?
+        - Decimal::from_i128_with_scale(repay_amount as i128, repay_token_decimals)
+            .mul(Decimal::from_i128_with_scale(
+                (user_borrowing_info.average_interest_rate.u128() / HUNDRED) as i128,
+                INTEREST_RATE_DECIMALS,
+            ))
+            .to_u128_with_decimals(INTEREST_RATE_DECIMALS)?;
 
-    let total_borrowed_amount = total_borrow_data.total_borrowed_amount
+    let total_borrowed_amount = total_borrow_data.total_borrowed_amount.u128()
         + user_borrow_amount_with_interest
         - user_borrowing_info.borrowed_amount.u128()
         - repay_amount;
@@ -2258,44 +4094,98 @@ pub fn execute_repay_native(
     if total_borrowed_amount != 0u128 {
         total_average_interest_rate = HUNDRED
             * Decimal::from_i128_with_scale(
-            expected_annual_interest_income as i128,
-            INTEREST_RATE_DECIMALS,
-        )
+                expected_annual_interest_income as i128,
+                INTEREST_RATE_DECIMALS,
+            )
             .div(Decimal::from_i128_with_scale(
                 total_borrowed_amount as i128,
                 repay_token_decimals,
             ))
-            .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-            .unwrap();
+            .to_u128_with_decimals(INTEREST_RATE_DECIMALS)?;
     }
 
     let new_total_borrow_data = TotalBorrowData {
-        denom: repay_token.denom.clone(),
-        total_borrowed_amount: total_borrowed_amount,
-        expected_annual_interest_income: expected_annual_interest_income,
-        average_interest_rate: total_average_interest_rate,
+        denom: denom.clone(),
+        total_borrowed_amount: Uint128::from(total_borrowed_amount),
+        expected_annual_interest_income: Uint128::from(expected_annual_interest_income),
+        average_interest_rate: Uint128::from(total_average_interest_rate),
+        variable_borrowed_amount: Uint128::from(
+            total_borrowed_amount
+                .saturating_sub(total_borrow_data.stable_borrowed_amount.u128())
+                .saturating_sub(total_borrow_data.term_borrowed_amount.u128()),
+        ),
+        stable_borrowed_amount: total_borrow_data.stable_borrowed_amount,
+        stable_average_interest_rate: total_borrow_data.stable_average_interest_rate,
+        term_borrowed_amount: total_borrow_data.term_borrowed_amount,
         timestamp: env.block.time,
     };
 
     USER_BORROWING_INFO.save(
         deps.storage,
-        (info.sender.to_string(), repay_token.denom.clone()),
+        (user, denom.clone()),
         &new_user_borrowing_info,
     )?;
 
-    TOTAL_BORROW_DATA.save(
-        deps.storage,
+    TOTAL_BORROW_DATA.save(deps.storage, denom, &new_total_borrow_data)?;
+
+    Ok(repay_amount)
+}
+
+pub fn execute_repay_native(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    on_behalf_of: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure!(!info.funds.is_empty(), ContractError::CoinNotFound {});
+    one_coin(&info)?;
+
+    let repay_token = info.funds.first().unwrap();
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, repay_token.denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    ensure_action_not_paused(deps.as_ref(), &repay_token.denom, PauseAction::Repay)?;
+
+    let borrower = get_account_or_sender(deps.as_ref(), &info.sender, on_behalf_of)?;
+
+    execute_update_liquidity_index_data(&mut deps, env.clone(), repay_token.denom.clone())?;
+
+    let repaid_amount = execute_repay_user_borrowing(
+        &mut deps,
+        env,
+        borrower.clone(),
         repay_token.denom.clone(),
-        &new_total_borrow_data,
+        repay_token.amount.u128(),
     )?;
 
+    let remaining_amount = repay_token.amount.u128() - repaid_amount;
+
+    let mut resp = Response::new();
+
+    // overpayment is returned to the payer, not to the borrower
     if remaining_amount > 0 {
-        Ok(Response::new().add_message(BankMsg::Send {
+        resp = resp.add_message(BankMsg::Send {
             to_address: info.sender.to_string(),
             amount: coins(remaining_amount, repay_token.denom.clone()),
-        }))
-    } else {
-        Ok(Response::default())
+        });
+    }
+
+    Ok(resp.add_attributes(vec![
+        attr("action", "repay"),
+        attr("payer", info.sender.to_string()),
+        attr("borrower", borrower),
+        attr("repaid_amount", Uint128::from(repaid_amount)),
+    ]))
+}
+
+// the sender acts for itself unless another account is given
+fn get_account_or_sender(deps: Deps, sender: &Addr, account: Option<String>) -> StdResult<String> {
+    match account {
+        Some(account) => Ok(deps.api.addr_validate(&account)?.to_string()),
+        None => Ok(sender.to_string()),
     }
 }
 
@@ -2306,6 +4196,7 @@ pub fn execute_cw20_repay(
     sender: Addr,
     amount: Uint128,
     denom: String,
+    on_behalf_of: Option<String>,
 ) -> Result<Response, ContractError> {
     // only cw20 tokens must be sent, not a coins
     nonpayable(&info)?;
@@ -2322,6 +4213,10 @@ pub fn execute_cw20_repay(
         ContractError::TokenNotSupported {}
     );
 
+    ensure_action_not_paused(deps.as_ref(), &denom, PauseAction::Repay)?;
+
+    let borrower = get_account_or_sender(deps.as_ref(), &sender, on_behalf_of)?;
+
     ensure!(
         SUPPORTED_TOKENS
             .load(deps.storage, denom.clone())
@@ -2332,125 +4227,382 @@ pub fn execute_cw20_repay(
         ContractError::TokenNotSupported {}
     );
 
-    let user_borrowing_info = get_user_borrowing_info(
-        deps.as_ref(),
-        env.clone(),
-        sender.to_string().clone(),
+    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+
+    let repaid_amount = execute_repay_user_borrowing(
+        &mut deps,
+        env,
+        borrower.clone(),
         denom.clone(),
+        amount.u128(),
+    )?;
+
+    let remaining_amount = amount.u128() - repaid_amount;
+
+    if remaining_amount > 0 {
+        let cw20_address = SUPPORTED_TOKENS
+            .load(deps.storage, denom.clone())
+            .unwrap()
+            .cw20_address;
+
+        let resp = Response::default().add_message(generate_transfer_message(
+            AssetInfo::Cw20(Addr::unchecked(cw20_address.unwrap())),
+            Uint128::from(remaining_amount),
+            sender.to_string(),
+        )?);
+
+        Ok(resp.add_attributes(vec![
+            attr("action", "repay"),
+            attr("depositee", sender.to_string()),
+            attr("borrower", borrower),
+            attr("amount", amount),
+        ]))
+    } else {
+        Ok(Response::default().add_attributes(vec![
+            attr("action", "repay"),
+            attr("depositee", sender.to_string()),
+            attr("borrower", borrower),
+            attr("amount", amount),
+        ]))
+    }
+}
+
+pub fn execute_liquidation_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    collateral_denom: String,
+    receive_mm_token: bool,
+    term_loan_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure!(!info.funds.is_empty(), ContractError::CoinNotFound {});
+    one_coin(&info)?;
+
+    let repay_token = info.funds.first().unwrap();
+
+    execute_liquidation(
+        deps,
+        env,
+        info.sender.clone(),
+        user,
+        repay_token.denom.clone(),
+        repay_token.amount,
+        collateral_denom,
+        receive_mm_token,
+        term_loan_id,
     )
-        .unwrap();
+}
 
-    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+#[allow(clippy::too_many_arguments)]
+pub fn execute_cw20_liquidation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+    user: String,
+    collateral_denom: String,
+    receive_mm_token: bool,
+    term_loan_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    // only cw20 tokens must be sent, not a coins
+    nonpayable(&info)?;
+
+    // debt token is the cw20 token that was sent
+    let debt_denom = get_supported_tokens(deps.as_ref())?
+        .supported_tokens
+        .into_iter()
+        .find(|token| token.cw20_address == Some(info.sender.to_string()))
+        .ok_or(ContractError::TokenNotSupported {})?
+        .denom;
+
+    execute_liquidation(
+        deps,
+        env,
+        sender,
+        user,
+        debt_denom,
+        amount,
+        collateral_denom,
+        receive_mm_token,
+        term_loan_id,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_liquidation(
+    mut deps: DepsMut,
+    env: Env,
+    liquidator: Addr,
+    user: String,
+    debt_denom: String,
+    amount: Uint128,
+    collateral_denom: String,
+    receive_mm_token: bool,
+    term_loan_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    // liquidator repays the borrow with the sent funds, so no position in the protocol is needed
+    ensure!(
+        amount > Uint128::zero(),
+        ContractError::InvalidFunds {
+            msg: "Cannot liquidate 0 amount".to_string()
+        }
+    );
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, debt_denom.clone())
+            && SUPPORTED_TOKENS.has(deps.storage, collateral_denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    ensure_action_not_paused(deps.as_ref(), &debt_denom, PauseAction::Liquidate)?;
+    ensure_action_not_paused(deps.as_ref(), &collateral_denom, PauseAction::Liquidate)?;
 
-    let user_borrow_amount_with_interest = get_user_borrow_amount_with_interest(
+    let user_collateral_balance = get_deposit(
         deps.as_ref(),
         env.clone(),
-        sender.to_string(),
-        denom.clone(),
-    )
-        .unwrap()
-        .u128();
+        user.clone(),
+        collateral_denom.clone(),
+    )?
+    .balance
+    .u128();
 
-    let mut remaining_amount = 0u128;
-    let mut average_interest_rate = user_borrowing_info.average_interest_rate;
+    ensure!(
+        user_deposit_as_collateral(deps.as_ref(), user.clone(), collateral_denom.clone())?
+            && user_collateral_balance > 0,
+        ContractError::NotCollateralToken {}
+    );
 
-    let amount_to_repay = if amount.u128() >= user_borrow_amount_with_interest {
-        remaining_amount = amount.u128() - user_borrow_amount_with_interest;
-        average_interest_rate = Uint128::zero();
-        user_borrow_amount_with_interest.clone()
-    } else {
-        amount.u128()
+    ensure_fresh_prices(deps.as_ref(), env.clone(), user.clone(), debt_denom.clone())?;
+
+    let user_utilization_rate =
+        get_user_utilization_rate(deps.as_ref(), env.clone(), user.clone())?;
+
+    let user_liquidation_threshold =
+        get_user_liquidation_threshold(deps.as_ref(), env.clone(), user.clone())?;
+
+    // term loan that is liquidated instead of the open-ended debt of the user
+    let term_loan = term_loan_id
+        .map(|loan_id| load_term_loan(deps.as_ref(), loan_id))
+        .transpose()?;
+
+    if let Some(term_loan) = &term_loan {
+        ensure!(
+            term_loan.denom == debt_denom,
+            ContractError::InvalidFunds {
+                msg: "Funds must be in the token of the loan".to_string()
+            }
+        );
+    }
+
+    // overdue term loans are liquidatable regardless of the user health
+    let is_overdue = term_loan
+        .as_ref()
+        .is_some_and(|term_loan| is_term_loan_overdue(&env, term_loan));
+
+    ensure!(
+        is_overdue || user_utilization_rate >= user_liquidation_threshold,
+        ContractError::UserIsNotLiquidatable {}
+    );
+
+    execute_update_liquidity_index_data(&mut deps, env.clone(), debt_denom.clone())?;
+    execute_update_liquidity_index_data(&mut deps, env.clone(), collateral_denom.clone())?;
+
+    let user_borrow_amount_with_interest = match &term_loan {
+        Some(term_loan) => {
+            get_term_loan_amount_with_interest(deps.as_ref(), env.clone(), term_loan)?.u128()
+        }
+        None => {
+            get_user_variable_borrow_amount_with_interest(
+                deps.as_ref(),
+                env.clone(),
+                user.clone(),
+                debt_denom.clone(),
+            )?
+            .u128()
+                + get_user_stable_borrow_amount_with_interest(
+                    deps.as_ref(),
+                    env.clone(),
+                    user.clone(),
+                    debt_denom.clone(),
+                )?
+                .u128()
+        }
     };
 
-    let new_user_borrowing_info = UserBorrowingInfo {
-        borrowed_amount: Uint128::from(user_borrow_amount_with_interest.clone() - amount_to_repay),
-        average_interest_rate,
-        timestamp: env.block.time,
+    ensure!(
+        user_borrow_amount_with_interest > 0,
+        ContractError::NothingToLiquidate {}
+    );
+
+    // close factor is taken from the debt market, bonus from the collateral market,
+    // overdue term loans can be repaid in full
+    let close_factor = if is_overdue {
+        HUNDRED_PERCENT
+    } else {
+        get_liquidation_configuration(deps.as_ref(), debt_denom.clone())?.close_factor
     };
+    let liquidation_bonus =
+        get_liquidation_configuration(deps.as_ref(), collateral_denom.clone())?.liquidation_bonus;
 
-    let total_borrow_data = get_total_borrow_data(deps.as_ref(), denom.clone()).unwrap_or_default();
+    let max_repay_amount = user_borrow_amount_with_interest * close_factor / HUNDRED_PERCENT;
+    let mut repay_amount = amount.u128().min(max_repay_amount);
 
-    let repay_token_decimals = get_token_decimal(deps.as_ref(), denom.clone())
-        .unwrap()
-        .u128() as u32;
+    let debt_decimals = get_token_decimal(deps.as_ref(), debt_denom.clone())?.u128() as u32;
+    let collateral_decimals =
+        get_token_decimal(deps.as_ref(), collateral_denom.clone())?.u128() as u32;
 
-    let expected_annual_interest_income = total_borrow_data.expected_annual_interest_income
-        + Decimal::from_i128_with_scale(
-        (user_borrow_amount_with_interest - user_borrowing_info.borrowed_amount.u128()) as i128,
-        repay_token_decimals,
-    )
+    let debt_price = fetch_price_by_token(deps.as_ref(), env.clone(), debt_denom.clone())?.u128();
+    let collateral_price =
+        fetch_price_by_token(deps.as_ref(), env.clone(), collateral_denom.clone())?.u128();
+
+    let bonus_multiplier =
+        Decimal::from_i128_with_scale((HUNDRED_PERCENT + liquidation_bonus) as i128, 0)
+            .div(Decimal::from_i128_with_scale(HUNDRED_PERCENT as i128, 0));
+
+    // collateral worth the repaid value plus the liquidation bonus
+    let mut seized_amount = Decimal::from_i128_with_scale(repay_amount as i128, debt_decimals)
         .mul(Decimal::from_i128_with_scale(
-            (user_borrowing_info.average_interest_rate.u128() / HUNDRED) as i128,
-            INTEREST_RATE_DECIMALS,
+            debt_price as i128,
+            USD_DECIMALS,
         ))
-        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-        .unwrap()
-        - Decimal::from_i128_with_scale((amount_to_repay) as i128, repay_token_decimals)
-        .mul(Decimal::from_i128_with_scale(
-            (user_borrowing_info.average_interest_rate.u128() / HUNDRED) as i128,
-            INTEREST_RATE_DECIMALS,
+        .mul(bonus_multiplier)
+        .div(Decimal::from_i128_with_scale(
+            collateral_price as i128,
+            USD_DECIMALS,
         ))
-        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-        .unwrap();
+        .to_u128_with_decimals(collateral_decimals)?;
 
-    let total_borrowed_amount = total_borrow_data.total_borrowed_amount
-        + user_borrow_amount_with_interest
-        - user_borrowing_info.borrowed_amount.u128()
-        - amount_to_repay;
+    // if the collateral is not enough, liquidator repays only the part it covers
+    if seized_amount > user_collateral_balance {
+        seized_amount = user_collateral_balance;
 
-    let mut total_average_interest_rate = 0u128;
-    if total_borrowed_amount != 0u128 {
-        total_average_interest_rate = HUNDRED
-            * Decimal::from_i128_with_scale(
-            expected_annual_interest_income as i128,
-            INTEREST_RATE_DECIMALS,
-        )
+        repay_amount = Decimal::from_i128_with_scale(seized_amount as i128, collateral_decimals)
+            .mul(Decimal::from_i128_with_scale(
+                collateral_price as i128,
+                USD_DECIMALS,
+            ))
+            .div(bonus_multiplier)
             .div(Decimal::from_i128_with_scale(
-                total_borrowed_amount as i128,
-                repay_token_decimals,
+                debt_price as i128,
+                USD_DECIMALS,
             ))
-            .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
-            .unwrap();
+            .to_u128_with_decimals(debt_decimals)?;
     }
 
-    let new_total_borrow_data = TotalBorrowData {
-        denom: denom.clone(),
-        total_borrowed_amount: total_borrowed_amount,
-        expected_annual_interest_income: expected_annual_interest_income,
-        average_interest_rate: total_average_interest_rate,
-        timestamp: env.block.time,
+    ensure!(repay_amount > 0, ContractError::NothingToLiquidate {});
+
+    let repaid_amount = match term_loan_id {
+        Some(loan_id) => execute_repay_term_loan(&mut deps, env.clone(), loan_id, repay_amount)?,
+        None => execute_repay_user_borrowing(
+            &mut deps,
+            env.clone(),
+            user.clone(),
+            debt_denom.clone(),
+            repay_amount,
+        )?,
     };
 
-    USER_BORROWING_INFO.save(
+    // seized collateral is taken from the user deposit
+    let user_collateral_mm_token_balance = USER_MM_TOKEN_BALANCE
+        .load(deps.storage, (user.clone(), collateral_denom.clone()))
+        .unwrap_or_default()
+        .u128();
+
+    let seized_mm_token_amount = if seized_amount == user_collateral_balance {
+        user_collateral_mm_token_balance
+    } else {
+        let collateral_mm_token_price =
+            get_mm_token_price(deps.as_ref(), env.clone(), collateral_denom.clone())?.u128();
+
+        Decimal::from_i128_with_scale(seized_amount as i128, collateral_decimals)
+            .div(Decimal::from_i128_with_scale(
+                collateral_mm_token_price as i128,
+                collateral_decimals,
+            ))
+            .to_u128_with_decimals(collateral_decimals)?
+            .min(user_collateral_mm_token_balance)
+    };
+
+    update_user_rewards(
+        &mut deps,
+        env.clone(),
+        user.clone(),
+        collateral_denom.clone(),
+        RewardSide::Supply,
+    )?;
+
+    USER_MM_TOKEN_BALANCE.save(
         deps.storage,
-        (sender.to_string(), denom.clone()),
-        &new_user_borrowing_info,
+        (user.clone(), collateral_denom.clone()),
+        &Uint128::from(user_collateral_mm_token_balance - seized_mm_token_amount),
     )?;
 
-    TOTAL_BORROW_DATA.save(deps.storage, denom.clone(), &new_total_borrow_data)?;
+    let mut resp = Response::new();
 
-    if remaining_amount > 0 {
-        let cw20_address = SUPPORTED_TOKENS
-            .load(deps.storage, denom.clone())
-            .unwrap()
-            .cw20_address;
+    if receive_mm_token {
+        update_user_rewards(
+            &mut deps,
+            env.clone(),
+            liquidator.to_string(),
+            collateral_denom.clone(),
+            RewardSide::Supply,
+        )?;
 
-        let resp = Response::default().add_message(generate_transfer_message(
-            AssetInfo::Cw20(Addr::unchecked(cw20_address.unwrap())),
+        let liquidator_collateral_mm_token_balance = USER_MM_TOKEN_BALANCE
+            .load(
+                deps.storage,
+                (liquidator.to_string(), collateral_denom.clone()),
+            )
+            .unwrap_or_default()
+            .u128();
+
+        USER_MM_TOKEN_BALANCE.save(
+            deps.storage,
+            (liquidator.to_string(), collateral_denom.clone()),
+            &Uint128::from(liquidator_collateral_mm_token_balance + seized_mm_token_amount),
+        )?;
+    } else {
+        ensure!(
+            get_available_liquidity_by_token(deps.as_ref(), env.clone(), collateral_denom.clone())?
+                .u128()
+                >= seized_amount,
+            ContractError::NotEnoughLiquidityToLiquidate {}
+        );
+
+        resp = resp.add_message(generate_transfer_message(
+            get_token_asset_info(deps.as_ref(), collateral_denom.clone())?,
+            Uint128::from(seized_amount),
+            liquidator.to_string(),
+        )?);
+    }
+
+    // funds sent above the repaid amount are returned to the liquidator
+    let remaining_amount = amount.u128() - repaid_amount;
+    if remaining_amount > 0 {
+        resp = resp.add_message(generate_transfer_message(
+            get_token_asset_info(deps.as_ref(), debt_denom.clone())?,
             Uint128::from(remaining_amount),
-            sender.to_string(),
+            liquidator.to_string(),
         )?);
+    }
 
-        Ok(resp.add_attributes(vec![
-            attr("action", "repay"),
-            attr("depositee", sender.to_string()),
-            attr("amount", amount),
-        ]))
-    } else {
-        Ok(Response::default().add_attributes(vec![
-            attr("action", "repay"),
-            attr("depositee", sender.to_string()),
-            attr("amount", amount),
-        ]))
+    // debt that is left without any collateral is written off
+    if !has_collateral(deps.as_ref(), env.clone(), user.clone())? {
+        resp = resp.add_events(write_off_bad_debt(&mut deps, env.clone(), user.clone())?);
     }
+
+    Ok(resp
+        .add_attributes(vec![
+            attr("action", "liquidation"),
+            attr("user", user),
+            attr("liquidator", liquidator.to_string()),
+            attr("debt_denom", debt_denom),
+            attr("repaid_amount", Uint128::from(repaid_amount)),
+            attr("collateral_denom", collateral_denom),
+            attr("seized_amount", Uint128::from(seized_amount)),
+            attr("receive_mm_token", receive_mm_token.to_string()),
+        ])
+        .add_attributes(term_loan_id.map(|loan_id| attr("term_loan_id", loan_id.to_string()))))
 }
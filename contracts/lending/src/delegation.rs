@@ -0,0 +1,127 @@
+use crate::error::ContractError;
+use crate::msg::BorrowAllowance;
+use crate::state::{BORROW_ALLOWANCES, SUPPORTED_TOKENS};
+use cosmwasm_std::{
+    attr, ensure, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Timestamp, Uint128,
+};
+use cw_utils::nonpayable;
+
+pub fn get_borrow_allowance(
+    deps: Deps,
+    env: Env,
+    delegator: String,
+    delegatee: String,
+    denom: String,
+) -> StdResult<BorrowAllowance> {
+    let borrow_allowance = BORROW_ALLOWANCES
+        .may_load(deps.storage, (delegator, delegatee, denom))?
+        .unwrap_or_default();
+
+    if env.block.time >= borrow_allowance.expiry {
+        return Ok(BorrowAllowance {
+            amount: Uint128::zero(),
+            ..borrow_allowance
+        });
+    }
+
+    Ok(borrow_allowance)
+}
+
+/// Decreases the allowance of the delegatee by the amount borrowed on behalf of the delegator
+pub fn use_borrow_allowance(
+    deps: &mut DepsMut,
+    env: Env,
+    delegator: String,
+    delegatee: String,
+    denom: String,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let borrow_allowance = get_borrow_allowance(
+        deps.as_ref(),
+        env,
+        delegator.clone(),
+        delegatee.clone(),
+        denom.clone(),
+    )?;
+
+    ensure!(
+        amount <= borrow_allowance.amount,
+        ContractError::BorrowAllowanceExceeded {}
+    );
+
+    BORROW_ALLOWANCES.save(
+        deps.storage,
+        (delegator, delegatee, denom),
+        &BorrowAllowance {
+            amount: borrow_allowance.amount - amount,
+            ..borrow_allowance
+        },
+    )?;
+
+    Ok(())
+}
+
+pub fn execute_approve_delegation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegatee: String,
+    denom: String,
+    amount: Uint128,
+    expiry: Timestamp,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    let delegatee = deps.api.addr_validate(&delegatee)?;
+
+    ensure!(
+        delegatee != info.sender && expiry > env.block.time,
+        ContractError::InvalidDelegation {}
+    );
+
+    // a new approval replaces the remaining allowance
+    BORROW_ALLOWANCES.save(
+        deps.storage,
+        (
+            info.sender.to_string(),
+            delegatee.to_string(),
+            denom.clone(),
+        ),
+        &BorrowAllowance { amount, expiry },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "approve_delegation"),
+        attr("delegator", info.sender.to_string()),
+        attr("delegatee", delegatee.to_string()),
+        attr("denom", denom),
+        attr("amount", amount),
+        attr("expiry", expiry.seconds().to_string()),
+    ]))
+}
+
+pub fn execute_revoke_delegation(
+    deps: DepsMut,
+    info: MessageInfo,
+    delegatee: String,
+    denom: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    BORROW_ALLOWANCES.remove(
+        deps.storage,
+        (info.sender.to_string(), delegatee.clone(), denom.clone()),
+    );
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "revoke_delegation"),
+        attr("delegator", info.sender.to_string()),
+        attr("delegatee", delegatee),
+        attr("denom", denom),
+    ]))
+}
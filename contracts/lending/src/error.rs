@@ -1,5 +1,7 @@
 use cosmwasm_std::StdError;
 use cw_utils::PaymentError;
+
+use crate::msg::{PauseAction, Role};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,9 +12,18 @@ pub enum ContractError {
     #[error("Allowed for Admin Only")]
     ForAdminOnly {},
 
+    #[error("Allowed for Admin or {role} Only")]
+    ForRoleOnly { role: Role },
+
+    #[error("Allowed for Pending Owner Only")]
+    ForPendingOwnerOnly {},
+
     #[error("Allowed for Price Updater Contract Only")]
     ForPriceUpdaterContractOnly {},
 
+    #[error("Allowed for mmToken Contract Only")]
+    ForMmTokenContractOnly {},
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
 
@@ -42,4 +53,175 @@ pub enum ContractError {
 
     #[error("Protocol Is Paused")]
     ProtocolIsPaused {},
+
+    #[error("{action} Is Paused: {denom}")]
+    ActionIsPaused { action: PauseAction, denom: String },
+
+    #[error("User Borrowing Has Not Reached The Liquidation Threshold")]
+    UserIsNotLiquidatable {},
+
+    #[error("User Has No Borrow To Liquidate")]
+    NothingToLiquidate {},
+
+    #[error("Token Is Not Used As Collateral By User")]
+    NotCollateralToken {},
+
+    #[error("Not Enough Liquidity To Transfer Seized Collateral")]
+    NotEnoughLiquidityToLiquidate {},
+
+    #[error("Invalid Liquidation Configuration")]
+    InvalidLiquidationConfiguration {},
+
+    #[error("Invalid Oracle Configuration")]
+    InvalidOracleConfiguration {},
+
+    #[error("Non-Positive Price From Oracle")]
+    NonPositivePrice {},
+
+    #[error("Price Exponent Is Out Of Range")]
+    InvalidPriceExponent {},
+
+    #[error("Price Confidence Interval Is Too Wide")]
+    PriceConfidenceTooWide {},
+
+    #[error("Stale Price: {denom}")]
+    StalePrice { denom: String },
+
+    #[error("Invalid Price Sources")]
+    InvalidPriceSources {},
+
+    #[error("Price Sources Deviation Is Too High: {denom}")]
+    PriceDeviationTooHigh { denom: String },
+
+    #[error("Supply Cap Exceeded")]
+    SupplyCapExceeded {},
+
+    #[error("Borrow Cap Exceeded")]
+    BorrowCapExceeded {},
+
+    #[error("Isolated Collateral Cannot Be Combined With Other Collateral")]
+    MixedIsolatedCollateral {},
+
+    #[error("Asset Is Not Borrowable In Isolation Mode")]
+    NotBorrowableInIsolation {},
+
+    #[error("Isolation Mode Debt Ceiling Exceeded")]
+    IsolationDebtCeilingExceeded {},
+
+    #[error("Invalid E-Mode Category")]
+    InvalidEModeCategory {},
+
+    #[error("E-Mode Category Not Found")]
+    EModeCategoryNotFound {},
+
+    #[error("Asset Is Not In The User E-Mode Category")]
+    NotInEModeCategory {},
+
+    #[error("Leaving E-Mode Makes The Position Undercollateralized")]
+    EModeChangeUndercollateralized {},
+
+    #[error("Invalid Flash Loan Assets")]
+    InvalidFlashLoanAssets {},
+
+    #[error("Invalid Flash Loan Fee")]
+    InvalidFlashLoanFee {},
+
+    #[error("Flash Loan Is In Progress")]
+    FlashLoanInProgress {},
+
+    #[error("Flash Loan Is Not Repaid: {denom}")]
+    FlashLoanNotRepaid { denom: String },
+
+    #[error("Invalid Contract Name: {contract}")]
+    InvalidContractName { contract: String },
+
+    #[error("Invalid Contract Version")]
+    InvalidContractVersion {},
+
+    #[error("Contract Downgrade Is Not Allowed From Version {from_version}")]
+    ContractDowngradeNotAllowed { from_version: String },
+
+    #[error("Storage Downgrade Is Not Allowed From Version {from_version}")]
+    StorageDowngradeNotAllowed { from_version: u64 },
+
+    #[error("No Pending Ownership Transfer")]
+    NoPendingOwnership {},
+
+    #[error("Ownership Transfer Is Expired")]
+    OwnershipTransferExpired {},
+
+    #[error("Invalid Expiry")]
+    InvalidExpiry {},
+
+    #[error("Invalid Timelock Delay")]
+    InvalidTimelockDelay {},
+
+    #[error("Queued Action Not Found")]
+    QueuedActionNotFound {},
+
+    #[error("Queued Action Is Not Ready")]
+    QueuedActionNotReady {},
+
+    #[error("Market Is Not Active: {denom}")]
+    MarketNotActive { denom: String },
+
+    #[error("Invalid Market Status Transition")]
+    InvalidMarketStatusTransition {},
+
+    #[error("Market Must Be Wound Down Before Removal")]
+    MarketNotWoundDown {},
+
+    #[error("Market Has Outstanding Balances: {denom}")]
+    MarketHasOutstandingBalances { denom: String },
+
+    #[error("Market Already Exists: {denom}")]
+    MarketAlreadyExists { denom: String },
+
+    #[error("Invalid Rate Strategy")]
+    InvalidRateStrategy {},
+
+    #[error("Stable Rate Borrowing Is Not Enabled")]
+    StableRateBorrowingNotEnabled {},
+
+    #[error("Invalid Stable Rate Configuration")]
+    InvalidStableRateConfiguration {},
+
+    #[error("Stable Rate Rebalance Is Not Allowed")]
+    StableRateRebalanceNotAllowed {},
+
+    #[error("Invalid Maturity")]
+    InvalidMaturity {},
+
+    #[error("Term Loan Not Found")]
+    TermLoanNotFound {},
+
+    #[error("Invalid Emission Schedule")]
+    InvalidEmissionSchedule {},
+
+    #[error("User Has No Bad Debt")]
+    NoBadDebt {},
+
+    #[error("Not Enough Insurance Fund To Withdraw")]
+    NotEnoughInsuranceFund {},
+
+    #[error("Invalid Delegation")]
+    InvalidDelegation {},
+
+    #[error("Borrow Allowance Exceeded")]
+    BorrowAllowanceExceeded {},
+
+    #[error("Invalid Reserve Factor")]
+    InvalidReserveFactor {},
+
+    #[error("Not Enough Protocol Reserves To Withdraw")]
+    NotEnoughProtocolReserves {},
+
+    #[error("Not Enough mmToken Balance")]
+    NotEnoughMmTokenBalance {},
+
+    #[error("Transferred mmTokens Are Used To Collateralise The Borrowing")]
+    NotEnoughCollateralToTransfer {},
+
+    #[error("Redeemed Tokens Are Used To Collateralise The Borrowing")]
+    NotEnoughCollateralToRedeem {},
 }
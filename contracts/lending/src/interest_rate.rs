@@ -0,0 +1,228 @@
+use crate::access_control::ensure_role;
+use crate::contract::query::get_interest_rate;
+use crate::contract::{execute_update_liquidity_index_data, HUNDRED_PERCENT, YEAR_IN_SECONDS};
+use crate::error::ContractError;
+use crate::msg::{AdaptiveRateState, RateStrategy, Role};
+use crate::state::{
+    ADAPTIVE_RATE_STATE, RATE_STRATEGIES, SUPPORTED_TOKENS, TOKENS_INTEREST_RATE_MODEL_PARAMS,
+};
+use cosmwasm_std::{ensure, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128};
+
+pub fn get_rate_strategy(deps: Deps, denom: String) -> StdResult<RateStrategy> {
+    Ok(RATE_STRATEGIES
+        .may_load(deps.storage, denom)?
+        .unwrap_or_default())
+}
+
+fn is_valid_rate_strategy(strategy: &RateStrategy) -> bool {
+    match *strategy {
+        RateStrategy::TwoSlope | RateStrategy::Fixed { .. } => true,
+        RateStrategy::ThreeSlope {
+            min_interest_rate,
+            first_kink_utilisation_ratio,
+            first_kink_rate,
+            second_kink_utilisation_ratio,
+            second_kink_rate,
+            max_interest_rate,
+        } => {
+            0 < first_kink_utilisation_ratio
+                && first_kink_utilisation_ratio < second_kink_utilisation_ratio
+                && second_kink_utilisation_ratio < HUNDRED_PERCENT
+                && min_interest_rate <= first_kink_rate
+                && first_kink_rate <= second_kink_rate
+                && second_kink_rate <= max_interest_rate
+        }
+        RateStrategy::Adaptive {
+            target_utilisation_ratio,
+            min_interest_rate,
+            max_interest_rate,
+            ..
+        } => {
+            0 < target_utilisation_ratio
+                && target_utilisation_ratio < HUNDRED_PERCENT
+                && min_interest_rate <= max_interest_rate
+        }
+    }
+}
+
+// rate on the segment of the curve between two (utilisation ratio, rate) points
+fn interpolate(utilization_rate: u128, from: (u128, u128), to: (u128, u128)) -> u128 {
+    from.1 + (utilization_rate - from.0) * (to.1 - from.1) / (to.0 - from.0)
+}
+
+pub fn calculate_two_slope_rate(
+    utilization_rate: u128,
+    min_interest_rate: u128,
+    safe_borrow_max_rate: u128,
+    rate_growth_factor: u128,
+    optimal_utilisation_ratio: u128,
+) -> u128 {
+    if utilization_rate <= optimal_utilisation_ratio {
+        min_interest_rate
+            + utilization_rate * (safe_borrow_max_rate - min_interest_rate)
+                / optimal_utilisation_ratio
+    } else {
+        safe_borrow_max_rate
+            + rate_growth_factor * (utilization_rate - optimal_utilisation_ratio)
+                / (HUNDRED_PERCENT - optimal_utilisation_ratio)
+    }
+}
+
+pub fn calculate_interest_rate(
+    deps: Deps,
+    env: Env,
+    denom: String,
+    utilization_rate: u128,
+) -> StdResult<u128> {
+    let strategy = get_rate_strategy(deps, denom.clone())?;
+
+    match strategy {
+        RateStrategy::TwoSlope => {
+            let params = TOKENS_INTEREST_RATE_MODEL_PARAMS.load(deps.storage, denom)?;
+
+            Ok(calculate_two_slope_rate(
+                utilization_rate,
+                params.min_interest_rate,
+                params.safe_borrow_max_rate,
+                params.rate_growth_factor,
+                params.optimal_utilisation_ratio,
+            ))
+        }
+        RateStrategy::ThreeSlope {
+            min_interest_rate,
+            first_kink_utilisation_ratio,
+            first_kink_rate,
+            second_kink_utilisation_ratio,
+            second_kink_rate,
+            max_interest_rate,
+        } => {
+            let first_kink = (first_kink_utilisation_ratio, first_kink_rate);
+            let second_kink = (second_kink_utilisation_ratio, second_kink_rate);
+
+            if utilization_rate <= first_kink_utilisation_ratio {
+                Ok(interpolate(
+                    utilization_rate,
+                    (0, min_interest_rate),
+                    first_kink,
+                ))
+            } else if utilization_rate <= second_kink_utilisation_ratio {
+                Ok(interpolate(utilization_rate, first_kink, second_kink))
+            } else {
+                Ok(interpolate(
+                    utilization_rate.min(HUNDRED_PERCENT),
+                    second_kink,
+                    (HUNDRED_PERCENT, max_interest_rate),
+                ))
+            }
+        }
+        RateStrategy::Fixed { rate } => Ok(rate),
+        RateStrategy::Adaptive {
+            target_utilisation_ratio,
+            adjustment_speed,
+            min_interest_rate,
+            max_interest_rate,
+        } => {
+            let adaptive_rate_state = ADAPTIVE_RATE_STATE.load(deps.storage, denom)?;
+
+            let elapsed = env
+                .block
+                .time
+                .seconds()
+                .saturating_sub(adaptive_rate_state.timestamp.seconds())
+                as u128;
+
+            // the deviation from the target is normalized, the full speed is reached at 0% and 100%
+            let (deviation, range) = if utilization_rate >= target_utilisation_ratio {
+                (
+                    utilization_rate - target_utilisation_ratio,
+                    HUNDRED_PERCENT - target_utilisation_ratio,
+                )
+            } else {
+                (
+                    target_utilisation_ratio - utilization_rate,
+                    target_utilisation_ratio,
+                )
+            };
+
+            let rate_change = Uint128::from(adjustment_speed)
+                .multiply_ratio(deviation * elapsed, range * YEAR_IN_SECONDS)
+                .u128();
+
+            if utilization_rate >= target_utilisation_ratio {
+                Ok((adaptive_rate_state.rate + rate_change).min(max_interest_rate))
+            } else {
+                Ok(adaptive_rate_state
+                    .rate
+                    .saturating_sub(rate_change)
+                    .max(min_interest_rate))
+            }
+        }
+    }
+}
+
+/// Stores the adaptive rate reached so far, called before every change of the market balances
+pub fn update_adaptive_rate(deps: &mut DepsMut, env: Env, denom: String) -> StdResult<()> {
+    if let RateStrategy::Adaptive { .. } = get_rate_strategy(deps.as_ref(), denom.clone())? {
+        let rate = get_interest_rate(deps.as_ref(), env.clone(), denom.clone())?.u128();
+
+        ADAPTIVE_RATE_STATE.save(
+            deps.storage,
+            denom,
+            &AdaptiveRateState {
+                rate,
+                timestamp: env.block.time,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn execute_set_rate_strategy(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    strategy: RateStrategy,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    ensure!(
+        is_valid_rate_strategy(&strategy),
+        ContractError::InvalidRateStrategy {}
+    );
+
+    // interest up to now accrues under the previous strategy
+    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+
+    if let RateStrategy::Adaptive {
+        min_interest_rate,
+        max_interest_rate,
+        ..
+    } = strategy
+    {
+        let current_rate = get_interest_rate(deps.as_ref(), env.clone(), denom.clone())?.u128();
+
+        ADAPTIVE_RATE_STATE.save(
+            deps.storage,
+            denom.clone(),
+            &AdaptiveRateState {
+                rate: current_rate.clamp(min_interest_rate, max_interest_rate),
+                timestamp: env.block.time,
+            },
+        )?;
+    } else {
+        ADAPTIVE_RATE_STATE.remove(deps.storage, denom.clone());
+    }
+
+    RATE_STRATEGIES.save(deps.storage, denom.clone(), &strategy)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set-rate-strategy")
+        .add_attribute("denom", denom))
+}
@@ -0,0 +1,155 @@
+use crate::error::ContractError;
+use crate::msg::{TotalBorrowData, UserBorrowingInfo};
+use crate::state::{STORAGE_VERSION, TOTAL_BORROW_DATA, USER_BORROWING_INFO};
+use cosmwasm_std::{ensure, Order, StdError, StdResult, Storage, Uint128};
+
+// version of the storage layout written by this code
+pub const CURRENT_STORAGE_VERSION: u64 = 3;
+
+// layouts of storage version 1, read from the same namespaces as the current ones
+mod v1 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Timestamp, Uint128};
+    use cw_storage_plus::Map;
+
+    #[cw_serde]
+    pub struct UserBorrowingInfo {
+        pub borrowed_amount: Uint128,
+        pub average_interest_rate: Uint128,
+        pub timestamp: Timestamp,
+    }
+
+    #[cw_serde]
+    pub struct TotalBorrowData {
+        pub denom: String,
+        pub total_borrowed_amount: u128,
+        pub expected_annual_interest_income: u128,
+        pub average_interest_rate: u128,
+        pub timestamp: Timestamp,
+    }
+
+    pub const USER_BORROWING_INFO: Map<(String, String), UserBorrowingInfo> =
+        Map::new("user_borrowing_info");
+
+    pub const TOTAL_BORROW_DATA: Map<String, TotalBorrowData> = Map::new("total_borrow_data");
+}
+
+// layouts of storage version 2
+mod v2 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Timestamp, Uint128};
+    use cw_storage_plus::Map;
+
+    #[cw_serde]
+    pub struct TotalBorrowData {
+        pub denom: String,
+        pub total_borrowed_amount: Uint128,
+        pub expected_annual_interest_income: Uint128,
+        pub average_interest_rate: Uint128,
+        pub timestamp: Timestamp,
+    }
+
+    pub const TOTAL_BORROW_DATA: Map<String, TotalBorrowData> = Map::new("total_borrow_data");
+}
+
+/// Runs all storage migrations from the stored version up to the current one,
+/// returns the storage version the contract was migrated from
+pub fn migrate_storage(storage: &mut dyn Storage) -> Result<u64, ContractError> {
+    let stored_version = STORAGE_VERSION.may_load(storage)?.unwrap_or(1);
+
+    // storage written by a newer code can't be read by this one
+    ensure!(
+        stored_version <= CURRENT_STORAGE_VERSION,
+        ContractError::StorageDowngradeNotAllowed {
+            from_version: stored_version
+        }
+    );
+
+    for version in stored_version..CURRENT_STORAGE_VERSION {
+        match version {
+            1 => migrate_v1_to_v2(storage)?,
+            2 => migrate_v2_to_v3(storage)?,
+            _ => {
+                return Err(StdError::generic_err(format!(
+                    "No storage migration from version {}",
+                    version
+                ))
+                .into())
+            }
+        }
+    }
+
+    STORAGE_VERSION.save(storage, &CURRENT_STORAGE_VERSION)?;
+
+    Ok(stored_version)
+}
+
+// UserBorrowingInfo gets the denom of the borrowed token,
+// TotalBorrowData amounts are stored as Uint128 instead of raw u128
+fn migrate_v1_to_v2(storage: &mut dyn Storage) -> StdResult<()> {
+    let user_borrowing_info = v1::USER_BORROWING_INFO
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for ((user, denom), info) in user_borrowing_info {
+        USER_BORROWING_INFO.save(
+            storage,
+            (user, denom.clone()),
+            &UserBorrowingInfo {
+                denom,
+                borrowed_amount: info.borrowed_amount,
+                average_interest_rate: info.average_interest_rate,
+                timestamp: info.timestamp,
+            },
+        )?;
+    }
+
+    let total_borrow_data = v1::TOTAL_BORROW_DATA
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (denom, data) in total_borrow_data {
+        v2::TOTAL_BORROW_DATA.save(
+            storage,
+            denom,
+            &v2::TotalBorrowData {
+                denom: data.denom,
+                total_borrowed_amount: Uint128::from(data.total_borrowed_amount),
+                expected_annual_interest_income: Uint128::from(
+                    data.expected_annual_interest_income,
+                ),
+                average_interest_rate: Uint128::from(data.average_interest_rate),
+                timestamp: data.timestamp,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+// TotalBorrowData tracks stable and variable debt separately, all existing debt is variable
+fn migrate_v2_to_v3(storage: &mut dyn Storage) -> StdResult<()> {
+    let total_borrow_data = v2::TOTAL_BORROW_DATA
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (denom, data) in total_borrow_data {
+        TOTAL_BORROW_DATA.save(
+            storage,
+            denom,
+            &TotalBorrowData {
+                denom: data.denom,
+                total_borrowed_amount: data.total_borrowed_amount,
+                expected_annual_interest_income: data.expected_annual_interest_income,
+                average_interest_rate: data.average_interest_rate,
+                variable_borrowed_amount: data.total_borrowed_amount,
+                stable_borrowed_amount: Uint128::zero(),
+                stable_average_interest_rate: Uint128::zero(),
+                term_borrowed_amount: Uint128::zero(),
+                timestamp: data.timestamp,
+            },
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,9 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
-use cosmwasm_std::{Addr, Timestamp};
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp};
 use cw20::Cw20ReceiveMsg;
+use cw_utils::Expiration;
+use std::fmt;
 
-use pyth_sdk_cw::{Price, PriceIdentifier};
+use pyth_sdk_cw::PriceIdentifier;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -24,6 +26,9 @@ pub struct InstantiateMsg {
     pub price_updater_addr: String,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     // Receive hook for Cw20 Send messages
@@ -31,18 +36,48 @@ pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
 
     // for depositing ERC20 Tokens, IBC Token and INJ
-    Deposit {},
+    Deposit {
+        // the deposit is credited to this address instead of the sender
+        on_behalf_of: Option<String>,
+    },
     Redeem {
         denom: String,
         amount: Uint128,
+        // redeemed tokens are sent to this address instead of the sender
+        recipient: Option<String>,
     },
     Borrow {
         denom: String,
         amount: Uint128,
+        // variable if not passed
+        rate_mode: Option<RateMode>,
+        // borrowed tokens are sent to this address instead of the sender
+        recipient: Option<String>,
+        // the debt is recorded on this delegator, limited by its borrow allowance to the sender
+        on_behalf_of: Option<String>,
+    },
+    Repay {
+        // the debt of this address is repaid instead of the sender's one
+        on_behalf_of: Option<String>,
+    },
+    // links the CW20 receipt token of the market to its mmToken balances
+    RegisterMmToken {
+        denom: String,
+        mm_token_address: String,
+    },
+    // hook of the registered receipt token, moves the mmToken balance between users
+    TransferMmToken {
+        sender: String,
+        recipient: String,
+        amount: Uint128,
     },
-    Repay {},
+    // borrow is repaid with the attached coin
     Liquidation {
         user: String,
+        // token of the collateral that the liquidator receives
+        collateral_denom: String,
+        // true - seized collateral is credited as mmToken balance, false - transferred directly
+        receive_mm_token: bool,
     },
     UpdatePrice {
         denom: Option<String>,
@@ -52,6 +87,65 @@ pub enum ExecuteMsg {
         denom: String,
         loan_to_value_ratio: u128,
         liquidation_threshold: u128,
+        supply_cap: Option<Uint128>,
+        borrow_cap: Option<Uint128>,
+    },
+    SetLiquidationConfiguration {
+        denom: String,
+        close_factor: u128,
+        liquidation_bonus: u128,
+    },
+    SetOracleConfiguration {
+        denom: String,
+        max_confidence_ratio: u128,
+        max_price_age: u64,
+    },
+    // secondary source is used when the primary one is stale, both must agree within max_deviation
+    SetPriceSources {
+        denom: String,
+        primary: PriceSource,
+        secondary: Option<PriceSource>,
+        max_deviation: u128,
+    },
+    // isolated collateral can't be combined with other collateral and backs limited debt
+    SetIsolationConfiguration {
+        denom: String,
+        is_isolated: bool,
+        debt_ceiling: Uint128,
+        borrowable_in_isolation: bool,
+    },
+    // category of correlated assets with elevated LTV and liquidation threshold
+    SetEModeCategory {
+        id: u8,
+        label: String,
+        loan_to_value_ratio: u128,
+        liquidation_threshold: u128,
+        price_source: Option<PriceSource>,
+        denoms: Vec<String>,
+    },
+    // opts the sender into the e-mode category, None leaves e-mode
+    SetUserEMode {
+        category_id: Option<u8>,
+    },
+    // lends the assets to the recipient contract, principal plus fee must be returned within the transaction
+    FlashLoan {
+        assets: Vec<Coin>,
+        recipient: String,
+        msg: Binary,
+    },
+    SetFlashLoanFee {
+        denom: String,
+        flash_loan_fee: u128,
+    },
+    SetReserveFactor {
+        denom: String,
+        reserve_factor: u128,
+    },
+    // transfers accrued protocol reserves out of the contract
+    WithdrawReserves {
+        denom: String,
+        amount: Uint128,
+        recipient: String,
     },
     SetTokenInterestRateModelParams {
         denom: String,
@@ -60,6 +154,73 @@ pub enum ExecuteMsg {
         rate_growth_factor: u128,
         optimal_utilisation_ratio: u128,
     },
+    // accrues the market before the switch, an adaptive strategy starts from the current rate
+    SetRateStrategy {
+        denom: String,
+        strategy: RateStrategy,
+    },
+    // kinked curve of the stable rate, stable borrowing is disabled until it is set
+    SetStableRateConfiguration {
+        denom: String,
+        min_interest_rate: u128,
+        safe_borrow_max_rate: u128,
+        rate_growth_factor: u128,
+        optimal_utilisation_ratio: u128,
+        rebalance_utilisation_ratio: u128,
+    },
+    // moves the stable position of the user to the current stable rate, callable by anyone
+    // once the utilisation passes the rebalance threshold
+    RebalanceStableRate {
+        user: String,
+        denom: String,
+    },
+    // repays the term loan with the sent funds, the excess is returned to the sender
+    RepayTermLoan {
+        loan_id: u64,
+    },
+    // term loan is liquidatable once it is overdue, or before that when the borrower is unhealthy
+    LiquidateTermLoan {
+        loan_id: u64,
+        collateral_denom: String,
+        receive_mm_token: bool,
+    },
+    // funded with the whole emission of the schedule in the reward denom
+    AddEmissionSchedule {
+        denom: String,
+        side: RewardSide,
+        reward_denom: String,
+        emission_per_second: Uint128,
+        start: Timestamp,
+        end: Timestamp,
+    },
+    // sends all accrued rewards of the sender
+    ClaimRewards {},
+    // sends the rewards emitted while nobody could earn them
+    SweepUndistributedRewards {
+        recipient: String,
+    },
+    // the sent funds cover bad debt of the market before the protocol reserves and depositors
+    FundInsuranceFund {},
+    // writes off the debt of a user without collateral, callable by anyone
+    WriteOffBadDebt {
+        user: String,
+    },
+    WithdrawInsuranceFund {
+        denom: String,
+        amount: Uint128,
+        recipient: String,
+    },
+    // allows the delegatee to borrow up to the amount against the collateral of the sender
+    ApproveDelegation {
+        delegatee: String,
+        denom: String,
+        amount: Uint128,
+        expiry: Timestamp,
+    },
+    RevokeDelegation {
+        delegatee: String,
+        denom: String,
+    },
     AddMarkets {
         denom: String,
         name: String,
@@ -87,24 +248,80 @@ pub enum ExecuteMsg {
     AddPriceFeedIds {
         price_ids: Vec<(String, PriceIdentifier)>,
     },
-    UpdateAdmin {
-        admin: String,
+    // first step of the admin transfer, the proposal can be accepted until it expires
+    TransferOwnership {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    CancelOwnershipTransfer {},
+    GrantRole {
+        role: Role,
+        address: String,
     },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    // freezing is immediate, wind-down goes through the timelock as it zeroes the collateral value
+    SetMarketStatus {
+        denom: String,
+        status: MarketStatus,
+    },
+    // removes a wound down market without outstanding balances
     RemoveSupportedToken {
         denom: String,
     },
     RemovePriceFeedId {
         denom: String,
     },
+    // pauses everything except repay and liquidation
     SetPause {
         value: bool,
     },
+    // pauses a single action for one market or for all markets if denom is not passed
+    SetActionPause {
+        denom: Option<String>,
+        action: PauseAction,
+        value: bool,
+    },
+    // delay in seconds for risk parameter changes, zero applies them immediately
+    SetTimelockDelay {
+        delay: u64,
+    },
+    // applies a queued action once its eta has passed, callable by anyone
+    ExecuteQueuedAction {
+        id: u64,
+    },
+    CancelQueuedAction {
+        id: u64,
+    },
 }
 
 #[cw_serde]
 pub enum Cw20HookMsg {
-    Deposit { denom: String },
-    Repay { denom: String },
+    Deposit {
+        denom: String,
+        on_behalf_of: Option<String>,
+    },
+    Repay {
+        denom: String,
+        on_behalf_of: Option<String>,
+    },
+    Liquidate {
+        user: String,
+        collateral_denom: String,
+        receive_mm_token: bool,
+    },
+    RepayTermLoan {
+        loan_id: u64,
+    },
+    LiquidateTermLoan {
+        loan_id: u64,
+        collateral_denom: String,
+        receive_mm_token: bool,
+    },
+    FundInsuranceFund {},
 }
 
 #[cw_serde]
@@ -122,6 +339,9 @@ pub enum QueryMsg {
     #[returns(UserBorrowingInfo)]
     GetUserBorrowingInfo { address: String, denom: String },
 
+    #[returns(UserBorrowingInfo)]
+    GetUserStableBorrowingInfo { address: String, denom: String },
+
     #[returns(TotalBorrowData)]
     GetTotalBorrowData { denom: String },
 
@@ -131,9 +351,68 @@ pub enum QueryMsg {
     #[returns(GetReserveConfigurationResponse)]
     GetReserveConfiguration {},
 
+    #[returns(MarketCapsHeadroom)]
+    GetMarketCapsHeadroom { denom: String },
+
+    #[returns(LiquidationConfiguration)]
+    GetLiquidationConfiguration { denom: String },
+
+    #[returns(Option < String >)]
+    GetMmTokenAddress { denom: String },
+
+    #[returns(Uint128)]
+    GetMmTokenBalance { address: String, denom: String },
+
+    #[returns(Uint128)]
+    GetTotalMmTokenSupply { denom: String },
+
+    #[returns(OracleConfiguration)]
+    GetOracleConfiguration { denom: String },
+
+    #[returns(PriceSourcesResponse)]
+    GetPriceSources { denom: String },
+
+    #[returns(ProtocolReserves)]
+    GetProtocolReserves { denom: String },
+
+    #[returns(Uint128)]
+    GetInsuranceFund { denom: String },
+
+    #[returns(Vec<BadDebtWriteOff>)]
+    GetBadDebtWriteOffs {},
+
+    // the amount is zero after the expiry
+    #[returns(BorrowAllowance)]
+    GetBorrowAllowance {
+        delegator: String,
+        delegatee: String,
+        denom: String,
+    },
+
+    #[returns(Vec<EModeCategory>)]
+    GetEModeCategories {},
+
+    #[returns(UserEMode)]
+    GetUserEMode { address: String },
+
+    #[returns(u128)]
+    GetFlashLoanFee { denom: String },
+
+    #[returns(IsolationConfiguration)]
+    GetIsolationConfiguration { denom: String },
+
+    #[returns(Uint128)]
+    GetIsolatedDebt { denom: String },
+
+    #[returns(MarketStatus)]
+    GetMarketStatus { denom: String },
+
     #[returns(GetTokensInterestRateModelParamsResponse)]
     GetTokensInterestRateModelParams {},
 
+    #[returns(RateStrategy)]
+    GetRateStrategy { denom: String },
+
     #[returns(Uint128)]
     GetPrice { denom: String },
 
@@ -143,6 +422,37 @@ pub enum QueryMsg {
     #[returns(Uint128)]
     GetInterestRate { denom: String },
 
+    // rate a new stable borrow would be locked at
+    #[returns(Uint128)]
+    GetStableInterestRate { denom: String },
+
+    #[returns(Option<StableRateConfiguration>)]
+    GetStableRateConfiguration { denom: String },
+
+    #[returns(TermLoan)]
+    GetTermLoan { loan_id: u64 },
+
+    #[returns(Uint128)]
+    GetTermLoanAmountWithInterest { loan_id: u64 },
+
+    #[returns(Vec<TermLoan>)]
+    GetUserTermLoans { address: String },
+
+    // loans past their maturity, liquidatable regardless of the borrower health
+    #[returns(Vec<TermLoan>)]
+    GetOverdueTermLoans {},
+
+    #[returns(Vec<EmissionSchedule>)]
+    GetEmissionSchedules {},
+
+    // accrued and not yet checkpointed rewards by reward denom
+    #[returns(Vec<Coin>)]
+    GetPendingRewards { address: String },
+
+    // rewards that can be swept by the treasury
+    #[returns(Vec<Coin>)]
+    GetUndistributedRewards {},
+
     #[returns(Uint128)]
     GetLiquidityRate { denom: String },
 
@@ -194,6 +504,9 @@ pub enum QueryMsg {
     #[returns(Uint128)]
     GetUserMaxAllowedBorrowAmountUsd { address: String },
 
+    #[returns(AccountSnapshot)]
+    GetAccountSnapshot { address: String },
+
     #[returns(Vec < String >)]
     GetAllUsersWithBorrows {},
 
@@ -203,11 +516,23 @@ pub enum QueryMsg {
     #[returns(String)]
     GetAdmin {},
 
+    #[returns(RolesResponse)]
+    GetRoles {},
+
     #[returns(Vec < (String, Uint128) >)]
     GetUserBalances { address: String },
 
     #[returns(bool)]
     IsPaused {},
+
+    #[returns(PauseState)]
+    GetPauseState {},
+
+    #[returns(u64)]
+    GetTimelockDelay {},
+
+    #[returns(Vec < QueuedAction >)]
+    GetPendingActions {},
 }
 
 #[cw_serde]
@@ -232,20 +557,90 @@ pub struct GetTokensInterestRateModelParamsResponse {
 
 #[cw_serde]
 pub struct UserBorrowingInfo {
+    pub denom: String,
     pub borrowed_amount: Uint128,
     pub average_interest_rate: Uint128,
     pub timestamp: Timestamp,
 }
 
+#[cw_serde]
+#[derive(Default)]
+pub enum RateMode {
+    // rate of the position follows new borrows of the user
+    #[default]
+    Variable,
+    // rate is locked at borrow time from the stable curve
+    Stable,
+    // separate loan at the current rate locked until the maturity, overdue loans are liquidatable
+    Term {
+        maturity: Timestamp,
+    },
+}
+
+#[cw_serde]
+pub struct TermLoan {
+    pub id: u64,
+    pub borrower: String,
+    pub denom: String,
+    // debt as of the timestamp, interest accrues at the locked rate
+    pub borrowed_amount: Uint128,
+    pub interest_rate: Uint128,
+    pub timestamp: Timestamp,
+    pub maturity: Timestamp,
+}
+
+#[cw_serde]
+pub struct StableRateConfiguration {
+    pub denom: String,
+    pub min_interest_rate: u128,
+    pub safe_borrow_max_rate: u128,
+    pub rate_growth_factor: u128,
+    pub optimal_utilisation_ratio: u128,
+    pub rebalance_utilisation_ratio: u128,
+}
+
 #[cw_serde]
 pub struct UserDataByToken {
     pub deposited: Uint128,
     pub borrowed: Uint128,
 }
 
+#[cw_serde]
+pub struct AccountMarketSnapshot {
+    pub denom: String,
+    pub deposited: Uint128,
+    pub deposited_usd: Uint128,
+    pub use_as_collateral: bool,
+    pub borrowed: Uint128,
+    pub borrowed_usd: Uint128,
+    pub price: Uint128,
+    pub loan_to_value_ratio: u128,
+    pub liquidation_threshold: u128,
+}
+
+#[cw_serde]
+pub struct AccountSnapshot {
+    pub markets: Vec<AccountMarketSnapshot>,
+    pub deposited_usd: Uint128,
+    pub collateral_usd: Uint128,
+    pub borrowed_usd: Uint128,
+    // max borrowed amount in USD allowed by LTV ratios of the collateral
+    pub borrow_capacity_usd: Uint128,
+    // borrowed amount in USD upon reaching which the account is liquidated
+    pub liquidation_threshold_usd: Uint128,
+    // weighted liquidation threshold of the collateral
+    pub liquidation_threshold: Uint128,
+    pub utilization_rate: Uint128,
+    // liquidation_threshold_usd / borrowed_usd with PERCENT_DECIMALS, None without borrows
+    pub health_factor: Option<Uint128>,
+    // additional borrowed amount in USD that makes the account liquidatable
+    pub distance_to_liquidation_usd: Uint128,
+}
+
 impl Default for UserBorrowingInfo {
     fn default() -> Self {
         UserBorrowingInfo {
+            denom: String::new(),
             borrowed_amount: Uint128::zero(),
             average_interest_rate: Uint128::zero(),
             timestamp: Default::default(),
@@ -278,6 +673,317 @@ pub struct ReserveConfiguration {
     pub loan_to_value_ratio: u128,
     // LTV ratio
     pub liquidation_threshold: u128,
+    // max total deposits of the market, uncapped if None
+    pub supply_cap: Option<Uint128>,
+    // max total borrows of the market, uncapped if None
+    pub borrow_cap: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct MarketCapsHeadroom {
+    pub denom: String,
+    pub supply_cap: Option<Uint128>,
+    pub borrow_cap: Option<Uint128>,
+    pub total_deposited: Uint128,
+    pub total_borrowed: Uint128,
+    // amount that still can be deposited, None if the market is uncapped
+    pub supply_headroom: Option<Uint128>,
+    // amount that still can be borrowed, None if the market is uncapped
+    pub borrow_headroom: Option<Uint128>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum MarketStatus {
+    #[default]
+    Active,
+    // no new deposits and borrows
+    Frozen,
+    // collateral value is zero, only repay, redeem and liquidation are allowed
+    WindDown,
+}
+
+#[cw_serde]
+pub struct LiquidationConfiguration {
+    pub denom: String,
+    // max share of a single borrow that can be repaid in one liquidation
+    pub close_factor: u128,
+    // extra collateral on top of the repaid value that goes to the liquidator
+    pub liquidation_bonus: u128,
+}
+
+#[cw_serde]
+pub struct OracleConfiguration {
+    pub denom: String,
+    // max Pyth confidence interval relative to the price, wider prices are rejected
+    pub max_confidence_ratio: u128,
+    // max age of the price in seconds, older prices are stale
+    pub max_price_age: u64,
+}
+
+#[cw_serde]
+pub enum PriceSource {
+    // Pyth feed from PRICE_FEED_IDS, falls back to the last pushed price
+    Pyth {},
+    // price pushed with UpdatePrice
+    Manual {},
+    // Band Protocol standard reference contract
+    Band {
+        contract_addr: String,
+        base_symbol: String,
+    },
+    // time-weighted average price over the window in seconds
+    Twap {
+        contract_addr: String,
+        market_id: String,
+        window: u64,
+    },
+    // pegged price with USD_DECIMALS, e.g. for stablecoins
+    Fixed {
+        price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct PriceSources {
+    pub denom: String,
+    pub primary: PriceSource,
+    pub secondary: Option<PriceSource>,
+    // max difference between primary and secondary prices relative to the primary one
+    pub max_deviation: u128,
+}
+
+#[cw_serde]
+pub struct SourcePrice {
+    pub price: Uint128,
+    pub timestamp: Timestamp,
+    pub is_fresh: bool,
+    // set if the source query failed, the price is reported as stale then
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub struct PriceSourcesResponse {
+    pub denom: String,
+    pub primary: PriceSource,
+    pub secondary: Option<PriceSource>,
+    pub max_deviation: u128,
+    // None if the source query failed
+    pub primary_price: Option<SourcePrice>,
+    // None if the source isn't set
+    pub secondary_price: Option<SourcePrice>,
+    // None unless both prices are fresh
+    pub deviation: Option<u128>,
+    // None if no source has a fresh price
+    pub served_by: Option<PriceSource>,
+    pub price: Uint128,
+    pub timestamp: Timestamp,
+}
+
+// callback executed on the flash loan recipient after the assets are sent
+#[cw_serde]
+pub enum FlashLoanReceiverMsg {
+    ExecuteOperation {
+        initiator: String,
+        assets: Vec<Coin>,
+        fees: Vec<Coin>,
+        msg: Binary,
+    },
+}
+
+#[cw_serde]
+pub struct FlashLoanData {
+    pub denom: String,
+    pub amount: Uint128,
+    pub fee: Uint128,
+    // contract balance before the loan, must be restored with the fee on top
+    pub balance_before: Uint128,
+}
+
+#[cw_serde]
+pub struct EModeCategory {
+    pub id: u8,
+    pub label: String,
+    pub loan_to_value_ratio: u128,
+    pub liquidation_threshold: u128,
+    // shared oracle for all assets of the category, markets use their own sources if None
+    pub price_source: Option<PriceSource>,
+    pub denoms: Vec<String>,
+}
+
+#[cw_serde]
+pub struct UserEMode {
+    pub address: String,
+    pub category_id: Option<u8>,
+    // category parameters are used only while all borrows of the user are inside the category
+    pub is_active: bool,
+}
+
+#[cw_serde]
+pub struct IsolationConfiguration {
+    pub denom: String,
+    // collateral that can only be used alone and only for assets borrowable in isolation
+    pub is_isolated: bool,
+    // max total debt in USD backed by this collateral
+    pub debt_ceiling: Uint128,
+    // asset can be borrowed against isolated collateral
+    pub borrowable_in_isolation: bool,
+}
+
+#[cw_serde]
+pub enum Role {
+    RiskManager,
+    OracleManager,
+    PauseGuardian,
+    Treasury,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [
+        Role::RiskManager,
+        Role::OracleManager,
+        Role::PauseGuardian,
+        Role::Treasury,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Role::RiskManager => "risk_manager",
+            Role::OracleManager => "oracle_manager",
+            Role::PauseGuardian => "pause_guardian",
+            Role::Treasury => "treasury",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::RiskManager => write!(f, "Risk Manager"),
+            Role::OracleManager => write!(f, "Oracle Manager"),
+            Role::PauseGuardian => write!(f, "Pause Guardian"),
+            Role::Treasury => write!(f, "Treasury"),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum PauseAction {
+    Deposit,
+    Redeem,
+    Borrow,
+    Repay,
+    Liquidate,
+    FlashLoan,
+}
+
+impl PauseAction {
+    pub fn key(&self) -> &'static str {
+        match self {
+            PauseAction::Deposit => "deposit",
+            PauseAction::Redeem => "redeem",
+            PauseAction::Borrow => "borrow",
+            PauseAction::Repay => "repay",
+            PauseAction::Liquidate => "liquidate",
+            PauseAction::FlashLoan => "flash_loan",
+        }
+    }
+}
+
+impl fmt::Display for PauseAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PauseAction::Deposit => write!(f, "Deposit"),
+            PauseAction::Redeem => write!(f, "Redeem"),
+            PauseAction::Borrow => write!(f, "Borrow"),
+            PauseAction::Repay => write!(f, "Repay"),
+            PauseAction::Liquidate => write!(f, "Liquidation"),
+            PauseAction::FlashLoan => write!(f, "Flash Loan"),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct PausedActions {
+    pub deposit: bool,
+    pub redeem: bool,
+    pub borrow: bool,
+    pub repay: bool,
+    pub liquidate: bool,
+    pub flash_loan: bool,
+}
+
+#[cw_serde]
+pub struct MarketPauseState {
+    pub denom: String,
+    // flags set for this market only
+    pub paused_actions: PausedActions,
+    // flags after the global pause and the flags for all markets are applied
+    pub effective: PausedActions,
+}
+
+#[cw_serde]
+pub struct PauseState {
+    pub is_paused: bool,
+    // flags set for all markets
+    pub paused_actions: PausedActions,
+    pub markets: Vec<MarketPauseState>,
+}
+
+#[cw_serde]
+pub struct QueuedAction {
+    pub id: u64,
+    pub msg: ExecuteMsg,
+    pub proposer: Addr,
+    pub eta: Timestamp,
+}
+
+#[cw_serde]
+pub struct PendingOwnership {
+    pub new_owner: Addr,
+    pub expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct RoleMembers {
+    pub role: Role,
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub admin: Addr,
+    pub pending_ownership: Option<PendingOwnership>,
+    pub roles: Vec<RoleMembers>,
+}
+
+#[cw_serde]
+pub struct ProtocolReserves {
+    pub denom: String,
+    // share of accrued borrow interest that goes to the protocol instead of depositors
+    pub reserve_factor: u128,
+    pub accrued_reserves: Uint128,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct BorrowAllowance {
+    pub amount: Uint128,
+    pub expiry: Timestamp,
+}
+
+#[cw_serde]
+pub struct BadDebtWriteOff {
+    pub id: u64,
+    pub user: String,
+    pub denom: String,
+    pub amount: Uint128,
+    // parts of the amount, covered in this order
+    pub covered_by_insurance_fund: Uint128,
+    pub covered_by_reserves: Uint128,
+    pub socialized_amount: Uint128,
+    pub timestamp: Timestamp,
 }
 
 #[cw_serde]
@@ -289,6 +995,77 @@ pub struct TokenInterestRateModelParams {
     pub optimal_utilisation_ratio: u128,
 }
 
+// rates use INTEREST_RATE_DECIMALS, utilisation ratios use PERCENT_DECIMALS
+#[cw_serde]
+#[derive(Default)]
+pub enum RateStrategy {
+    // kinked curve built from TokenInterestRateModelParams of the market
+    #[default]
+    TwoSlope,
+    ThreeSlope {
+        min_interest_rate: u128,
+        first_kink_utilisation_ratio: u128,
+        first_kink_rate: u128,
+        second_kink_utilisation_ratio: u128,
+        second_kink_rate: u128,
+        max_interest_rate: u128,
+    },
+    // for pegged assets, the rate does not depend on utilisation
+    Fixed {
+        rate: u128,
+    },
+    // the rate moves up while utilisation is above the target and down while it is below,
+    // by at most adjustment_speed per year
+    Adaptive {
+        target_utilisation_ratio: u128,
+        adjustment_speed: u128,
+        min_interest_rate: u128,
+        max_interest_rate: u128,
+    },
+}
+
+#[cw_serde]
+pub enum RewardSide {
+    // rewards are shared by mmToken balances
+    Supply,
+    // rewards are shared by variable debt
+    Borrow,
+}
+
+impl RewardSide {
+    pub fn key(&self) -> &'static str {
+        match self {
+            RewardSide::Supply => "supply",
+            RewardSide::Borrow => "borrow",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct EmissionSchedule {
+    pub id: u64,
+    pub denom: String,
+    pub side: RewardSide,
+    pub reward_denom: String,
+    pub emission_per_second: Uint128,
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct RewardIndex {
+    // rewards per unit of the side balance, in 18 decimals
+    pub index: Uint128,
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct AdaptiveRateState {
+    pub rate: u128,
+    pub timestamp: Timestamp,
+}
+
 #[cw_serde]
 pub struct LiquidityIndexData {
     pub denom: String,
@@ -300,8 +1077,13 @@ pub struct LiquidityIndexData {
 #[derive(Default)]
 pub struct TotalBorrowData {
     pub denom: String,
-    pub total_borrowed_amount: u128,
-    pub expected_annual_interest_income: u128,
-    pub average_interest_rate: u128,
+    pub total_borrowed_amount: Uint128,
+    pub expected_annual_interest_income: Uint128,
+    pub average_interest_rate: Uint128,
+    // parts of total_borrowed_amount, stable and term debt are also included in the totals above
+    pub variable_borrowed_amount: Uint128,
+    pub stable_borrowed_amount: Uint128,
+    pub stable_average_interest_rate: Uint128,
+    pub term_borrowed_amount: Uint128,
     pub timestamp: Timestamp,
 }
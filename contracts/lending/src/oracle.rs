@@ -0,0 +1,253 @@
+use crate::contract::query::get_oracle_configuration;
+use crate::contract::{normalize_pyth_price, HUNDRED_PERCENT, USD_DECIMALS};
+use crate::msg::{PriceSource, PriceSources, PriceSourcesResponse, SourcePrice};
+use crate::state::{
+    IS_TESTING, PRICES, PRICE_FEED_IDS, PRICE_SOURCES, PRICE_TIMESTAMPS, PYTH_CONTRACT,
+};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Deps, Env, StdError, StdResult, Timestamp, Uint128, Uint256};
+use pyth_sdk_cw::{query_price_feed, PriceFeedResponse};
+
+// Band Protocol standard reference contract interface
+const BAND_RATE_DECIMALS: u32 = 18;
+const BAND_QUOTE_SYMBOL: &str = "USD";
+
+#[cw_serde]
+pub enum BandQueryMsg {
+    GetReferenceData {
+        base_symbol: String,
+        quote_symbol: String,
+    },
+}
+
+#[cw_serde]
+pub struct BandReferenceData {
+    pub rate: Uint256,
+    pub last_updated_base: u64,
+    pub last_updated_quote: u64,
+}
+
+// stand-in for the Injective exchange module TWAP until it's queryable from contracts
+#[cw_serde]
+pub enum TwapQueryMsg {
+    Twap { market_id: String, window: u64 },
+}
+
+#[cw_serde]
+pub struct TwapResponse {
+    pub price: Uint128,
+    pub decimals: u32,
+    pub timestamp: u64,
+}
+
+fn scale_to_usd_decimals(price: u128, decimals: u32) -> StdResult<u128> {
+    if decimals >= USD_DECIMALS {
+        Ok(price / 10u128.pow(decimals - USD_DECIMALS))
+    } else {
+        price
+            .checked_mul(10u128.pow(USD_DECIMALS - decimals))
+            .ok_or_else(|| StdError::generic_err("Price overflow"))
+    }
+}
+
+/// Queries the price with USD_DECIMALS and its publish time from a single source
+pub fn query_source_price(
+    deps: Deps,
+    env: Env,
+    denom: String,
+    source: &PriceSource,
+) -> StdResult<(u128, Timestamp)> {
+    match source {
+        PriceSource::Manual {} => {
+            let price = PRICES.load(deps.storage, denom.clone()).unwrap_or(0u128);
+
+            // prices set manually in testing mode never expire
+            let timestamp = if IS_TESTING.load(deps.storage)? {
+                env.block.time
+            } else {
+                PRICE_TIMESTAMPS
+                    .load(deps.storage, denom)
+                    .unwrap_or_default()
+            };
+
+            Ok((price, timestamp))
+        }
+        PriceSource::Pyth {} => {
+            let pyth_contract = PYTH_CONTRACT.load(deps.storage)?;
+
+            let price_identifier = PRICE_FEED_IDS.load(deps.storage, denom.clone())?;
+
+            let price_feed_response: PriceFeedResponse =
+                query_price_feed(&deps.querier, pyth_contract, price_identifier)?;
+            let price_feed = price_feed_response.price_feed;
+
+            let oracle_configuration = get_oracle_configuration(deps, denom.clone())?;
+
+            // if Pyth price is available getting most recent price if not - just load from a contract
+            let pyth_current_price = price_feed.get_price_no_older_than(
+                env.block.time.seconds() as i64,
+                oracle_configuration.max_price_age,
+            );
+
+            if let Some(pyth_current_price) = pyth_current_price {
+                let price = normalize_pyth_price(
+                    pyth_current_price,
+                    oracle_configuration.max_confidence_ratio,
+                )
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+                Ok((
+                    price,
+                    Timestamp::from_seconds(pyth_current_price.publish_time as u64),
+                ))
+            } else {
+                Ok((
+                    PRICES.load(deps.storage, denom.clone()).unwrap_or(0u128),
+                    PRICE_TIMESTAMPS
+                        .load(deps.storage, denom)
+                        .unwrap_or_default(),
+                ))
+            }
+        }
+        PriceSource::Band {
+            contract_addr,
+            base_symbol,
+        } => {
+            let reference_data: BandReferenceData = deps.querier.query_wasm_smart(
+                contract_addr,
+                &BandQueryMsg::GetReferenceData {
+                    base_symbol: base_symbol.clone(),
+                    quote_symbol: BAND_QUOTE_SYMBOL.to_string(),
+                },
+            )?;
+
+            let rate: Uint128 = reference_data.rate.try_into()?;
+
+            Ok((
+                scale_to_usd_decimals(rate.u128(), BAND_RATE_DECIMALS)?,
+                Timestamp::from_seconds(
+                    reference_data
+                        .last_updated_base
+                        .min(reference_data.last_updated_quote),
+                ),
+            ))
+        }
+        PriceSource::Twap {
+            contract_addr,
+            market_id,
+            window,
+        } => {
+            let twap: TwapResponse = deps.querier.query_wasm_smart(
+                contract_addr,
+                &TwapQueryMsg::Twap {
+                    market_id: market_id.clone(),
+                    window: *window,
+                },
+            )?;
+
+            Ok((
+                scale_to_usd_decimals(twap.price.u128(), twap.decimals)?,
+                Timestamp::from_seconds(twap.timestamp),
+            ))
+        }
+        // pegged price is always fresh
+        PriceSource::Fixed { price } => Ok((price.u128(), env.block.time)),
+    }
+}
+
+/// Queries primary and secondary sources of the market and picks the price
+pub fn get_price_sources(deps: Deps, env: Env, denom: String) -> StdResult<PriceSourcesResponse> {
+    let price_sources = match PRICE_SOURCES.may_load(deps.storage, denom.clone())? {
+        Some(price_sources) => price_sources,
+        // markets without explicit sources keep using the initial oracle
+        None => PriceSources {
+            denom: denom.clone(),
+            primary: if IS_TESTING.load(deps.storage)? {
+                PriceSource::Manual {}
+            } else {
+                PriceSource::Pyth {}
+            },
+            secondary: None,
+            max_deviation: HUNDRED_PERCENT,
+        },
+    };
+
+    let max_price_age = get_oracle_configuration(deps, denom.clone())?.max_price_age;
+
+    let to_source_price = |(price, timestamp): (u128, Timestamp)| SourcePrice {
+        price: Uint128::from(price),
+        timestamp,
+        is_fresh: price > 0
+            && env.block.time.seconds().saturating_sub(timestamp.seconds()) <= max_price_age,
+        error: None,
+    };
+
+    // a broken source is reported as stale instead of being dropped from the response
+    let to_failed_source_price = |err: StdError| SourcePrice {
+        price: Uint128::zero(),
+        timestamp: Timestamp::default(),
+        is_fresh: false,
+        error: Some(err.to_string()),
+    };
+
+    let primary_price =
+        query_source_price(deps, env.clone(), denom.clone(), &price_sources.primary)
+            .map(to_source_price);
+
+    let secondary_price = price_sources.secondary.as_ref().map(|secondary| {
+        query_source_price(deps, env.clone(), denom.clone(), secondary)
+            .map_or_else(to_failed_source_price, to_source_price)
+    });
+
+    // without a fresh secondary price errors of the primary source are returned as is
+    let primary_price = match primary_price {
+        Ok(primary_price) => Some(primary_price),
+        Err(err) => {
+            if !secondary_price.as_ref().is_some_and(|price| price.is_fresh) {
+                return Err(err);
+            }
+            None
+        }
+    };
+
+    let deviation = match (&primary_price, &secondary_price) {
+        (Some(primary_price), Some(secondary_price))
+            if primary_price.is_fresh && secondary_price.is_fresh =>
+        {
+            Some(
+                primary_price
+                    .price
+                    .u128()
+                    .abs_diff(secondary_price.price.u128())
+                    * HUNDRED_PERCENT
+                    / primary_price.price.u128(),
+            )
+        }
+        _ => None,
+    };
+
+    let (served_by, source_price) = match (&primary_price, &secondary_price) {
+        (Some(primary_price), _) if primary_price.is_fresh => {
+            (Some(price_sources.primary.clone()), Some(primary_price))
+        }
+        (_, Some(secondary_price)) if secondary_price.is_fresh => {
+            (price_sources.secondary.clone(), Some(secondary_price))
+        }
+        // no fresh price, the stale one is returned and rejected where freshness is required
+        (Some(primary_price), _) => (None, Some(primary_price)),
+        _ => (None, secondary_price.as_ref()),
+    };
+
+    Ok(PriceSourcesResponse {
+        denom,
+        price: source_price.map_or(Uint128::zero(), |source_price| source_price.price),
+        timestamp: source_price.map_or(Timestamp::default(), |source_price| source_price.timestamp),
+        primary: price_sources.primary,
+        secondary: price_sources.secondary,
+        max_deviation: price_sources.max_deviation,
+        primary_price,
+        secondary_price,
+        deviation,
+        served_by,
+    })
+}
@@ -0,0 +1,129 @@
+use crate::access_control::ensure_role;
+use crate::error::ContractError;
+use crate::msg::{MarketPauseState, PauseAction, PauseState, PausedActions, Role};
+use crate::state::{IS_PAUSED, MARKET_PAUSED_ACTIONS, PAUSED_ACTIONS, SUPPORTED_TOKENS};
+use cosmwasm_std::{ensure, Deps, DepsMut, MessageInfo, Order, Response, StdResult};
+
+// positions can always be made safer unless these actions are paused explicitly
+fn is_paused_globally(deps: Deps, action: &PauseAction) -> StdResult<bool> {
+    Ok(IS_PAUSED.load(deps.storage)?
+        && !matches!(action, PauseAction::Repay | PauseAction::Liquidate))
+}
+
+fn is_flag_set(deps: Deps, denom: &str, action: &PauseAction) -> StdResult<bool> {
+    Ok(PAUSED_ACTIONS
+        .may_load(deps.storage, action.key().to_string())?
+        .unwrap_or_default()
+        || MARKET_PAUSED_ACTIONS
+            .may_load(deps.storage, (denom.to_string(), action.key().to_string()))?
+            .unwrap_or_default())
+}
+
+pub fn is_action_paused(deps: Deps, denom: &str, action: PauseAction) -> StdResult<bool> {
+    Ok(is_paused_globally(deps, &action)? || is_flag_set(deps, denom, &action)?)
+}
+
+pub fn ensure_action_not_paused(
+    deps: Deps,
+    denom: &str,
+    action: PauseAction,
+) -> Result<(), ContractError> {
+    ensure!(
+        !is_paused_globally(deps, &action)?,
+        ContractError::ProtocolIsPaused {}
+    );
+
+    ensure!(
+        !is_flag_set(deps, denom, &action)?,
+        ContractError::ActionIsPaused {
+            action,
+            denom: denom.to_string()
+        }
+    );
+
+    Ok(())
+}
+
+pub fn execute_set_action_pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: Option<String>,
+    action: PauseAction,
+    value: bool,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.as_ref(), &info.sender, Role::PauseGuardian)?;
+
+    match denom.clone() {
+        Some(denom) => {
+            ensure!(
+                SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+                ContractError::TokenNotSupported {}
+            );
+
+            if value {
+                MARKET_PAUSED_ACTIONS.save(
+                    deps.storage,
+                    (denom, action.key().to_string()),
+                    &true,
+                )?;
+            } else {
+                MARKET_PAUSED_ACTIONS.remove(deps.storage, (denom, action.key().to_string()));
+            }
+        }
+        None => {
+            if value {
+                PAUSED_ACTIONS.save(deps.storage, action.key().to_string(), &true)?;
+            } else {
+                PAUSED_ACTIONS.remove(deps.storage, action.key().to_string());
+            }
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set-action-pause")
+        .add_attribute("denom", denom.unwrap_or_else(|| "all".to_string()))
+        .add_attribute("action", action.key())
+        .add_attribute("is_paused", format!("{}", value)))
+}
+
+fn get_paused_actions(
+    is_paused: impl Fn(PauseAction) -> StdResult<bool>,
+) -> StdResult<PausedActions> {
+    Ok(PausedActions {
+        deposit: is_paused(PauseAction::Deposit)?,
+        redeem: is_paused(PauseAction::Redeem)?,
+        borrow: is_paused(PauseAction::Borrow)?,
+        repay: is_paused(PauseAction::Repay)?,
+        liquidate: is_paused(PauseAction::Liquidate)?,
+        flash_loan: is_paused(PauseAction::FlashLoan)?,
+    })
+}
+
+pub fn get_pause_state(deps: Deps) -> StdResult<PauseState> {
+    let markets = SUPPORTED_TOKENS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|denom| {
+            let denom = denom?;
+
+            Ok(MarketPauseState {
+                paused_actions: get_paused_actions(|action| {
+                    Ok(MARKET_PAUSED_ACTIONS
+                        .may_load(deps.storage, (denom.clone(), action.key().to_string()))?
+                        .unwrap_or_default())
+                })?,
+                effective: get_paused_actions(|action| is_action_paused(deps, &denom, action))?,
+                denom,
+            })
+        })
+        .collect::<StdResult<Vec<MarketPauseState>>>()?;
+
+    Ok(PauseState {
+        is_paused: IS_PAUSED.load(deps.storage)?,
+        paused_actions: get_paused_actions(|action| {
+            Ok(PAUSED_ACTIONS
+                .may_load(deps.storage, action.key().to_string())?
+                .unwrap_or_default())
+        })?,
+        markets,
+    })
+}
@@ -0,0 +1,488 @@
+use crate::access_control::{ensure_admin, ensure_role};
+use crate::contract::query::{
+    get_supported_tokens, get_total_borrow_data, get_total_mm_token_supply,
+};
+use crate::error::ContractError;
+use crate::msg::{EmissionSchedule, RewardIndex, RewardSide, Role};
+use crate::state::{
+    EMISSION_SCHEDULES, NEXT_EMISSION_SCHEDULE_ID, NEXT_REWARD_ID, REWARD_IDS, REWARD_INDEX,
+    SUPPORTED_TOKENS, UNDISTRIBUTED_REWARDS, USER_ACCRUED_REWARDS, USER_BORROWING_INFO,
+    USER_MM_TOKEN_BALANCE, USER_REWARD_INDEX,
+};
+use cosmwasm_std::{
+    attr, coin, ensure, BankMsg, Coin, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
+    Timestamp, Uint128,
+};
+use cw_utils::{must_pay, nonpayable};
+use std::collections::BTreeMap;
+
+const REWARD_INDEX_DECIMALS: u32 = 18;
+
+const REWARD_SIDES: [RewardSide; 2] = [RewardSide::Supply, RewardSide::Borrow];
+
+pub fn get_emission_schedules(deps: Deps) -> StdResult<Vec<EmissionSchedule>> {
+    EMISSION_SCHEDULES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, schedule)| schedule))
+        .collect()
+}
+
+pub fn get_undistributed_rewards(deps: Deps) -> StdResult<Vec<Coin>> {
+    UNDISTRIBUTED_REWARDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(reward_denom, amount)| coin(amount.u128(), reward_denom)))
+        .collect()
+}
+
+fn get_market_emission_schedules(
+    deps: Deps,
+    denom: String,
+    side: &RewardSide,
+) -> StdResult<Vec<EmissionSchedule>> {
+    EMISSION_SCHEDULES
+        .prefix((denom, side.key().to_string()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, schedule)| schedule))
+        .collect()
+}
+
+// reward ids of the side by reward denom
+fn get_reward_ids(deps: Deps, denom: String, side: &RewardSide) -> StdResult<Vec<(String, u64)>> {
+    REWARD_IDS
+        .prefix((denom, side.key().to_string()))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+// balance of the user that the rewards of the side are shared by
+fn get_user_reward_balance(
+    deps: Deps,
+    user: String,
+    denom: String,
+    side: &RewardSide,
+) -> StdResult<u128> {
+    Ok(match side {
+        RewardSide::Supply => USER_MM_TOKEN_BALANCE
+            .may_load(deps.storage, (user, denom))?
+            .unwrap_or_default()
+            .u128(),
+        RewardSide::Borrow => USER_BORROWING_INFO
+            .may_load(deps.storage, (user, denom))?
+            .unwrap_or_default()
+            .borrowed_amount
+            .u128(),
+    })
+}
+
+fn get_total_reward_balance(deps: Deps, denom: String, side: &RewardSide) -> StdResult<u128> {
+    Ok(match side {
+        RewardSide::Supply => get_total_mm_token_supply(deps, denom)?.u128(),
+        RewardSide::Borrow => get_total_borrow_data(deps, denom)
+            .unwrap_or_default()
+            .variable_borrowed_amount
+            .u128(),
+    })
+}
+
+// all schedules of the reward denom on the side share one index,
+// emissions in periods without any balance on the side are returned as undistributed
+fn get_current_reward_index(
+    deps: Deps,
+    env: Env,
+    schedules: &[EmissionSchedule],
+    reward_denom: &str,
+    reward_id: u64,
+    total_balance: u128,
+) -> StdResult<(RewardIndex, Uint128)> {
+    let reward_index = REWARD_INDEX
+        .may_load(deps.storage, reward_id)?
+        .unwrap_or_default();
+
+    let mut emission = Uint128::zero();
+    for schedule in schedules {
+        if schedule.reward_denom != reward_denom {
+            continue;
+        }
+
+        let from = reward_index.timestamp.max(schedule.start).seconds();
+        let to = env.block.time.min(schedule.end).seconds();
+
+        if to > from {
+            emission += schedule.emission_per_second * Uint128::from(to - from);
+        }
+    }
+
+    let mut index = reward_index.index;
+    let mut undistributed = Uint128::zero();
+    if total_balance > 0 {
+        index += emission.multiply_ratio(10u128.pow(REWARD_INDEX_DECIMALS), total_balance);
+    } else {
+        undistributed = emission;
+    }
+
+    Ok((
+        RewardIndex {
+            index,
+            timestamp: env.block.time,
+        },
+        undistributed,
+    ))
+}
+
+fn calc_accrued_rewards(balance: u128, index: Uint128, user_index: Uint128) -> Uint128 {
+    (index - user_index).multiply_ratio(balance, 10u128.pow(REWARD_INDEX_DECIMALS))
+}
+
+/// Brings the reward indexes of the side up to date and removes the finished schedules,
+/// returns the current index by reward id and reward denom
+fn update_reward_indexes(
+    deps: &mut DepsMut,
+    env: Env,
+    denom: String,
+    side: &RewardSide,
+) -> StdResult<Vec<(u64, String, Uint128)>> {
+    let schedules = get_market_emission_schedules(deps.as_ref(), denom.clone(), side)?;
+    let total_balance = get_total_reward_balance(deps.as_ref(), denom.clone(), side)?;
+
+    let mut reward_indexes = vec![];
+    for (reward_denom, reward_id) in get_reward_ids(deps.as_ref(), denom.clone(), side)? {
+        let (reward_index, undistributed) = get_current_reward_index(
+            deps.as_ref(),
+            env.clone(),
+            &schedules,
+            &reward_denom,
+            reward_id,
+            total_balance,
+        )?;
+
+        REWARD_INDEX.save(deps.storage, reward_id, &reward_index)?;
+
+        if !undistributed.is_zero() {
+            UNDISTRIBUTED_REWARDS.update(
+                deps.storage,
+                reward_denom.clone(),
+                |amount| -> StdResult<_> { Ok(amount.unwrap_or_default() + undistributed) },
+            )?;
+        }
+
+        reward_indexes.push((reward_id, reward_denom, reward_index.index));
+    }
+
+    // the whole emission of a finished schedule is already in the index
+    for schedule in schedules {
+        if schedule.end <= env.block.time {
+            EMISSION_SCHEDULES.remove(
+                deps.storage,
+                (denom.clone(), side.key().to_string(), schedule.id),
+            );
+        }
+    }
+
+    Ok(reward_indexes)
+}
+
+/// Checkpoints the rewards of the user, called before every change of the balances of the side
+pub fn update_user_rewards(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+    side: RewardSide,
+) -> StdResult<()> {
+    let reward_indexes = update_reward_indexes(deps, env, denom.clone(), &side)?;
+
+    let balance = get_user_reward_balance(deps.as_ref(), user.clone(), denom, &side)?;
+
+    for (reward_id, reward_denom, index) in reward_indexes {
+        let user_index = USER_REWARD_INDEX
+            .may_load(deps.storage, (user.clone(), reward_id))?
+            .unwrap_or_default();
+
+        let accrued_rewards = calc_accrued_rewards(balance, index, user_index);
+
+        if !accrued_rewards.is_zero() {
+            USER_ACCRUED_REWARDS.update(
+                deps.storage,
+                (user.clone(), reward_denom),
+                |rewards| -> StdResult<_> { Ok(rewards.unwrap_or_default() + accrued_rewards) },
+            )?;
+        }
+
+        USER_REWARD_INDEX.save(deps.storage, (user.clone(), reward_id), &index)?;
+    }
+
+    Ok(())
+}
+
+pub fn get_pending_rewards(deps: Deps, env: Env, user: String) -> StdResult<Vec<Coin>> {
+    let mut pending_rewards: BTreeMap<String, Uint128> = BTreeMap::new();
+
+    for item in
+        USER_ACCRUED_REWARDS
+            .prefix(user.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (reward_denom, amount) = item?;
+        *pending_rewards.entry(reward_denom).or_default() += amount;
+    }
+
+    // nothing accrues on the sides where the user has no balance
+    for token in get_supported_tokens(deps)?.supported_tokens {
+        for side in REWARD_SIDES.iter() {
+            let balance = get_user_reward_balance(deps, user.clone(), token.denom.clone(), side)?;
+
+            if balance == 0 {
+                continue;
+            }
+
+            let schedules = get_market_emission_schedules(deps, token.denom.clone(), side)?;
+            let total_balance = get_total_reward_balance(deps, token.denom.clone(), side)?;
+
+            for (reward_denom, reward_id) in get_reward_ids(deps, token.denom.clone(), side)? {
+                let (reward_index, _) = get_current_reward_index(
+                    deps,
+                    env.clone(),
+                    &schedules,
+                    &reward_denom,
+                    reward_id,
+                    total_balance,
+                )?;
+
+                let user_index = USER_REWARD_INDEX
+                    .may_load(deps.storage, (user.clone(), reward_id))?
+                    .unwrap_or_default();
+
+                *pending_rewards.entry(reward_denom).or_default() +=
+                    calc_accrued_rewards(balance, reward_index.index, user_index);
+            }
+        }
+    }
+
+    Ok(pending_rewards
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(reward_denom, amount)| coin(amount.u128(), reward_denom))
+        .collect())
+}
+
+/// Removes the emission schedules of a market without balances,
+/// the rewards that were not emitted yet can be swept as undistributed
+pub fn remove_market_emission_schedules(
+    deps: &mut DepsMut,
+    env: Env,
+    denom: String,
+) -> StdResult<()> {
+    for side in REWARD_SIDES.iter() {
+        update_reward_indexes(deps, env.clone(), denom.clone(), side)?;
+
+        for schedule in get_market_emission_schedules(deps.as_ref(), denom.clone(), side)? {
+            let from = env.block.time.max(schedule.start).seconds();
+            let to = schedule.end.seconds();
+
+            if to > from {
+                let remaining_emission = schedule.emission_per_second * Uint128::from(to - from);
+
+                UNDISTRIBUTED_REWARDS.update(
+                    deps.storage,
+                    schedule.reward_denom.clone(),
+                    |amount| -> StdResult<_> {
+                        Ok(amount.unwrap_or_default() + remaining_emission)
+                    },
+                )?;
+            }
+
+            EMISSION_SCHEDULES.remove(
+                deps.storage,
+                (denom.clone(), side.key().to_string(), schedule.id),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_add_emission_schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    side: RewardSide,
+    reward_denom: String,
+    emission_per_second: Uint128,
+    start: Timestamp,
+    end: Timestamp,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info.sender)?;
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    // rewards are kept apart from the liquidity of the markets
+    ensure!(
+        !SUPPORTED_TOKENS.has(deps.storage, reward_denom.clone())
+            && !emission_per_second.is_zero()
+            && env.block.time <= start
+            && start < end,
+        ContractError::InvalidEmissionSchedule {}
+    );
+
+    let total_emission = emission_per_second * Uint128::from(end.seconds() - start.seconds());
+
+    ensure!(
+        must_pay(&info, &reward_denom)? == total_emission,
+        ContractError::InvalidFunds {
+            msg: "Funds must cover the whole emission of the schedule".to_string()
+        }
+    );
+
+    let id = NEXT_EMISSION_SCHEDULE_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    NEXT_EMISSION_SCHEDULE_ID.save(deps.storage, &(id + 1))?;
+
+    EMISSION_SCHEDULES.save(
+        deps.storage,
+        (denom.clone(), side.key().to_string(), id),
+        &EmissionSchedule {
+            id,
+            denom: denom.clone(),
+            side: side.clone(),
+            reward_denom: reward_denom.clone(),
+            emission_per_second,
+            start,
+            end,
+        },
+    )?;
+
+    // the index of the reward denom is created with its first schedule
+    let reward_key = (denom.clone(), side.key().to_string(), reward_denom.clone());
+    if !REWARD_IDS.has(deps.storage, reward_key.clone()) {
+        let reward_id = NEXT_REWARD_ID.may_load(deps.storage)?.unwrap_or_default();
+        NEXT_REWARD_ID.save(deps.storage, &(reward_id + 1))?;
+
+        REWARD_IDS.save(deps.storage, reward_key, &reward_id)?;
+        REWARD_INDEX.save(
+            deps.storage,
+            reward_id,
+            &RewardIndex {
+                index: Uint128::zero(),
+                timestamp: env.block.time,
+            },
+        )?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_emission_schedule"),
+        attr("schedule_id", id.to_string()),
+        attr("denom", denom),
+        attr("side", side.key()),
+        attr("reward_denom", reward_denom),
+        attr("total_emission", total_emission),
+    ]))
+}
+
+pub fn execute_claim_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let user = info.sender.to_string();
+
+    for token in get_supported_tokens(deps.as_ref())?.supported_tokens {
+        for side in REWARD_SIDES {
+            if get_user_reward_balance(deps.as_ref(), user.clone(), token.denom.clone(), &side)? > 0
+            {
+                update_user_rewards(
+                    &mut deps,
+                    env.clone(),
+                    user.clone(),
+                    token.denom.clone(),
+                    side,
+                )?;
+            }
+        }
+    }
+
+    let accrued_rewards = USER_ACCRUED_REWARDS
+        .prefix(user.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut claimed_rewards = vec![];
+    for (reward_denom, amount) in accrued_rewards {
+        USER_ACCRUED_REWARDS.remove(deps.storage, (user.clone(), reward_denom.clone()));
+        claimed_rewards.push(coin(amount.u128(), reward_denom));
+    }
+
+    let mut resp = Response::new();
+
+    if !claimed_rewards.is_empty() {
+        resp = resp.add_message(BankMsg::Send {
+            to_address: user.clone(),
+            amount: claimed_rewards.clone(),
+        });
+    }
+
+    Ok(resp.add_attributes(vec![
+        attr("action", "claim_rewards"),
+        attr("user", user),
+        attr(
+            "claimed_rewards",
+            claimed_rewards
+                .iter()
+                .map(|reward| reward.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+    ]))
+}
+
+/// Sends the rewards emitted while nobody could earn them to the recipient
+pub fn execute_sweep_undistributed_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    ensure_role(deps.as_ref(), &info.sender, Role::Treasury)?;
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    for token in get_supported_tokens(deps.as_ref())?.supported_tokens {
+        for side in REWARD_SIDES.iter() {
+            update_reward_indexes(&mut deps, env.clone(), token.denom.clone(), side)?;
+        }
+    }
+
+    let undistributed_rewards = get_undistributed_rewards(deps.as_ref())?;
+
+    for reward in undistributed_rewards.iter() {
+        UNDISTRIBUTED_REWARDS.remove(deps.storage, reward.denom.clone());
+    }
+
+    let mut resp = Response::new();
+
+    if !undistributed_rewards.is_empty() {
+        resp = resp.add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: undistributed_rewards.clone(),
+        });
+    }
+
+    Ok(resp.add_attributes(vec![
+        attr("action", "sweep_undistributed_rewards"),
+        attr("recipient", recipient),
+        attr(
+            "swept_rewards",
+            undistributed_rewards
+                .iter()
+                .map(|reward| reward.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+    ]))
+}
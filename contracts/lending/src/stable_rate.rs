@@ -0,0 +1,431 @@
+use crate::access_control::ensure_role;
+use crate::contract::query::{
+    calc_borrow_amount_with_interest, get_token_decimal, get_total_borrow_data,
+    get_utilization_rate_by_token,
+};
+use crate::contract::{
+    execute_update_liquidity_index_data, DecimalExt, HUNDRED, HUNDRED_PERCENT,
+    INTEREST_RATE_DECIMALS,
+};
+use crate::error::ContractError;
+use crate::interest_rate::calculate_two_slope_rate;
+use crate::msg::{Role, StableRateConfiguration, TotalBorrowData, UserBorrowingInfo};
+use crate::state::{
+    STABLE_RATE_CONFIGURATION, SUPPORTED_TOKENS, TOTAL_BORROW_DATA, USER_STABLE_BORROWING_INFO,
+};
+use cosmwasm_std::{attr, ensure, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128};
+use rust_decimal::Decimal;
+use std::ops::{Add, Div, Mul};
+
+pub fn get_stable_rate_configuration(
+    deps: Deps,
+    denom: String,
+) -> StdResult<Option<StableRateConfiguration>> {
+    STABLE_RATE_CONFIGURATION.may_load(deps.storage, denom)
+}
+
+pub fn get_stable_interest_rate(deps: Deps, env: Env, denom: String) -> StdResult<Uint128> {
+    let Some(configuration) = get_stable_rate_configuration(deps, denom.clone())? else {
+        return Ok(Uint128::zero());
+    };
+
+    let utilization_rate = get_utilization_rate_by_token(deps, env, denom)?.u128();
+
+    Ok(Uint128::from(calculate_two_slope_rate(
+        utilization_rate,
+        configuration.min_interest_rate,
+        configuration.safe_borrow_max_rate,
+        configuration.rate_growth_factor,
+        configuration.optimal_utilisation_ratio,
+    )))
+}
+
+pub fn get_user_stable_borrowing_info(
+    deps: Deps,
+    env: Env,
+    user: String,
+    denom: String,
+) -> StdResult<UserBorrowingInfo> {
+    match USER_STABLE_BORROWING_INFO.may_load(deps.storage, (user, denom.clone()))? {
+        Some(user_stable_borrowing_info) => Ok(user_stable_borrowing_info),
+        None => Ok(UserBorrowingInfo {
+            denom: denom.clone(),
+            borrowed_amount: Uint128::zero(),
+            average_interest_rate: get_stable_interest_rate(deps, env.clone(), denom)?,
+            timestamp: env.block.time,
+        }),
+    }
+}
+
+pub fn get_user_stable_borrow_amount_with_interest(
+    deps: Deps,
+    env: Env,
+    user: String,
+    denom: String,
+) -> StdResult<Uint128> {
+    let Some(user_stable_borrowing_info) =
+        USER_STABLE_BORROWING_INFO.may_load(deps.storage, (user, denom.clone()))?
+    else {
+        return Ok(Uint128::zero());
+    };
+
+    calc_borrow_amount_with_interest(
+        user_stable_borrowing_info.borrowed_amount.u128(),
+        user_stable_borrowing_info.average_interest_rate.u128(),
+        env.block
+            .time
+            .seconds()
+            .saturating_sub(user_stable_borrowing_info.timestamp.seconds()) as u128,
+        get_token_decimal(deps, denom)?.u128() as u32,
+    )
+}
+
+fn calc_annual_interest_income(
+    borrowed_amount: u128,
+    interest_rate: u128,
+    token_decimals: u32,
+) -> StdResult<u128> {
+    Decimal::from_i128_with_scale(borrowed_amount as i128, token_decimals)
+        .mul(Decimal::from_i128_with_scale(
+            (interest_rate / HUNDRED) as i128,
+            INTEREST_RATE_DECIMALS,
+        ))
+        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)
+}
+
+fn calc_average_interest_rate(
+    expected_annual_interest_income: u128,
+    borrowed_amount: u128,
+    token_decimals: u32,
+) -> StdResult<u128> {
+    if borrowed_amount == 0 {
+        return Ok(0);
+    }
+
+    Ok(HUNDRED
+        * Decimal::from_i128_with_scale(
+            expected_annual_interest_income as i128,
+            INTEREST_RATE_DECIMALS,
+        )
+        .div(Decimal::from_i128_with_scale(
+            borrowed_amount as i128,
+            token_decimals,
+        ))
+        .to_u128_with_decimals(INTEREST_RATE_DECIMALS)?)
+}
+
+// replaces the old stable position with the new one in the market totals
+pub(crate) fn update_total_stable_borrow_data(
+    deps: &mut DepsMut,
+    env: Env,
+    denom: String,
+    old_position: &UserBorrowingInfo,
+    new_position: &UserBorrowingInfo,
+) -> StdResult<()> {
+    update_total_fixed_rate_borrow_data(deps, env, denom, old_position, new_position, false)
+}
+
+// replaces the old term loan with the new one in the market totals,
+// term debt is kept out of the stable totals
+pub(crate) fn update_total_term_borrow_data(
+    deps: &mut DepsMut,
+    env: Env,
+    denom: String,
+    old_loan: &UserBorrowingInfo,
+    new_loan: &UserBorrowingInfo,
+) -> StdResult<()> {
+    update_total_fixed_rate_borrow_data(deps, env, denom, old_loan, new_loan, true)
+}
+
+fn update_total_fixed_rate_borrow_data(
+    deps: &mut DepsMut,
+    env: Env,
+    denom: String,
+    old_position: &UserBorrowingInfo,
+    new_position: &UserBorrowingInfo,
+    is_term_loan: bool,
+) -> StdResult<()> {
+    let total_borrow_data = get_total_borrow_data(deps.as_ref(), denom.clone())?;
+
+    let token_decimals = get_token_decimal(deps.as_ref(), denom.clone())?.u128() as u32;
+
+    let old_income = calc_annual_interest_income(
+        old_position.borrowed_amount.u128(),
+        old_position.average_interest_rate.u128(),
+        token_decimals,
+    )?;
+    let new_income = calc_annual_interest_income(
+        new_position.borrowed_amount.u128(),
+        new_position.average_interest_rate.u128(),
+        token_decimals,
+    )?;
+
+    let expected_annual_interest_income =
+        (total_borrow_data.expected_annual_interest_income.u128() + new_income)
+            .saturating_sub(old_income);
+    let total_borrowed_amount = (total_borrow_data.total_borrowed_amount
+        + new_position.borrowed_amount)
+        .u128()
+        .saturating_sub(old_position.borrowed_amount.u128());
+
+    let mut stable_expected_annual_interest_income = calc_annual_interest_income(
+        total_borrow_data.stable_borrowed_amount.u128(),
+        total_borrow_data.stable_average_interest_rate.u128(),
+        token_decimals,
+    )?;
+    let mut stable_borrowed_amount = total_borrow_data.stable_borrowed_amount.u128();
+    let mut term_borrowed_amount = total_borrow_data.term_borrowed_amount.u128();
+
+    if is_term_loan {
+        term_borrowed_amount = (term_borrowed_amount + new_position.borrowed_amount.u128())
+            .saturating_sub(old_position.borrowed_amount.u128());
+    } else {
+        stable_expected_annual_interest_income =
+            (stable_expected_annual_interest_income + new_income).saturating_sub(old_income);
+        stable_borrowed_amount = (stable_borrowed_amount + new_position.borrowed_amount.u128())
+            .saturating_sub(old_position.borrowed_amount.u128());
+    }
+
+    TOTAL_BORROW_DATA.save(
+        deps.storage,
+        denom.clone(),
+        &TotalBorrowData {
+            denom,
+            total_borrowed_amount: Uint128::from(total_borrowed_amount),
+            expected_annual_interest_income: Uint128::from(expected_annual_interest_income),
+            average_interest_rate: Uint128::from(calc_average_interest_rate(
+                expected_annual_interest_income,
+                total_borrowed_amount,
+                token_decimals,
+            )?),
+            variable_borrowed_amount: Uint128::from(
+                total_borrowed_amount
+                    .saturating_sub(stable_borrowed_amount)
+                    .saturating_sub(term_borrowed_amount),
+            ),
+            stable_borrowed_amount: Uint128::from(stable_borrowed_amount),
+            stable_average_interest_rate: Uint128::from(calc_average_interest_rate(
+                stable_expected_annual_interest_income,
+                stable_borrowed_amount,
+                token_decimals,
+            )?),
+            term_borrowed_amount: Uint128::from(term_borrowed_amount),
+            timestamp: env.block.time,
+        },
+    )
+}
+
+/// Adds the amount to the stable position of the user at the current stable rate
+pub fn execute_stable_borrow(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+    amount: u128,
+) -> Result<(), ContractError> {
+    ensure!(
+        STABLE_RATE_CONFIGURATION.has(deps.storage, denom.clone()),
+        ContractError::StableRateBorrowingNotEnabled {}
+    );
+
+    let user_stable_borrowing_info =
+        get_user_stable_borrowing_info(deps.as_ref(), env.clone(), user.clone(), denom.clone())?;
+
+    let user_stable_borrow_amount_with_interest = get_user_stable_borrow_amount_with_interest(
+        deps.as_ref(),
+        env.clone(),
+        user.clone(),
+        denom.clone(),
+    )?
+    .u128();
+
+    let stable_interest_rate =
+        get_stable_interest_rate(deps.as_ref(), env.clone(), denom.clone())?.u128();
+
+    let token_decimals = get_token_decimal(deps.as_ref(), denom.clone())?.u128() as u32;
+
+    let new_user_borrow_amount = user_stable_borrow_amount_with_interest + amount;
+
+    // the rate of the new part is locked at the current stable rate
+    let average_interest_rate = Decimal::from_i128_with_scale(
+        user_stable_borrow_amount_with_interest as i128,
+        token_decimals,
+    )
+    .mul(Decimal::from_i128_with_scale(
+        user_stable_borrowing_info.average_interest_rate.u128() as i128,
+        INTEREST_RATE_DECIMALS,
+    ))
+    .add(
+        Decimal::from_i128_with_scale(amount as i128, token_decimals).mul(
+            Decimal::from_i128_with_scale(stable_interest_rate as i128, INTEREST_RATE_DECIMALS),
+        ),
+    )
+    .div(Decimal::from_i128_with_scale(
+        new_user_borrow_amount as i128,
+        token_decimals,
+    ))
+    .to_u128_with_decimals(INTEREST_RATE_DECIMALS)?;
+
+    let new_user_stable_borrowing_info = UserBorrowingInfo {
+        denom: denom.clone(),
+        borrowed_amount: Uint128::from(new_user_borrow_amount),
+        average_interest_rate: Uint128::from(average_interest_rate),
+        timestamp: env.block.time,
+    };
+
+    update_total_stable_borrow_data(
+        deps,
+        env,
+        denom.clone(),
+        &user_stable_borrowing_info,
+        &new_user_stable_borrowing_info,
+    )?;
+
+    USER_STABLE_BORROWING_INFO.save(
+        deps.storage,
+        (user, denom),
+        &new_user_stable_borrowing_info,
+    )?;
+
+    Ok(())
+}
+
+/// Decreases the stable position of the user, returns the amount that was actually repaid
+pub fn execute_repay_stable_borrowing(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+    amount: u128,
+) -> StdResult<u128> {
+    let Some(user_stable_borrowing_info) =
+        USER_STABLE_BORROWING_INFO.may_load(deps.storage, (user.clone(), denom.clone()))?
+    else {
+        return Ok(0);
+    };
+
+    let user_stable_borrow_amount_with_interest = get_user_stable_borrow_amount_with_interest(
+        deps.as_ref(),
+        env.clone(),
+        user.clone(),
+        denom.clone(),
+    )?
+    .u128();
+
+    let repay_amount = amount.min(user_stable_borrow_amount_with_interest);
+
+    let new_user_stable_borrowing_info = UserBorrowingInfo {
+        denom: denom.clone(),
+        borrowed_amount: Uint128::from(user_stable_borrow_amount_with_interest - repay_amount),
+        average_interest_rate: user_stable_borrowing_info.average_interest_rate,
+        timestamp: env.block.time,
+    };
+
+    update_total_stable_borrow_data(
+        deps,
+        env,
+        denom.clone(),
+        &user_stable_borrowing_info,
+        &new_user_stable_borrowing_info,
+    )?;
+
+    if new_user_stable_borrowing_info.borrowed_amount.is_zero() {
+        USER_STABLE_BORROWING_INFO.remove(deps.storage, (user, denom));
+    } else {
+        USER_STABLE_BORROWING_INFO.save(
+            deps.storage,
+            (user, denom),
+            &new_user_stable_borrowing_info,
+        )?;
+    }
+
+    Ok(repay_amount)
+}
+
+pub fn execute_set_stable_rate_configuration(
+    deps: DepsMut,
+    info: MessageInfo,
+    configuration: StableRateConfiguration,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.as_ref(), &info.sender, Role::RiskManager)?;
+
+    ensure!(
+        SUPPORTED_TOKENS.has(deps.storage, configuration.denom.clone()),
+        ContractError::TokenNotSupported {}
+    );
+
+    ensure!(
+        0 < configuration.optimal_utilisation_ratio
+            && configuration.optimal_utilisation_ratio < HUNDRED_PERCENT
+            && configuration.min_interest_rate <= configuration.safe_borrow_max_rate
+            && configuration.rebalance_utilisation_ratio <= HUNDRED_PERCENT,
+        ContractError::InvalidStableRateConfiguration {}
+    );
+
+    STABLE_RATE_CONFIGURATION.save(deps.storage, configuration.denom.clone(), &configuration)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set-stable-rate-configuration")
+        .add_attribute("denom", configuration.denom))
+}
+
+/// Moves the stable position up to the current stable rate, positions are never rebalanced down
+pub fn execute_rebalance_stable_rate(
+    mut deps: DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let configuration = get_stable_rate_configuration(deps.as_ref(), denom.clone())?
+        .ok_or(ContractError::StableRateBorrowingNotEnabled {})?;
+
+    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+
+    let user_stable_borrowing_info = USER_STABLE_BORROWING_INFO
+        .may_load(deps.storage, (user.clone(), denom.clone()))?
+        .ok_or(ContractError::StableRateRebalanceNotAllowed {})?;
+
+    let utilization_rate =
+        get_utilization_rate_by_token(deps.as_ref(), env.clone(), denom.clone())?.u128();
+
+    let stable_interest_rate = get_stable_interest_rate(deps.as_ref(), env.clone(), denom.clone())?;
+
+    ensure!(
+        utilization_rate >= configuration.rebalance_utilisation_ratio
+            && stable_interest_rate > user_stable_borrowing_info.average_interest_rate,
+        ContractError::StableRateRebalanceNotAllowed {}
+    );
+
+    let new_user_stable_borrowing_info = UserBorrowingInfo {
+        denom: denom.clone(),
+        borrowed_amount: get_user_stable_borrow_amount_with_interest(
+            deps.as_ref(),
+            env.clone(),
+            user.clone(),
+            denom.clone(),
+        )?,
+        average_interest_rate: stable_interest_rate,
+        timestamp: env.block.time,
+    };
+
+    update_total_stable_borrow_data(
+        &mut deps,
+        env,
+        denom.clone(),
+        &user_stable_borrowing_info,
+        &new_user_stable_borrowing_info,
+    )?;
+
+    USER_STABLE_BORROWING_INFO.save(
+        deps.storage,
+        (user.clone(), denom.clone()),
+        &new_user_stable_borrowing_info,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "rebalance_stable_rate"),
+        attr("user", user),
+        attr("denom", denom),
+        attr("interest_rate", stable_interest_rate),
+    ]))
+}
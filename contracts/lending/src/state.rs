@@ -1,10 +1,12 @@
 use crate::msg::{
-    LiquidityIndexData, ReserveConfiguration, TokenInfo, TokenInterestRateModelParams,
-    TotalBorrowData, UserBorrowingInfo,
+    AdaptiveRateState, BadDebtWriteOff, BorrowAllowance, EModeCategory, EmissionSchedule,
+    FlashLoanData, IsolationConfiguration, LiquidationConfiguration, LiquidityIndexData,
+    MarketStatus, OracleConfiguration, PendingOwnership, PriceSources, QueuedAction, RateStrategy,
+    ReserveConfiguration, RewardIndex, StableRateConfiguration, TermLoan, TokenInfo,
+    TokenInterestRateModelParams, TotalBorrowData, UserBorrowingInfo,
 };
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Timestamp};
 use pyth_sdk_cw::PriceIdentifier;
-use std::ops::Add;
 use {
     cosmwasm_std::Uint128,
     cw_storage_plus::{Item, Map},
@@ -18,6 +20,12 @@ Key: (user_address_1, token_B) -> Value: balance_for_token_B
 Key: (user_address_2, token_A) -> Value: balance_for_token_A
  */
 
+pub const MM_TOKEN_ADDRESS: Map<String, Addr> = Map::new("mm_token_address");
+/*
+MM_TOKEN_ADDRESS STORAGE
+Key: denom -> Value: address of the CW20 receipt token
+ */
+
 pub const USER_DEPOSIT_AS_COLLATERAL: Map<(String, String), bool> =
     Map::new("user_deposit_as_collateral");
 /*
@@ -29,17 +37,49 @@ Key: (user_address_2, token_A) -> Value: user_deposit_as_collateral
 
 pub const PRICES: Map<String, u128> = Map::new("prices");
 
+pub const PRICE_TIMESTAMPS: Map<String, Timestamp> = Map::new("price_timestamps");
+/*
+PRICE_TIMESTAMPS STORAGE
+Key: denom -> Value: publish time of the cached price in PRICES
+*/
+
 pub const SUPPORTED_TOKENS: Map<String, TokenInfo> = Map::new("supported_tokens");
 /*
 SUPPORTED_TOKENS STORAGE
 Key: denom -> Value: TokenInfo
 */
 
-pub const ADMIN: Item<String> = Item::new("admin");
+pub const ADMIN: Item<Addr> = Item::new("admin");
 /*
 ADMIN that is eligible to fund contract with reserves [ contract itself by default ]
 */
 
+pub const PENDING_OWNERSHIP: Item<PendingOwnership> = Item::new("pending_ownership");
+/*
+PENDING_OWNERSHIP STORAGE
+Value: proposed admin and proposal expiry, removed once accepted or cancelled
+*/
+
+pub const ROLES: Map<(String, Addr), bool> = Map::new("roles");
+/*
+ROLES STORAGE
+Key: (role, address) -> Value: true while the role is granted
+*/
+
+pub const TIMELOCK_DELAY: Item<u64> = Item::new("timelock_delay");
+/*
+TIMELOCK_DELAY STORAGE
+Value: delay in seconds before a queued action can be executed, absent means no timelock
+*/
+
+pub const QUEUED_ACTIONS: Map<u64, QueuedAction> = Map::new("queued_actions");
+/*
+QUEUED_ACTIONS STORAGE
+Key: action id -> Value: QueuedAction, removed once executed or cancelled
+*/
+
+pub const NEXT_ACTION_ID: Item<u64> = Item::new("next_action_id");
+
 pub const PRICE_UPDATER_ADDRESS: Item<String> = Item::new("price_updater");
 
 pub const USER_BORROWING_INFO: Map<(String, String), UserBorrowingInfo> =
@@ -51,6 +91,75 @@ Key: (user_address_1, token_B) -> Value: user_borrowing_info
 Key: (user_address_2, token_A) -> Value: user_borrowing_info
  */
 
+pub const USER_STABLE_BORROWING_INFO: Map<(String, String), UserBorrowingInfo> =
+    Map::new("user_stable_borrowing_info");
+/*
+USER_STABLE_BORROWING_INFO STORAGE
+Key: (user, denom) -> Value: stable rate position of the user, removed once repaid
+*/
+
+pub const TERM_LOANS: Map<u64, TermLoan> = Map::new("term_loans");
+/*
+TERM_LOANS STORAGE
+Key: loan id -> Value: TermLoan, removed once repaid
+*/
+
+pub const USER_TERM_LOANS: Map<(String, u64), bool> = Map::new("user_term_loans");
+/*
+USER_TERM_LOANS STORAGE
+Key: (borrower, loan id) -> Value: true while the loan is outstanding
+*/
+
+pub const NEXT_TERM_LOAN_ID: Item<u64> = Item::new("next_term_loan_id");
+
+pub const BORROW_ALLOWANCES: Map<(String, String, String), BorrowAllowance> =
+    Map::new("borrow_allowances");
+/*
+BORROW_ALLOWANCES STORAGE
+Key: (delegator, delegatee, denom) -> Value: BorrowAllowance, decreased by every delegated borrow
+*/
+
+pub const EMISSION_SCHEDULES: Map<(String, String, u64), EmissionSchedule> =
+    Map::new("emission_schedules");
+/*
+EMISSION_SCHEDULES STORAGE
+Key: (denom, side, schedule id) -> Value: EmissionSchedule, removed once its emission is in the reward index
+*/
+
+pub const NEXT_EMISSION_SCHEDULE_ID: Item<u64> = Item::new("next_emission_schedule_id");
+
+pub const REWARD_IDS: Map<(String, String, String), u64> = Map::new("reward_ids");
+/*
+REWARD_IDS STORAGE
+Key: (denom, side, reward denom) -> Value: id of the reward index shared by the schedules of the reward denom
+*/
+
+pub const NEXT_REWARD_ID: Item<u64> = Item::new("next_reward_id");
+
+pub const REWARD_INDEX: Map<u64, RewardIndex> = Map::new("reward_index");
+/*
+REWARD_INDEX STORAGE
+Key: reward id -> Value: global RewardIndex of the reward
+*/
+
+pub const USER_REWARD_INDEX: Map<(String, u64), Uint128> = Map::new("user_reward_index");
+/*
+USER_REWARD_INDEX STORAGE
+Key: (user, reward id) -> Value: reward index at the last checkpoint of the user
+*/
+
+pub const UNDISTRIBUTED_REWARDS: Map<String, Uint128> = Map::new("undistributed_rewards");
+/*
+UNDISTRIBUTED_REWARDS STORAGE
+Key: reward denom -> Value: rewards emitted while the side had no balance, kept until swept
+*/
+
+pub const USER_ACCRUED_REWARDS: Map<(String, String), Uint128> = Map::new("user_accrued_rewards");
+/*
+USER_ACCRUED_REWARDS STORAGE
+Key: (user, reward denom) -> Value: rewards accrued and not yet claimed
+*/
+
 pub const RESERVE_CONFIGURATION: Map<String, ReserveConfiguration> =
     Map::new("reserve_configuration");
 /*
@@ -58,6 +167,31 @@ RESERVE_CONFIGURATION STORAGE
 Key: denom -> Value: ReserveConfiguration
 */
 
+pub const LIQUIDATION_CONFIGURATION: Map<String, LiquidationConfiguration> =
+    Map::new("liquidation_configuration");
+/*
+LIQUIDATION_CONFIGURATION STORAGE
+Key: denom -> Value: LiquidationConfiguration
+*/
+
+pub const ORACLE_CONFIGURATION: Map<String, OracleConfiguration> = Map::new("oracle_configuration");
+/*
+ORACLE_CONFIGURATION STORAGE
+Key: denom -> Value: OracleConfiguration
+*/
+
+pub const PRICE_SOURCES: Map<String, PriceSources> = Map::new("price_sources");
+/*
+PRICE_SOURCES STORAGE
+Key: denom -> Value: PriceSources
+*/
+
+pub const RESERVE_FACTOR: Map<String, u128> = Map::new("reserve_factor");
+/*
+RESERVE_FACTOR STORAGE
+Key: denom -> Value: reserve_factor
+*/
+
 pub const TOKENS_INTEREST_RATE_MODEL_PARAMS: Map<String, TokenInterestRateModelParams> =
     Map::new("token_interest_rate_model_params");
 /*
@@ -65,6 +199,25 @@ TOKENS_INTEREST_RATE_MODEL_PARAMS STORAGE
 Key: denom -> Value: TokenInterestRateModelParams
 */
 
+pub const STABLE_RATE_CONFIGURATION: Map<String, StableRateConfiguration> =
+    Map::new("stable_rate_configuration");
+/*
+STABLE_RATE_CONFIGURATION STORAGE
+Key: denom -> Value: StableRateConfiguration, absent if stable borrowing is disabled
+*/
+
+pub const RATE_STRATEGIES: Map<String, RateStrategy> = Map::new("rate_strategies");
+/*
+RATE_STRATEGIES STORAGE
+Key: denom -> Value: RateStrategy, absent for markets on the two-slope curve
+*/
+
+pub const ADAPTIVE_RATE_STATE: Map<String, AdaptiveRateState> = Map::new("adaptive_rate_state");
+/*
+ADAPTIVE_RATE_STATE STORAGE
+Key: denom -> Value: rate of the adaptive strategy at the last update of the market
+*/
+
 pub const LIQUIDITY_INDEX_DATA: Map<String, LiquidityIndexData> = Map::new("liquidity_index_data");
 /*
 LIQUIDITY_INDEX_DATA STORAGE
@@ -77,6 +230,76 @@ TOTAL_BORROW_DATA STORAGE
 Key: denom -> Value: TotalBorrowData
 */
 
+pub const PROTOCOL_RESERVES: Map<String, u128> = Map::new("protocol_reserves");
+/*
+PROTOCOL_RESERVES STORAGE
+Key: denom -> Value: protocol reserves accrued until the last liquidity index update
+*/
+
+pub const INSURANCE_FUND: Map<String, Uint128> = Map::new("insurance_fund");
+/*
+INSURANCE_FUND STORAGE
+Key: denom -> Value: tokens kept to cover bad debt of the market, not available as liquidity
+*/
+
+pub const LIQUIDITY_INDEX_WRITE_DOWN_LN: Map<String, u128> =
+    Map::new("liquidity_index_write_down_ln");
+/*
+LIQUIDITY_INDEX_WRITE_DOWN_LN STORAGE
+Key: denom -> Value: ln of the factor the liquidity index is lowered by for socialized bad debt
+*/
+
+pub const BAD_DEBT_WRITE_OFFS: Map<u64, BadDebtWriteOff> = Map::new("bad_debt_write_offs");
+/*
+BAD_DEBT_WRITE_OFFS STORAGE
+Key: write-off id -> Value: BadDebtWriteOff
+*/
+
+pub const NEXT_BAD_DEBT_WRITE_OFF_ID: Item<u64> = Item::new("next_bad_debt_write_off_id");
+
+pub const E_MODE_CATEGORIES: Map<u8, EModeCategory> = Map::new("e_mode_categories");
+/*
+E_MODE_CATEGORIES STORAGE
+Key: category id -> Value: EModeCategory
+*/
+
+pub const USER_E_MODE: Map<String, u8> = Map::new("user_e_mode");
+/*
+USER_E_MODE STORAGE
+Key: user address -> Value: e-mode category id
+*/
+
+pub const FLASH_LOAN_FEE: Map<String, u128> = Map::new("flash_loan_fee");
+/*
+FLASH_LOAN_FEE STORAGE
+Key: denom -> Value: flash loan fee
+*/
+
+pub const FLASH_LOAN: Item<Vec<FlashLoanData>> = Item::new("flash_loan");
+/*
+FLASH_LOAN STORAGE
+Value: assets of the flash loan in progress, removed once the repayment is checked
+*/
+
+pub const MARKET_STATUS: Map<String, MarketStatus> = Map::new("market_status");
+/*
+MARKET_STATUS STORAGE
+Key: denom -> Value: MarketStatus, absent for active markets
+*/
+
+pub const ISOLATION_CONFIGURATION: Map<String, IsolationConfiguration> =
+    Map::new("isolation_configuration");
+/*
+ISOLATION_CONFIGURATION STORAGE
+Key: denom -> Value: IsolationConfiguration
+*/
+
+pub const ISOLATED_DEBT: Map<(String, String), Uint128> = Map::new("isolated_debt");
+/*
+ISOLATED_DEBT STORAGE
+Key: (isolated collateral denom, borrowed denom) -> Value: borrowed amount backed by this collateral
+*/
+
 // mapping of (token denom, price_identifier)
 pub const PRICE_FEED_IDS: Map<String, PriceIdentifier> = Map::new("price_feed_ids");
 
@@ -86,3 +309,21 @@ pub const PYTH_CONTRACT: Item<Addr> = Item::new("pyth_contract");
 pub const IS_TESTING: Item<bool> = Item::new("is_testing");
 
 pub const IS_PAUSED: Item<bool> = Item::new("is_paused");
+
+pub const PAUSED_ACTIONS: Map<String, bool> = Map::new("paused_actions");
+/*
+PAUSED_ACTIONS STORAGE
+Key: action -> Value: true if the action is paused for all markets
+*/
+
+pub const MARKET_PAUSED_ACTIONS: Map<(String, String), bool> = Map::new("market_paused_actions");
+/*
+MARKET_PAUSED_ACTIONS STORAGE
+Key: (denom, action) -> Value: true if the action is paused for the market
+*/
+
+pub const STORAGE_VERSION: Item<u64> = Item::new("storage_version");
+/*
+STORAGE_VERSION STORAGE
+Value: version of the storage layout, absent for contracts deployed before versioning (version 1)
+*/
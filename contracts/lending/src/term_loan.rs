@@ -0,0 +1,274 @@
+use crate::contract::query::{
+    calc_borrow_amount_with_interest, get_interest_rate, get_supported_tokens, get_token_decimal,
+};
+use crate::contract::{
+    execute_update_liquidity_index_data, generate_transfer_message, get_token_asset_info,
+    release_isolated_debt,
+};
+use crate::error::ContractError;
+use crate::msg::{PauseAction, TermLoan, UserBorrowingInfo};
+use crate::pause::ensure_action_not_paused;
+use crate::stable_rate::update_total_term_borrow_data;
+use crate::state::{NEXT_TERM_LOAN_ID, TERM_LOANS, USER_TERM_LOANS};
+use cosmwasm_std::{
+    attr, ensure, Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Timestamp,
+    Uint128,
+};
+use cw_utils::{nonpayable, one_coin};
+
+pub fn load_term_loan(deps: Deps, loan_id: u64) -> Result<TermLoan, ContractError> {
+    TERM_LOANS
+        .may_load(deps.storage, loan_id)?
+        .ok_or(ContractError::TermLoanNotFound {})
+}
+
+pub fn is_term_loan_overdue(env: &Env, term_loan: &TermLoan) -> bool {
+    env.block.time > term_loan.maturity
+}
+
+pub fn get_term_loan_amount_with_interest(
+    deps: Deps,
+    env: Env,
+    term_loan: &TermLoan,
+) -> StdResult<Uint128> {
+    calc_borrow_amount_with_interest(
+        term_loan.borrowed_amount.u128(),
+        term_loan.interest_rate.u128(),
+        env.block
+            .time
+            .seconds()
+            .saturating_sub(term_loan.timestamp.seconds()) as u128,
+        get_token_decimal(deps, term_loan.denom.clone())?.u128() as u32,
+    )
+}
+
+pub fn get_user_term_loans(deps: Deps, user: String) -> StdResult<Vec<TermLoan>> {
+    USER_TERM_LOANS
+        .prefix(user)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|loan_id| TERM_LOANS.load(deps.storage, loan_id?))
+        .collect()
+}
+
+pub fn get_user_term_borrow_amount_with_interest(
+    deps: Deps,
+    env: Env,
+    user: String,
+    denom: String,
+) -> StdResult<Uint128> {
+    let mut borrow_amount_with_interest = Uint128::zero();
+
+    for term_loan in get_user_term_loans(deps, user)? {
+        if term_loan.denom == denom {
+            borrow_amount_with_interest +=
+                get_term_loan_amount_with_interest(deps, env.clone(), &term_loan)?;
+        }
+    }
+
+    Ok(borrow_amount_with_interest)
+}
+
+pub fn get_overdue_term_loans(deps: Deps, env: Env) -> StdResult<Vec<TermLoan>> {
+    TERM_LOANS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, term_loan)| is_term_loan_overdue(&env, term_loan))
+        })
+        .map(|item| item.map(|(_, term_loan)| term_loan))
+        .collect()
+}
+
+pub fn get_term_loan_borrowers(deps: Deps) -> StdResult<Vec<String>> {
+    USER_TERM_LOANS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|key| key.map(|(borrower, _)| borrower))
+        .collect()
+}
+
+// term loan seen as a fixed-rate position for the market totals
+fn as_borrowing_info(term_loan: &TermLoan) -> UserBorrowingInfo {
+    UserBorrowingInfo {
+        denom: term_loan.denom.clone(),
+        borrowed_amount: term_loan.borrowed_amount,
+        average_interest_rate: term_loan.interest_rate,
+        timestamp: term_loan.timestamp,
+    }
+}
+
+/// Opens a new term loan at the current interest rate, returns the id of the loan
+pub fn execute_term_borrow(
+    deps: &mut DepsMut,
+    env: Env,
+    user: String,
+    denom: String,
+    amount: u128,
+    maturity: Timestamp,
+) -> Result<u64, ContractError> {
+    ensure!(maturity > env.block.time, ContractError::InvalidMaturity {});
+
+    let loan_id = NEXT_TERM_LOAN_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    NEXT_TERM_LOAN_ID.save(deps.storage, &(loan_id + 1))?;
+
+    let term_loan = TermLoan {
+        id: loan_id,
+        borrower: user.clone(),
+        denom: denom.clone(),
+        borrowed_amount: Uint128::from(amount),
+        interest_rate: get_interest_rate(deps.as_ref(), env.clone(), denom.clone())?,
+        timestamp: env.block.time,
+        maturity,
+    };
+
+    let empty_position = UserBorrowingInfo {
+        denom: denom.clone(),
+        borrowed_amount: Uint128::zero(),
+        average_interest_rate: Uint128::zero(),
+        timestamp: env.block.time,
+    };
+
+    update_total_term_borrow_data(
+        deps,
+        env,
+        denom,
+        &empty_position,
+        &as_borrowing_info(&term_loan),
+    )?;
+
+    TERM_LOANS.save(deps.storage, loan_id, &term_loan)?;
+    USER_TERM_LOANS.save(deps.storage, (user, loan_id), &true)?;
+
+    Ok(loan_id)
+}
+
+/// Decreases the term loan, returns the amount that was actually repaid
+pub fn execute_repay_term_loan(
+    deps: &mut DepsMut,
+    env: Env,
+    loan_id: u64,
+    amount: u128,
+) -> Result<u128, ContractError> {
+    let term_loan = load_term_loan(deps.as_ref(), loan_id)?;
+
+    let term_loan_amount_with_interest =
+        get_term_loan_amount_with_interest(deps.as_ref(), env.clone(), &term_loan)?.u128();
+
+    let repay_amount = amount.min(term_loan_amount_with_interest);
+
+    let new_term_loan = TermLoan {
+        borrowed_amount: Uint128::from(term_loan_amount_with_interest - repay_amount),
+        timestamp: env.block.time,
+        ..term_loan.clone()
+    };
+
+    update_total_term_borrow_data(
+        deps,
+        env.clone(),
+        term_loan.denom.clone(),
+        &as_borrowing_info(&term_loan),
+        &as_borrowing_info(&new_term_loan),
+    )?;
+
+    if new_term_loan.borrowed_amount.is_zero() {
+        TERM_LOANS.remove(deps.storage, loan_id);
+        USER_TERM_LOANS.remove(deps.storage, (term_loan.borrower.clone(), loan_id));
+    } else {
+        TERM_LOANS.save(deps.storage, loan_id, &new_term_loan)?;
+    }
+
+    release_isolated_debt(deps, term_loan.borrower, term_loan.denom, repay_amount)?;
+
+    Ok(repay_amount)
+}
+
+fn execute_repay_term_loan_with_funds(
+    mut deps: DepsMut,
+    env: Env,
+    payer: Addr,
+    loan_id: u64,
+    denom: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure!(
+        amount > Uint128::zero(),
+        ContractError::InvalidFunds {
+            msg: "Cannot repay 0 amount".to_string()
+        }
+    );
+
+    let term_loan = load_term_loan(deps.as_ref(), loan_id)?;
+
+    ensure!(
+        term_loan.denom == denom,
+        ContractError::InvalidFunds {
+            msg: "Funds must be in the token of the loan".to_string()
+        }
+    );
+
+    ensure_action_not_paused(deps.as_ref(), &denom, PauseAction::Repay)?;
+
+    execute_update_liquidity_index_data(&mut deps, env.clone(), denom.clone())?;
+
+    let repaid_amount = execute_repay_term_loan(&mut deps, env, loan_id, amount.u128())?;
+
+    let mut resp = Response::new();
+
+    let remaining_amount = amount.u128() - repaid_amount;
+    if remaining_amount > 0 {
+        resp = resp.add_message(generate_transfer_message(
+            get_token_asset_info(deps.as_ref(), denom.clone())?,
+            Uint128::from(remaining_amount),
+            payer.to_string(),
+        )?);
+    }
+
+    Ok(resp.add_attributes(vec![
+        attr("action", "repay_term_loan"),
+        attr("loan_id", loan_id.to_string()),
+        attr("borrower", term_loan.borrower),
+        attr("payer", payer.to_string()),
+        attr("repaid_amount", Uint128::from(repaid_amount)),
+    ]))
+}
+
+pub fn execute_repay_term_loan_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    loan_id: u64,
+) -> Result<Response, ContractError> {
+    ensure!(!info.funds.is_empty(), ContractError::CoinNotFound {});
+    let repay_token = one_coin(&info)?;
+
+    execute_repay_term_loan_with_funds(
+        deps,
+        env,
+        info.sender,
+        loan_id,
+        repay_token.denom,
+        repay_token.amount,
+    )
+}
+
+pub fn execute_cw20_repay_term_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+    loan_id: u64,
+) -> Result<Response, ContractError> {
+    // only cw20 tokens must be sent, not a coins
+    nonpayable(&info)?;
+
+    let denom = get_supported_tokens(deps.as_ref())?
+        .supported_tokens
+        .into_iter()
+        .find(|token| token.cw20_address == Some(info.sender.to_string()))
+        .ok_or(ContractError::TokenNotSupported {})?
+        .denom;
+
+    execute_repay_term_loan_with_funds(deps, env, sender, loan_id, denom, amount)
+}
@@ -0,0 +1,150 @@
+use crate::access_control::{ensure_admin, ensure_role};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, MarketStatus, QueuedAction, Role};
+use crate::state::{NEXT_ACTION_ID, QUEUED_ACTIONS, TIMELOCK_DELAY};
+use cosmwasm_std::{ensure, Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+
+const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60; // 30 days
+
+/// Messages that change risk parameters and have to wait for the timelock delay
+pub fn is_timelocked(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::SetReserveConfiguration { .. }
+            | ExecuteMsg::SetTokenInterestRateModelParams { .. }
+            | ExecuteMsg::SetRateStrategy { .. }
+            | ExecuteMsg::SetStableRateConfiguration { .. }
+            | ExecuteMsg::SetLiquidationConfiguration { .. }
+            | ExecuteMsg::SetEModeCategory { .. }
+            | ExecuteMsg::SetPriceSources { .. }
+            | ExecuteMsg::SetOracleConfiguration { .. }
+            | ExecuteMsg::SetIsolationConfiguration { .. }
+            | ExecuteMsg::AddPriceFeedIds { .. }
+            | ExecuteMsg::RemovePriceFeedId { .. }
+            | ExecuteMsg::AddMarkets { .. }
+            | ExecuteMsg::UpdatePythContract { .. }
+            | ExecuteMsg::SetTimelockDelay { .. }
+            | ExecuteMsg::SetMarketStatus {
+                status: MarketStatus::WindDown,
+                ..
+            }
+    )
+}
+
+// the same permission is checked again when the action is executed
+fn ensure_can_queue(deps: Deps, sender: &Addr, msg: &ExecuteMsg) -> Result<(), ContractError> {
+    match msg {
+        ExecuteMsg::SetReserveConfiguration { .. }
+        | ExecuteMsg::SetTokenInterestRateModelParams { .. }
+        | ExecuteMsg::SetRateStrategy { .. }
+        | ExecuteMsg::SetStableRateConfiguration { .. }
+        | ExecuteMsg::SetLiquidationConfiguration { .. }
+        | ExecuteMsg::SetEModeCategory { .. }
+        | ExecuteMsg::SetIsolationConfiguration { .. }
+        | ExecuteMsg::SetMarketStatus { .. } => ensure_role(deps, sender, Role::RiskManager),
+        ExecuteMsg::UpdatePythContract { .. }
+        | ExecuteMsg::SetPriceSources { .. }
+        | ExecuteMsg::SetOracleConfiguration { .. }
+        | ExecuteMsg::AddPriceFeedIds { .. }
+        | ExecuteMsg::RemovePriceFeedId { .. } => ensure_role(deps, sender, Role::OracleManager),
+        _ => ensure_admin(deps, sender),
+    }
+}
+
+pub fn get_timelock_delay(deps: Deps) -> StdResult<u64> {
+    Ok(TIMELOCK_DELAY.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn execute_set_timelock_delay(
+    deps: DepsMut,
+    info: MessageInfo,
+    delay: u64,
+) -> Result<Response, ContractError> {
+    ensure_admin(deps.as_ref(), &info.sender)?;
+
+    ensure!(
+        delay <= MAX_TIMELOCK_DELAY,
+        ContractError::InvalidTimelockDelay {}
+    );
+
+    TIMELOCK_DELAY.save(deps.storage, &delay)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set-timelock-delay")
+        .add_attribute("delay", delay.to_string()))
+}
+
+pub fn execute_queue_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    ensure_can_queue(deps.as_ref(), &info.sender, &msg)?;
+
+    let id = NEXT_ACTION_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_ACTION_ID.save(deps.storage, &(id + 1))?;
+
+    let eta = env
+        .block
+        .time
+        .plus_seconds(get_timelock_delay(deps.as_ref())?);
+
+    QUEUED_ACTIONS.save(
+        deps.storage,
+        id,
+        &QueuedAction {
+            id,
+            msg,
+            proposer: info.sender,
+            eta,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "queue-action")
+        .add_attribute("id", id.to_string())
+        .add_attribute("eta", eta.seconds().to_string()))
+}
+
+/// Removes the action from the queue if its eta has passed
+pub fn take_ready_action(deps: DepsMut, env: Env, id: u64) -> Result<QueuedAction, ContractError> {
+    let queued_action = QUEUED_ACTIONS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::QueuedActionNotFound {})?;
+
+    ensure!(
+        queued_action.eta <= env.block.time,
+        ContractError::QueuedActionNotReady {}
+    );
+
+    QUEUED_ACTIONS.remove(deps.storage, id);
+
+    Ok(queued_action)
+}
+
+pub fn execute_cancel_queued_action(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ensure_role(deps.as_ref(), &info.sender, Role::PauseGuardian)?;
+
+    ensure!(
+        QUEUED_ACTIONS.has(deps.storage, id),
+        ContractError::QueuedActionNotFound {}
+    );
+
+    QUEUED_ACTIONS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("method", "cancel-queued-action")
+        .add_attribute("id", id.to_string()))
+}
+
+pub fn get_pending_actions(deps: Deps) -> StdResult<Vec<QueuedAction>> {
+    QUEUED_ACTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, queued_action)| queued_action))
+        .collect()
+}
@@ -0,0 +1,2 @@
+mod test_ownership;
+mod test_roles;
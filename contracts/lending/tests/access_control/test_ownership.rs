@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_deposit_of_one_token_setup;
+    use cosmwasm_std::{Addr, BlockInfo};
+    use cw_multi_test::Executor;
+    use cw_utils::Expiration;
+    use lending::msg::{ExecuteMsg, PendingOwnership, QueryMsg, Role, RolesResponse};
+
+    #[test]
+    fn test_success_transfer_ownership() {
+        let (mut app, addr) = success_deposit_of_one_token_setup();
+
+        let expiry = Expiration::AtHeight(app.block_info().height + 100);
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "new_owner".to_string(),
+                expiry: Some(expiry),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // admin is unchanged until the transfer is accepted
+        let roles: RolesResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetRoles {})
+            .unwrap();
+
+        assert_eq!(roles.admin, Addr::unchecked("owner"));
+        assert_eq!(
+            roles.pending_ownership,
+            Some(PendingOwnership {
+                new_owner: Addr::unchecked("new_owner"),
+                expiry: Some(expiry),
+            })
+        );
+
+        app.execute_contract(
+            Addr::unchecked("new_owner"),
+            addr.clone(),
+            &ExecuteMsg::AcceptOwnership {},
+            &[],
+        )
+        .unwrap();
+
+        let roles: RolesResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetRoles {})
+            .unwrap();
+
+        assert_eq!(roles.admin, Addr::unchecked("new_owner"));
+        assert_eq!(roles.pending_ownership, None);
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::GrantRole {
+                    role: Role::Treasury,
+                    address: "treasury".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Allowed for Admin Only");
+    }
+
+    #[test]
+    fn test_fail_transfer_ownership() {
+        let (mut app, addr) = success_deposit_of_one_token_setup();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::TransferOwnership {
+                    new_owner: "user".to_string(),
+                    expiry: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Allowed for Admin Only");
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::TransferOwnership {
+                    new_owner: "new_owner".to_string(),
+                    expiry: Some(Expiration::AtHeight(app.block_info().height)),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Invalid Expiry");
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("new_owner"),
+                addr.clone(),
+                &ExecuteMsg::AcceptOwnership {},
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "No Pending Ownership Transfer"
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "new_owner".to_string(),
+                expiry: Some(Expiration::AtHeight(app.block_info().height + 10)),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::AcceptOwnership {},
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Pending Owner Only"
+        );
+
+        app.set_block(BlockInfo {
+            height: app.block_info().height + 10,
+            time: app.block_info().time,
+            chain_id: app.block_info().chain_id,
+        });
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("new_owner"),
+                addr.clone(),
+                &ExecuteMsg::AcceptOwnership {},
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Ownership Transfer Is Expired"
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::CancelOwnershipTransfer {},
+            &[],
+        )
+        .unwrap();
+
+        let admin: String = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetAdmin {})
+            .unwrap();
+
+        assert_eq!(admin, "owner");
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_deposit_of_one_token_setup;
+    use cosmwasm_std::Addr;
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, QueryMsg, Role, RoleMembers, RolesResponse};
+
+    const PERCENT_DECIMALS: u32 = 5;
+
+    #[test]
+    fn test_success_grant_and_revoke_roles() {
+        let (mut app, addr) = success_deposit_of_one_token_setup();
+
+        for (role, address) in [
+            (Role::RiskManager, "risk_manager"),
+            (Role::PauseGuardian, "guardian"),
+        ] {
+            app.execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::GrantRole {
+                    role,
+                    address: address.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let roles: RolesResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetRoles {})
+            .unwrap();
+
+        assert_eq!(
+            roles.roles,
+            vec![
+                RoleMembers {
+                    role: Role::RiskManager,
+                    members: vec![Addr::unchecked("risk_manager")],
+                },
+                RoleMembers {
+                    role: Role::OracleManager,
+                    members: vec![],
+                },
+                RoleMembers {
+                    role: Role::PauseGuardian,
+                    members: vec![Addr::unchecked("guardian")],
+                },
+                RoleMembers {
+                    role: Role::Treasury,
+                    members: vec![],
+                },
+            ]
+        );
+
+        app.execute_contract(
+            Addr::unchecked("risk_manager"),
+            addr.clone(),
+            &ExecuteMsg::SetReserveFactor {
+                denom: "eth".to_string(),
+                reserve_factor: 20 * 10u128.pow(PERCENT_DECIMALS),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // roles are not interchangeable
+        let error = app
+            .execute_contract(
+                Addr::unchecked("risk_manager"),
+                addr.clone(),
+                &ExecuteMsg::SetPause { value: true },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Pause Guardian Only"
+        );
+
+        app.execute_contract(
+            Addr::unchecked("guardian"),
+            addr.clone(),
+            &ExecuteMsg::SetPause { value: true },
+            &[],
+        )
+        .unwrap();
+
+        let is_paused: bool = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::IsPaused {})
+            .unwrap();
+
+        assert!(is_paused);
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::RevokeRole {
+                role: Role::RiskManager,
+                address: "risk_manager".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("risk_manager"),
+                addr.clone(),
+                &ExecuteMsg::SetReserveFactor {
+                    denom: "eth".to_string(),
+                    reserve_factor: 10 * 10u128.pow(PERCENT_DECIMALS),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Risk Manager Only"
+        );
+    }
+
+    #[test]
+    fn test_fail_grant_role() {
+        let (mut app, addr) = success_deposit_of_one_token_setup();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::GrantRole {
+                role: Role::RiskManager,
+                address: "risk_manager".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // only admin manages roles
+        let error = app
+            .execute_contract(
+                Addr::unchecked("risk_manager"),
+                addr.clone(),
+                &ExecuteMsg::GrantRole {
+                    role: Role::Treasury,
+                    address: "risk_manager".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Allowed for Admin Only");
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::SetPause { value: true },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Pause Guardian Only"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{BankSudo, BasicApp, Executor, SudoMsg};
+    use lending::msg::{BadDebtWriteOff, ExecuteMsg, QueryMsg};
+
+    const TOKENS_DECIMALS: u32 = 18;
+
+    const DEPOSIT_AMOUNT_ATOM: u128 = 1000 * 10u128.pow(TOKENS_DECIMALS);
+    const BORROW_AMOUNT_ETH: u128 = 3 * 10u128.pow(TOKENS_DECIMALS);
+    const INSURANCE_FUND_ETH: u128 = 10u128.pow(TOKENS_DECIMALS);
+
+    const PRICE_DECIMALS: u32 = 8;
+
+    // borrower with 1000 ATOM as collateral and 3 ETH of debt
+    fn borrower_setup(app: &mut BasicApp, addr: Addr) {
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: "borrower".to_string(),
+            amount: coins(DEPOSIT_AMOUNT_ATOM, "atom"),
+        }))
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("borrower"),
+            addr.clone(),
+            &ExecuteMsg::Deposit { on_behalf_of: None },
+            &coins(DEPOSIT_AMOUNT_ATOM, "atom"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("borrower"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "atom".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("borrower"),
+            addr,
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(BORROW_AMOUNT_ETH),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn get_mm_token_price(app: &BasicApp, addr: Addr) -> u128 {
+        let mm_token_price: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetMmTokenPrice {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        mm_token_price.u128()
+    }
+
+    fn get_insurance_fund(app: &BasicApp, addr: Addr) -> u128 {
+        let insurance_fund: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetInsuranceFund {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        insurance_fund.u128()
+    }
+
+    #[test]
+    fn test_success_write_off_bad_debt_on_liquidation() {
+        // user deposited 200 ETH and 300 ATOM as collateral and borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        borrower_setup(&mut app, addr.clone());
+
+        app.execute_contract(
+            Addr::unchecked("liquidator"),
+            addr.clone(),
+            &ExecuteMsg::FundInsuranceFund {},
+            &coins(INSURANCE_FUND_ETH, "eth"),
+        )
+        .unwrap();
+
+        assert_eq!(get_insurance_fund(&app, addr.clone()), INSURANCE_FUND_ETH);
+
+        // the insurance fund is not a liquidity of the market
+        let available_liquidity: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetAvailableLiquidityByToken {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let contract_balance = app
+            .wrap()
+            .query_balance(addr.clone(), "eth")
+            .unwrap()
+            .amount;
+
+        assert!(available_liquidity.u128() <= contract_balance.u128() - INSURANCE_FUND_ETH);
+
+        // ATOM drops to 1$, the collateral of 1000$ can't cover the debt of 6000$
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::UpdatePrice {
+                denom: Some("atom".to_string()),
+                price: Some(10u128.pow(PRICE_DECIMALS)),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let mm_token_price_before = get_mm_token_price(&app, addr.clone());
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("liquidator"),
+                addr.clone(),
+                &ExecuteMsg::Liquidation {
+                    user: "borrower".to_string(),
+                    collateral_denom: "atom".to_string(),
+                    receive_mm_token: false,
+                },
+                &coins(BORROW_AMOUNT_ETH, "eth"),
+            )
+            .unwrap();
+
+        assert!(resp
+            .events
+            .iter()
+            .any(|event| event.ty == "wasm-bad_debt_write_off"));
+
+        let borrower_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "borrower".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(borrower_borrow_amount_with_interest.is_zero());
+
+        let bad_debt_write_offs: Vec<BadDebtWriteOff> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetBadDebtWriteOffs {})
+            .unwrap();
+
+        assert_eq!(bad_debt_write_offs.len(), 1);
+
+        let write_off = &bad_debt_write_offs[0];
+
+        assert_eq!(write_off.user, "borrower".to_string());
+        assert_eq!(write_off.denom, "eth".to_string());
+        assert!(write_off.amount.u128() < BORROW_AMOUNT_ETH);
+        assert_eq!(
+            write_off.covered_by_insurance_fund.u128(),
+            INSURANCE_FUND_ETH
+        );
+        assert_eq!(
+            write_off.amount,
+            write_off.covered_by_insurance_fund
+                + write_off.covered_by_reserves
+                + write_off.socialized_amount
+        );
+        assert!(!write_off.socialized_amount.is_zero());
+
+        assert_eq!(get_insurance_fund(&app, addr.clone()), 0);
+
+        // depositors share the rest of the loss
+        assert!(get_mm_token_price(&app, addr.clone()) < mm_token_price_before);
+    }
+
+    #[test]
+    fn test_success_withdraw_insurance_fund() {
+        let (mut app, addr) = success_borrow_setup();
+
+        app.execute_contract(
+            Addr::unchecked("liquidator"),
+            addr.clone(),
+            &ExecuteMsg::FundInsuranceFund {},
+            &coins(INSURANCE_FUND_ETH, "eth"),
+        )
+        .unwrap();
+
+        let withdraw_insurance_fund = |app: &mut BasicApp, sender: &str, amount: u128| {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                addr.clone(),
+                &ExecuteMsg::WithdrawInsuranceFund {
+                    denom: "eth".to_string(),
+                    amount: Uint128::from(amount),
+                    recipient: "treasury".to_string(),
+                },
+                &[],
+            )
+            .err()
+            .map(|error| error.root_cause().to_string())
+        };
+
+        assert_eq!(
+            withdraw_insurance_fund(&mut app, "user", INSURANCE_FUND_ETH),
+            Some("Allowed for Admin or Treasury Only".to_string())
+        );
+
+        assert_eq!(
+            withdraw_insurance_fund(&mut app, "owner", INSURANCE_FUND_ETH + 1),
+            Some("Not Enough Insurance Fund To Withdraw".to_string())
+        );
+
+        assert_eq!(
+            withdraw_insurance_fund(&mut app, "owner", INSURANCE_FUND_ETH),
+            None
+        );
+
+        assert_eq!(get_insurance_fund(&app, addr.clone()), 0);
+        assert_eq!(
+            app.wrap()
+                .query_balance("treasury", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            INSURANCE_FUND_ETH
+        );
+    }
+
+    #[test]
+    fn test_fail_write_off_bad_debt() {
+        let (mut app, addr) = success_borrow_setup();
+
+        // user still has collateral to be liquidated
+        assert_eq!(
+            app.execute_contract(
+                Addr::unchecked("liquidator"),
+                addr.clone(),
+                &ExecuteMsg::WriteOffBadDebt {
+                    user: "user".to_string(),
+                },
+                &[],
+            )
+            .err()
+            .map(|error| error.root_cause().to_string()),
+            Some("User Has No Bad Debt".to_string())
+        );
+
+        // no debt to write off
+        assert_eq!(
+            app.execute_contract(
+                Addr::unchecked("liquidator"),
+                addr.clone(),
+                &ExecuteMsg::WriteOffBadDebt {
+                    user: "borrower".to_string(),
+                },
+                &[],
+            )
+            .err()
+            .map(|error| error.root_cause().to_string()),
+            Some("User Has No Bad Debt".to_string())
+        );
+    }
+}
@@ -2,5 +2,6 @@ mod test_fail_borrow_cw20;
 mod test_fail_borrow_native;
 mod test_success_borrow_cw20_by_parts;
 mod test_success_borrow_one_token_two_times_in_a_row;
+mod test_success_borrow_to_recipient;
 mod test_success_deposit_one_token_borrow_another;
 mod test_sucess_borrow_one_token;
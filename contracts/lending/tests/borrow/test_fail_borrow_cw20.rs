@@ -1,17 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::utils::success_native_and_cw20_setup;
     use cosmwasm_std::{coins, to_json_binary, Addr, Uint128};
-    use cw20::{BalanceResponse, Cw20QueryMsg, Cw20ReceiveMsg};
-    use cw20_base::msg::{
-        ExecuteMsg as ExecuteMsgCW20, InstantiateMsg as InstantiateMsgCW20,
-        QueryMsg as QueryMsgCW20,
-    };
+    use cw20::{BalanceResponse, Cw20QueryMsg};
+    use cw20_base::msg::ExecuteMsg as ExecuteMsgCW20;
     use cw_multi_test::Executor;
     use lending::msg::{
         Cw20HookMsg, ExecuteMsg, GetBalanceResponse, GetSupportedTokensResponse, QueryMsg,
-        UserDataByToken,
     };
 
     #[test]
@@ -138,6 +133,7 @@ mod tests {
 
         let hook = Cw20HookMsg::Deposit {
             denom: "ilend-denom".to_string(),
+            on_behalf_of: None,
         };
 
         let send_msg = ExecuteMsgCW20::Send {
@@ -186,7 +182,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("cw20-user"),
             lending_addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(DEPOSIT_AMOUNT_ETH, "eth"),
         )
         .unwrap();
@@ -274,6 +270,9 @@ mod tests {
                 &ExecuteMsg::Borrow {
                     denom: "ilend-denom".to_string(),
                     amount: Uint128::from(available_to_borrow_cw20_before_failed_borrow.u128() * 2),
+                    rate_mode: None,
+                    recipient: None,
+                    on_behalf_of: None,
                 },
                 &[],
             )
@@ -21,6 +21,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "usdt".to_string(),
                 amount: Uint128::from(BORROW_AMOUNT_UNSUPPORTED_TOKEN),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -43,6 +46,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "eth".to_string(),
                 amount: Uint128::from(BORROW_AMOUNT_ETH), // 300 ETH
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -36,6 +36,7 @@ mod tests {
             &ExecuteMsg::Redeem {
                 denom: "atom".to_string(),
                 amount: Uint128::from(DEPOSIT_OF_SECOND_TOKEN),
+                recipient: None,
             },
             &[],
         )
@@ -69,6 +70,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "atom".to_string(),
                 amount: Uint128::from(BORROW_SECOND_TOKEN_FIRST_PART),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -102,6 +106,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "atom".to_string(),
                 amount: Uint128::from(BORROW_SECOND_TOKEN_SECOND_PART),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
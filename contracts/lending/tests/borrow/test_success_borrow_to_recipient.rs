@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, QueryMsg};
+
+    #[test]
+    fn test_success_borrow_to_recipient() {
+        const BORROW_AMOUNT_ETH: u128 = 10 * 10u128.pow(18);
+
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let user_balance_before_borrow = app.wrap().query_balance("user", "eth").unwrap().amount;
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(BORROW_AMOUNT_ETH),
+                rate_mode: None,
+                recipient: Some("recipient".to_string()),
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the debt is recorded on the user, the tokens are sent to the recipient
+        let user_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(user_borrow_amount_with_interest.u128() >= 60 * 10u128.pow(18));
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("recipient", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            BORROW_AMOUNT_ETH
+        );
+        assert_eq!(
+            app.wrap().query_balance("user", "eth").unwrap().amount,
+            user_balance_before_borrow
+        );
+    }
+}
@@ -206,7 +206,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(DEPOSIT_AMOUNT_ETH, "eth"),
         )
         .unwrap();
@@ -294,6 +294,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "atom".to_string(),
                 amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
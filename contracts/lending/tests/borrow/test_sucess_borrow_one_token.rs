@@ -46,6 +46,7 @@ mod tests {
             &ExecuteMsg::Redeem {
                 denom: "atom".to_string(),
                 amount: Uint128::from(DEPOSIT_OF_SECOND_TOKEN),
+                recipient: None,
             },
             &[],
         )
@@ -79,6 +80,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "atom".to_string(),
                 amount: Uint128::from(BORROW_SECOND_TOKEN),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
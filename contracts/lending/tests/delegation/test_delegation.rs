@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{BorrowAllowance, ExecuteMsg, QueryMsg};
+
+    const TOKENS_DECIMALS: u32 = 18;
+
+    const BORROW_AMOUNT_ETH: u128 = 50 * 10u128.pow(TOKENS_DECIMALS);
+    const ALLOWANCE_ETH: u128 = 10 * 10u128.pow(TOKENS_DECIMALS);
+
+    const DAY_IN_SECONDS: u64 = 24 * 60 * 60;
+
+    fn approve_delegation(app: &mut BasicApp, addr: Addr, expiry: Timestamp) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::ApproveDelegation {
+                delegatee: "delegatee".to_string(),
+                denom: "eth".to_string(),
+                amount: Uint128::from(ALLOWANCE_ETH),
+                expiry,
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn delegated_borrow(app: &mut BasicApp, addr: Addr, amount: u128) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("delegatee"),
+            addr,
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(amount),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: Some("user".to_string()),
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn get_borrow_allowance(app: &BasicApp, addr: Addr) -> BorrowAllowance {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetBorrowAllowance {
+                    delegator: "user".to_string(),
+                    delegatee: "delegatee".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    fn skip_time(app: &mut BasicApp, seconds: u64) {
+        app.set_block(BlockInfo {
+            height: app.block_info().height + 1,
+            time: app.block_info().time.plus_seconds(seconds),
+            chain_id: app.block_info().chain_id,
+        });
+    }
+
+    #[test]
+    fn test_success_delegated_borrow() {
+        // user deposited 200 ETH and 300 ATOM as collateral and borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let expiry = app.block_info().time.plus_seconds(DAY_IN_SECONDS);
+
+        assert_eq!(approve_delegation(&mut app, addr.clone(), expiry), None);
+
+        assert_eq!(
+            get_borrow_allowance(&app, addr.clone()),
+            BorrowAllowance {
+                amount: Uint128::from(ALLOWANCE_ETH),
+                expiry,
+            }
+        );
+
+        assert_eq!(
+            delegated_borrow(&mut app, addr.clone(), ALLOWANCE_ETH / 2),
+            None
+        );
+
+        // the delegatee receives the tokens, the debt is recorded on the delegator
+        assert_eq!(
+            app.wrap()
+                .query_balance("delegatee", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            ALLOWANCE_ETH / 2
+        );
+
+        let user_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            user_borrow_amount_with_interest.u128(),
+            BORROW_AMOUNT_ETH + ALLOWANCE_ETH / 2
+        );
+
+        let delegatee_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "delegatee".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(delegatee_borrow_amount_with_interest.is_zero());
+
+        assert_eq!(
+            get_borrow_allowance(&app, addr.clone()).amount.u128(),
+            ALLOWANCE_ETH / 2
+        );
+
+        assert_eq!(
+            delegated_borrow(&mut app, addr.clone(), ALLOWANCE_ETH),
+            Some("Borrow Allowance Exceeded".to_string())
+        );
+
+        // the allowance can't be used after the expiry
+        skip_time(&mut app, 2 * DAY_IN_SECONDS);
+
+        assert!(get_borrow_allowance(&app, addr.clone()).amount.is_zero());
+    }
+
+    #[test]
+    fn test_fail_delegated_borrow() {
+        let (mut app, addr) = success_borrow_setup();
+
+        let now = app.block_info().time;
+
+        assert_eq!(
+            delegated_borrow(&mut app, addr.clone(), ALLOWANCE_ETH),
+            Some("Borrow Allowance Exceeded".to_string())
+        );
+
+        assert_eq!(
+            approve_delegation(&mut app, addr.clone(), now),
+            Some("Invalid Delegation".to_string())
+        );
+
+        approve_delegation(&mut app, addr.clone(), now.plus_seconds(DAY_IN_SECONDS));
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::RevokeDelegation {
+                delegatee: "delegatee".to_string(),
+                denom: "eth".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            delegated_borrow(&mut app, addr.clone(), ALLOWANCE_ETH),
+            Some("Borrow Allowance Exceeded".to_string())
+        );
+    }
+}
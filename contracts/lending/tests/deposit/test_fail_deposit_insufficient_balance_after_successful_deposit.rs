@@ -115,7 +115,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(INIT_USER_BALANCE / 2, "eth"),
         )
         .unwrap();
@@ -137,7 +137,7 @@ mod tests {
             .execute_contract(
                 Addr::unchecked("user"),
                 addr.clone(),
-                &ExecuteMsg::Deposit {},
+                &ExecuteMsg::Deposit { on_behalf_of: None },
                 &coins(FIRST_DEPOSIT_AMOUNT, "eth"),
             )
             .is_err());
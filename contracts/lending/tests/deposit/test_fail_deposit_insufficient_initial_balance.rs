@@ -117,7 +117,7 @@ mod tests {
             .execute_contract(
                 Addr::unchecked("user"),
                 addr.clone(),
-                &ExecuteMsg::Deposit {},
+                &ExecuteMsg::Deposit { on_behalf_of: None },
                 &coins(FIRST_DEPOSIT_AMOUNT, "eth"),
             )
             .is_err());
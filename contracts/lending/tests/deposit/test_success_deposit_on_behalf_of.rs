@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, GetBalanceResponse, QueryMsg};
+
+    #[test]
+    fn test_success_deposit_on_behalf_of() {
+        const DEPOSIT_AMOUNT_ETH: u128 = 100 * 10u128.pow(18);
+
+        // user deposited 200 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        // liquidator deposits for the user and holds no position itself
+        app.execute_contract(
+            Addr::unchecked("liquidator"),
+            addr.clone(),
+            &ExecuteMsg::Deposit {
+                on_behalf_of: Some("user".to_string()),
+            },
+            &coins(DEPOSIT_AMOUNT_ETH, "eth"),
+        )
+        .unwrap();
+
+        let user_deposited_balance: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(user_deposited_balance.balance.u128() >= 300 * 10u128.pow(18) - 1);
+
+        let liquidator_deposited_balance: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "liquidator".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(liquidator_deposited_balance.balance.u128(), 0);
+    }
+}
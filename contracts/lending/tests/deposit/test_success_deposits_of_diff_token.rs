@@ -157,7 +157,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(DEPOSIT_OF_FIRST_TOKEN, "eth"),
         )
         .unwrap();
@@ -199,7 +199,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(DEPOSIT_OF_SECOND_TOKEN, "atom"),
         )
         .unwrap();
@@ -190,7 +190,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(FIRST_DEPOSIT_AMOUNT, "eth"),
         )
         .unwrap();
@@ -198,7 +198,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("owner"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(FIRST_DEPOSIT_AMOUNT * 15 / 10, "eth"),
         )
         .unwrap();
@@ -259,7 +259,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(SECOND_DEPOSIT_AMOUNT, "eth"),
         )
         .unwrap();
@@ -270,6 +270,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "eth".to_string(),
                 amount: Uint128::from(SECOND_DEPOSIT_AMOUNT / 2),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -281,7 +284,7 @@ mod tests {
             chain_id: "custom_chain_id".to_string(),
         });
 
-        let total_borrow_data: TotalBorrowData = app
+        let _total_borrow_data: TotalBorrowData = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
@@ -291,7 +294,7 @@ mod tests {
             )
             .unwrap();
 
-        let reserves_by_token: Uint128 = app
+        let _reserves_by_token: Uint128 = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
@@ -301,7 +304,7 @@ mod tests {
             )
             .unwrap();
 
-        let liquidity_rate: Uint128 = app
+        let _liquidity_rate: Uint128 = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
@@ -311,7 +314,7 @@ mod tests {
             )
             .unwrap();
 
-        let borrow_amount_with_interest: Uint128 = app
+        let _borrow_amount_with_interest: Uint128 = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
@@ -333,7 +336,7 @@ mod tests {
             )
             .unwrap();
 
-        let price: Uint128 = app
+        let _price: Uint128 = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
@@ -25,7 +25,7 @@ mod tests {
             )
             .unwrap();
 
-        assert_eq!(user_eth_deposit_as_collateral, false);
+        assert!(!user_eth_deposit_as_collateral);
 
         let user_collateral_usd: Uint128 = app
             .wrap()
@@ -64,7 +64,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(DEPOSIT_AMOUNT_ATOM, "atom"),
         )
         .unwrap();
@@ -80,7 +80,7 @@ mod tests {
             )
             .unwrap();
 
-        assert_eq!(user_atom_deposit_as_collateral, false);
+        assert!(!user_atom_deposit_as_collateral);
 
         let user_collateral_usd: Uint128 = app
             .wrap()
@@ -43,7 +43,7 @@ mod tests {
             )
             .unwrap();
 
-        assert_eq!(user_eth_deposit_as_collateral, true);
-        assert_eq!(user_atom_deposit_as_collateral, false);
+        assert!(user_eth_deposit_as_collateral);
+        assert!(!user_atom_deposit_as_collateral);
     }
 }
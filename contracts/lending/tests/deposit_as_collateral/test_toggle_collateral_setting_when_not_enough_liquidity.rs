@@ -48,7 +48,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("owner"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(OWNER_DEPOSIT_AMOUNT_ATOM, "atom"), // 500_000 ATOM
         )
         .unwrap();
@@ -59,6 +59,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "eth".to_string(),
                 amount: Uint128::from(OWNER_BORROW_AMOUNT_ETH), // 1100 ETH
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -102,7 +105,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(USER_DEPOSIT_AMOUNT_ATOM, "atom"), // 200_000 ATOM
         )
         .unwrap();
@@ -113,6 +116,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "eth".to_string(),
                 amount: Uint128::from(USER_BORROW_AMOUNT_ETH), // 100 ETH
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
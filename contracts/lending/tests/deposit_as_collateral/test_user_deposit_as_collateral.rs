@@ -29,7 +29,7 @@ mod tests {
             )
             .unwrap();
 
-        assert_eq!(user_eth_deposit_as_collateral, false);
-        assert_eq!(user_atom_deposit_as_collateral, false);
+        assert!(!user_eth_deposit_as_collateral);
+        assert!(!user_atom_deposit_as_collateral);
     }
 }
@@ -0,0 +1,371 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{band_setup, set_band_price, success_deposit_of_diff_token_with_prices};
+    use cosmwasm_std::{Addr, Uint128, Uint256};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{ExecuteMsg, PriceSource, QueryMsg, UserEMode};
+
+    const TOKENS_DECIMALS: u32 = 18;
+    const PERCENT_DECIMALS: u32 = 5;
+    const USD_DECIMALS: u32 = 8;
+    const E_MODE_LTV: u128 = 95 * 10u128.pow(PERCENT_DECIMALS); // 95%
+    const E_MODE_LIQUIDATION_THRESHOLD: u128 = 97 * 10u128.pow(PERCENT_DECIMALS); // 97%
+
+    fn set_e_mode_category(
+        app: &mut BasicApp,
+        addr: Addr,
+        id: u8,
+        price_source: Option<PriceSource>,
+        denoms: Vec<&str>,
+    ) {
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::SetEModeCategory {
+                id,
+                label: format!("category {}", id),
+                loan_to_value_ratio: E_MODE_LTV,
+                liquidation_threshold: E_MODE_LIQUIDATION_THRESHOLD,
+                price_source,
+                denoms: denoms.into_iter().map(String::from).collect(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn query_usd(app: &BasicApp, addr: Addr, msg: &QueryMsg) -> u128 {
+        app.wrap()
+            .query_wasm_smart::<Uint128>(addr, msg)
+            .unwrap()
+            .u128()
+    }
+
+    #[test]
+    fn test_success_e_mode() {
+        const BORROW_AMOUNT_ETH: u128 = 13 * 10u128.pow(TOKENS_DECIMALS - 1); // 1.3 ETH
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_e_mode_category(&mut app, addr.clone(), 1, None, vec!["eth", "atom"]);
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "atom".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let max_allowed_borrow_msg = QueryMsg::GetUserMaxAllowedBorrowAmountUsd {
+            address: "user".to_string(),
+        };
+
+        // 300 ATOM * 10$ * 75%
+        assert_eq!(
+            query_usd(&app, addr.clone(), &max_allowed_borrow_msg),
+            2250 * 10u128.pow(USD_DECIMALS)
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::SetUserEMode {
+                category_id: Some(1),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // 300 ATOM * 10$ * 95%
+        assert_eq!(
+            query_usd(&app, addr.clone(), &max_allowed_borrow_msg),
+            2850 * 10u128.pow(USD_DECIMALS)
+        );
+        assert_eq!(
+            query_usd(
+                &app,
+                addr.clone(),
+                &QueryMsg::GetUserLiquidationThreshold {
+                    address: "user".to_string(),
+                }
+            ),
+            E_MODE_LIQUIDATION_THRESHOLD
+        );
+
+        // 1.3 ETH * 2000$ is above the regular LTV but within the e-mode one
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(BORROW_AMOUNT_ETH),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let user_e_mode: UserEMode = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserEMode {
+                    address: "user".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(user_e_mode.category_id, Some(1));
+        assert!(user_e_mode.is_active);
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::SetUserEMode { category_id: None },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Leaving E-Mode Makes The Position Undercollateralized"
+        );
+    }
+
+    #[test]
+    fn test_success_e_mode_shared_oracle() {
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_e_mode_category(
+            &mut app,
+            addr.clone(),
+            1,
+            Some(PriceSource::Fixed {
+                price: Uint128::from(12 * 10u128.pow(USD_DECIMALS)), // 12$
+            }),
+            vec!["atom"],
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "atom".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::SetUserEMode {
+                category_id: Some(1),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // 300 ATOM * 12$
+        assert_eq!(
+            query_usd(
+                &app,
+                addr.clone(),
+                &QueryMsg::GetUserCollateralUsd {
+                    address: "user".to_string(),
+                }
+            ),
+            3600 * 10u128.pow(USD_DECIMALS)
+        );
+    }
+
+    #[test]
+    fn test_fail_e_mode_stale_shared_oracle() {
+        const BAND_RATE_DECIMALS: u32 = 18;
+        const BORROW_AMOUNT_ATOM: u128 = 10u128.pow(TOKENS_DECIMALS - 1); // 0.1 ATOM
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        let band_addr = band_setup(&mut app);
+
+        // 12$
+        set_band_price(
+            &mut app,
+            band_addr.clone(),
+            "ATOM",
+            Uint256::from(12u128 * 10u128.pow(BAND_RATE_DECIMALS)),
+        );
+
+        set_e_mode_category(
+            &mut app,
+            addr.clone(),
+            1,
+            Some(PriceSource::Band {
+                contract_addr: band_addr.to_string(),
+                base_symbol: "ATOM".to_string(),
+            }),
+            vec!["atom"],
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "atom".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::SetUserEMode {
+                category_id: Some(1),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let borrow_msg = ExecuteMsg::Borrow {
+            denom: "atom".to_string(),
+            amount: Uint128::from(BORROW_AMOUNT_ATOM),
+            rate_mode: None,
+            recipient: None,
+            on_behalf_of: None,
+        };
+
+        app.execute_contract(Addr::unchecked("user"), addr.clone(), &borrow_msg, &[])
+            .unwrap();
+
+        // the category oracle isn't updated while the market prices stay fresh
+        app.update_block(|block| block.time = block.time.plus_seconds(120));
+
+        let error = app
+            .wrap()
+            .query_wasm_smart::<Uint128>(
+                addr.clone(),
+                &QueryMsg::GetUserCollateralUsd {
+                    address: "user".to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(error.to_string().contains("Stale Price: atom"));
+
+        let error = app
+            .execute_contract(Addr::unchecked("user"), addr.clone(), &borrow_msg, &[])
+            .unwrap_err();
+
+        assert!(error.root_cause().to_string().contains("Stale Price: atom"));
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Redeem {
+                    denom: "atom".to_string(),
+                    amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                    recipient: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert!(error.root_cause().to_string().contains("Stale Price: atom"));
+    }
+
+    #[test]
+    fn test_fail_e_mode() {
+        const BORROW_AMOUNT_ETH: u128 = 10u128.pow(TOKENS_DECIMALS - 1); // 0.1 ETH
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::SetEModeCategory {
+                    id: 1,
+                    label: "atom".to_string(),
+                    loan_to_value_ratio: E_MODE_LIQUIDATION_THRESHOLD,
+                    liquidation_threshold: E_MODE_LTV,
+                    price_source: None,
+                    denoms: vec!["atom".to_string()],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Invalid E-Mode Category");
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::SetUserEMode {
+                    category_id: Some(1),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "E-Mode Category Not Found");
+
+        set_e_mode_category(&mut app, addr.clone(), 1, None, vec!["atom"]);
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "atom".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::SetUserEMode {
+                category_id: Some(1),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Borrow {
+                    denom: "eth".to_string(),
+                    amount: Uint128::from(BORROW_AMOUNT_ETH),
+                    rate_mode: None,
+                    recipient: None,
+                    on_behalf_of: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Asset Is Not In The User E-Mode Category"
+        );
+    }
+}
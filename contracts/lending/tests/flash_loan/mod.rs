@@ -0,0 +1 @@
+mod test_flash_loan;
@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{
+        flash_loan_receiver_setup, success_deposit_of_diff_token_with_prices, MockFlashLoanAction,
+    };
+    use cosmwasm_std::{coin, coins, to_json_binary, Addr, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{ExecuteMsg, GetBalanceResponse, QueryMsg};
+
+    const TOKENS_DECIMALS: u32 = 18;
+    const FLASH_LOAN_AMOUNT_ETH: u128 = 100 * 10u128.pow(TOKENS_DECIMALS); // 100 ETH
+
+    fn flash_loan(
+        app: &mut BasicApp,
+        addr: Addr,
+        receiver_addr: Addr,
+        amount: u128,
+        action: MockFlashLoanAction,
+    ) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::FlashLoan {
+                assets: vec![coin(amount, "eth")],
+                recipient: receiver_addr.to_string(),
+                msg: to_json_binary(&action).unwrap(),
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    #[test]
+    fn test_success_flash_loan() {
+        const FLASH_LOAN_FEE: u128 = 9000; // 0.09%
+        const FEE_AMOUNT_ETH: u128 = 9 * 10u128.pow(TOKENS_DECIMALS - 2); // 0.09 ETH
+        const DEPOSIT_AMOUNT_ETH: u128 = 200 * 10u128.pow(TOKENS_DECIMALS); // 200 ETH
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        let receiver_addr = flash_loan_receiver_setup(&mut app);
+
+        // receiver pays the fee from its own funds
+        app.send_tokens(
+            Addr::unchecked("user"),
+            receiver_addr.clone(),
+            &coins(10u128.pow(TOKENS_DECIMALS), "eth"),
+        )
+        .unwrap();
+
+        let flash_loan_fee: u128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetFlashLoanFee {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(flash_loan_fee, FLASH_LOAN_FEE);
+
+        assert_eq!(
+            flash_loan(
+                &mut app,
+                addr.clone(),
+                receiver_addr.clone(),
+                FLASH_LOAN_AMOUNT_ETH,
+                MockFlashLoanAction::RepayWithFee {},
+            ),
+            None
+        );
+
+        assert_eq!(
+            app.wrap()
+                .query_balance(receiver_addr, "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            10u128.pow(TOKENS_DECIMALS) - FEE_AMOUNT_ETH
+        );
+
+        // the whole fee goes to the only depositor, up to the liquidity index rounding
+        let user_deposit: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(
+            user_deposit
+                .balance
+                .u128()
+                .abs_diff(DEPOSIT_AMOUNT_ETH + FEE_AMOUNT_ETH)
+                < 10u128.pow(TOKENS_DECIMALS - 8)
+        );
+
+        // fee can be changed by the admin
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetFlashLoanFee {
+                denom: "eth".to_string(),
+                flash_loan_fee: 0,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let user_balance_before = app
+            .wrap()
+            .query_balance("user", "eth")
+            .unwrap()
+            .amount
+            .u128();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Redeem {
+                denom: "eth".to_string(),
+                amount: Uint128::from(DEPOSIT_AMOUNT_ETH + FEE_AMOUNT_ETH / 2),
+                recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("user", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            user_balance_before + DEPOSIT_AMOUNT_ETH + FEE_AMOUNT_ETH / 2
+        );
+    }
+
+    #[test]
+    fn test_fail_flash_loan() {
+        const CONTRACT_BALANCE_ETH: u128 = 1200 * 10u128.pow(TOKENS_DECIMALS); // 1200 ETH
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        let receiver_addr = flash_loan_receiver_setup(&mut app);
+
+        assert_eq!(
+            flash_loan(
+                &mut app,
+                addr.clone(),
+                receiver_addr.clone(),
+                FLASH_LOAN_AMOUNT_ETH,
+                MockFlashLoanAction::RepayPrincipal {},
+            )
+            .unwrap(),
+            "Flash Loan Is Not Repaid: eth"
+        );
+
+        assert_eq!(
+            flash_loan(
+                &mut app,
+                addr.clone(),
+                receiver_addr.clone(),
+                FLASH_LOAN_AMOUNT_ETH,
+                MockFlashLoanAction::Deposit {},
+            )
+            .unwrap(),
+            "Flash Loan Is In Progress"
+        );
+
+        assert_eq!(
+            flash_loan(
+                &mut app,
+                addr.clone(),
+                receiver_addr.clone(),
+                CONTRACT_BALANCE_ETH + 1,
+                MockFlashLoanAction::RepayWithFee {},
+            )
+            .unwrap(),
+            "Not Enough Liquidity To Borrow"
+        );
+
+        // failed flash loans are reverted entirely
+        assert_eq!(
+            app.wrap().query_balance(addr, "eth").unwrap().amount.u128(),
+            CONTRACT_BALANCE_ETH
+        );
+    }
+}
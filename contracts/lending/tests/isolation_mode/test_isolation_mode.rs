@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_deposit_of_diff_token_with_prices;
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{ExecuteMsg, IsolationConfiguration, QueryMsg};
+
+    const TOKENS_DECIMALS: u32 = 18;
+    const USD_DECIMALS: u32 = 8;
+    const DEBT_CEILING_ATOM: u128 = 1000 * 10u128.pow(USD_DECIMALS); // 1000$
+
+    fn set_isolation_configuration(
+        app: &mut BasicApp,
+        addr: Addr,
+        denom: &str,
+        is_isolated: bool,
+        debt_ceiling: u128,
+        borrowable_in_isolation: bool,
+    ) {
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::SetIsolationConfiguration {
+                denom: denom.to_string(),
+                is_isolated,
+                debt_ceiling: Uint128::from(debt_ceiling),
+                borrowable_in_isolation,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // returns the error message if toggle failed
+    fn toggle_collateral(app: &mut BasicApp, addr: Addr, denom: &str) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: denom.to_string(),
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn get_isolated_debt(app: &BasicApp, addr: Addr, denom: &str) -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetIsolatedDebt {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_success_isolation_mode() {
+        const BORROW_AMOUNT_ETH: u128 = 4 * 10u128.pow(TOKENS_DECIMALS - 1); // 0.4 ETH
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_isolation_configuration(
+            &mut app,
+            addr.clone(),
+            "atom",
+            true,
+            DEBT_CEILING_ATOM,
+            false,
+        );
+        set_isolation_configuration(&mut app, addr.clone(), "eth", false, 0, true);
+
+        let isolation_configuration: IsolationConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetIsolationConfiguration {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(isolation_configuration.is_isolated);
+        assert_eq!(
+            isolation_configuration.debt_ceiling.u128(),
+            DEBT_CEILING_ATOM
+        );
+
+        assert_eq!(toggle_collateral(&mut app, addr.clone(), "atom"), None);
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(BORROW_AMOUNT_ETH),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // 0.4 ETH * 2000$
+        assert_eq!(
+            get_isolated_debt(&app, addr.clone(), "atom").u128(),
+            800 * 10u128.pow(USD_DECIMALS)
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::UpdatePrice {
+                denom: Some("eth".to_string()),
+                price: Some(2500 * 10u128.pow(USD_DECIMALS)),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // debt is tracked in ETH and valued with the current price: 0.4 ETH * 2500$
+        assert_eq!(
+            get_isolated_debt(&app, addr.clone(), "atom").u128(),
+            DEBT_CEILING_ATOM
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Repay { on_behalf_of: None },
+            &coins(BORROW_AMOUNT_ETH, "eth"),
+        )
+        .unwrap();
+
+        assert_eq!(get_isolated_debt(&app, addr.clone(), "atom").u128(), 0);
+
+        // leaving isolation mode allows to use other collateral
+        assert_eq!(toggle_collateral(&mut app, addr.clone(), "atom"), None);
+        assert_eq!(toggle_collateral(&mut app, addr.clone(), "eth"), None);
+    }
+
+    #[test]
+    fn test_fail_isolation_mode() {
+        const BORROW_AMOUNT_ATOM: u128 = 10 * 10u128.pow(TOKENS_DECIMALS); // 10 ATOM
+        const BORROW_AMOUNT_ETH: u128 = 6 * 10u128.pow(TOKENS_DECIMALS - 1); // 0.6 ETH
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_isolation_configuration(
+            &mut app,
+            addr.clone(),
+            "atom",
+            true,
+            DEBT_CEILING_ATOM,
+            false,
+        );
+
+        assert_eq!(toggle_collateral(&mut app, addr.clone(), "eth"), None);
+
+        assert_eq!(
+            toggle_collateral(&mut app, addr.clone(), "atom").unwrap(),
+            "Isolated Collateral Cannot Be Combined With Other Collateral"
+        );
+
+        assert_eq!(toggle_collateral(&mut app, addr.clone(), "eth"), None);
+        assert_eq!(toggle_collateral(&mut app, addr.clone(), "atom"), None);
+
+        assert_eq!(
+            toggle_collateral(&mut app, addr.clone(), "eth").unwrap(),
+            "Isolated Collateral Cannot Be Combined With Other Collateral"
+        );
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Borrow {
+                    denom: "atom".to_string(),
+                    amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                    rate_mode: None,
+                    recipient: None,
+                    on_behalf_of: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Asset Is Not Borrowable In Isolation Mode"
+        );
+
+        set_isolation_configuration(&mut app, addr.clone(), "eth", false, 0, true);
+
+        // 0.6 ETH * 2000$ is above 1000$ debt ceiling
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Borrow {
+                    denom: "eth".to_string(),
+                    amount: Uint128::from(BORROW_AMOUNT_ETH),
+                    rate_mode: None,
+                    recipient: None,
+                    on_behalf_of: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Isolation Mode Debt Ceiling Exceeded"
+        );
+    }
+}
@@ -1,2 +1,3 @@
 mod test_fail_liquidation;
 mod test_success_liquidation;
+mod test_success_liquidation_close_factor;
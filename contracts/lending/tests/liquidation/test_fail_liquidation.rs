@@ -136,6 +136,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "eth".to_string(),
                 amount: Uint128::from(BORROW_AMOUNT_ETH),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -268,7 +271,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("liquidator"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
+            &ExecuteMsg::Deposit { on_behalf_of: None },
             &coins(LIQUIDATOR_DEPOSIT_AMOUNT_ETH, "eth"),
         )
         .unwrap();
@@ -306,6 +309,8 @@ mod tests {
             addr.clone(),
             &ExecuteMsg::Liquidation {
                 user: "user".to_string(),
+                collateral_denom: "eth".to_string(),
+                receive_mm_token: true,
             },
             &[],
         )
@@ -10,7 +10,9 @@ mod tests {
     fn test_success_liquidation() {
         const TOKENS_DECIMALS: u32 = 18;
         const BORROW_AMOUNT_ETH: u128 = 121 * 10u128.pow(TOKENS_DECIMALS); // 121 ETH
-        const LIQUIDATOR_DEPOSIT_AMOUNT_ETH: u128 = 10_000 * 10u128.pow(TOKENS_DECIMALS); // 10_000 ETH
+        const LIQUIDATION_AMOUNT_ETH: u128 = 50 * 10u128.pow(TOKENS_DECIMALS); // 50 ETH
+        const USER_DEPOSIT_AFTER_LIQUIDATION_ETH: u128 = 150699701323908896000; // 150.699701323908896000 ETH
+        const LIQUIDATOR_DEPOSIT_AFTER_LIQUIDATION_ETH: u128 = 52499999999999999999; // 52.499999999999999999 ETH
         const YEAR_IN_SECONDS: u64 = 31536000;
 
         // contract reserves: 1000 ETH
@@ -135,6 +137,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "eth".to_string(),
                 amount: Uint128::from(BORROW_AMOUNT_ETH),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -267,74 +272,61 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("liquidator"),
             addr.clone(),
-            &ExecuteMsg::Deposit {},
-            &coins(LIQUIDATOR_DEPOSIT_AMOUNT_ETH, "eth"),
+            &ExecuteMsg::Liquidation {
+                user: "user".to_string(),
+                collateral_denom: "eth".to_string(),
+                receive_mm_token: true,
+            },
+            &coins(LIQUIDATION_AMOUNT_ETH, "eth"),
         )
         .unwrap();
 
-        let liquidator_deposit_amount_eth: GetBalanceResponse = app
+        let user_borrow_amount_eth: Uint128 = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
-                &QueryMsg::GetDeposit {
-                    address: "liquidator".to_string(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
                     denom: "eth".to_string(),
                 },
             )
             .unwrap();
 
-        let liquidator_deposit_amount_atom: GetBalanceResponse = app
-            .wrap()
-            .query_wasm_smart(
-                addr.clone(),
-                &QueryMsg::GetDeposit {
-                    address: "liquidator".to_string(),
-                    denom: "atom".to_string(),
-                },
-            )
-            .unwrap();
-
-        assert_eq!(
-            liquidator_deposit_amount_eth.balance.u128(),
-            9999999999999999999999
-        ); // 9999.999999999999999999 ETH
-        assert_eq!(liquidator_deposit_amount_atom.balance.u128(), 0); // 0
-
-        app.execute_contract(
-            Addr::unchecked("liquidator"),
-            addr.clone(),
-            &ExecuteMsg::Liquidation {
-                user: "user".to_string(),
-            },
-            &[],
-        )
-        .unwrap();
+        // only the repaid part of the borrowing is covered by the liquidator funds
+        assert_eq!(user_borrow_amount_eth.u128(), 141850604584630250327); // 191.850604584630250327 - 50 ETH
 
-        let user_collateral_usd: Uint128 = app
+        let user_deposit_amount_eth: GetBalanceResponse = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
-                &QueryMsg::GetUserCollateralUsd {
+                &QueryMsg::GetDeposit {
                     address: "user".to_string(),
+                    denom: "eth".to_string(),
                 },
             )
             .unwrap();
 
-        // after liquidation, all collateral is transferred to the liquidator
-        assert_eq!(user_collateral_usd.u128(), 0);
-
-        let user_borrowed_usd: Uint128 = app
+        let user_deposit_amount_atom: GetBalanceResponse = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
-                &QueryMsg::GetUserBorrowedUsd {
+                &QueryMsg::GetDeposit {
                     address: "user".to_string(),
+                    denom: "atom".to_string(),
                 },
             )
             .unwrap();
 
-        // after liquidation, all borrowings are repaid by the liquidator
-        assert_eq!(user_borrowed_usd.u128(), 0);
+        // 50 ETH repaid + 5% liquidation bonus = 52.5 ETH of collateral is seized
+        // from the user ETH deposit with accrued interest
+        assert_eq!(
+            user_deposit_amount_eth.balance.u128(),
+            USER_DEPOSIT_AFTER_LIQUIDATION_ETH
+        );
+        assert_eq!(
+            user_deposit_amount_atom.balance.u128(),
+            300000000000000000000
+        ); // 300 ATOM, untouched
 
         let liquidator_deposit_amount_eth: GetBalanceResponse = app
             .wrap()
@@ -360,11 +352,21 @@ mod tests {
 
         assert_eq!(
             liquidator_deposit_amount_eth.balance.u128(),
-            10008510511955314159271
-        ); // 10008.510511955314159271 ETH
-        assert_eq!(
-            liquidator_deposit_amount_atom.balance.u128(),
-            300000000000000000000
-        ); // 300 ATOM
+            LIQUIDATOR_DEPOSIT_AFTER_LIQUIDATION_ETH
+        ); // 52.5 ETH of seized collateral as deposit
+        assert_eq!(liquidator_deposit_amount_atom.balance.u128(), 0);
+
+        let user_utilization_rate: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserUtilizationRate {
+                    address: "user".to_string(),
+                },
+            )
+            .unwrap();
+
+        // the rest of the position is untouched and still backed by the remaining collateral
+        assert_eq!(user_utilization_rate.u128(), 9320031); // 93.20031%
     }
 }
@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr, BlockInfo, Timestamp, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, GetBalanceResponse, LiquidationConfiguration, QueryMsg};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_success_liquidation_close_factor() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const BORROW_AMOUNT_ETH: u128 = 121 * 10u128.pow(TOKENS_DECIMALS); // 121 ETH
+        const YEAR_IN_SECONDS: u64 = 31536000;
+
+        const PERCENT_DECIMALS: u32 = 5;
+        const CLOSE_FACTOR_ETH: u128 = 25 * 10u128.pow(PERCENT_DECIMALS); // 25%
+        const LIQUIDATION_BONUS_ETH: u128 = 10 * 10u128.pow(PERCENT_DECIMALS); // 10%
+
+        // contract reserves: 1000 ETH
+        // user deposited 200 ETH and 300 ATOM
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let liquidation_configuration: LiquidationConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetLiquidationConfiguration {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // default configuration: 50% close factor, 5% bonus
+        assert_eq!(liquidation_configuration.close_factor, 5000000);
+        assert_eq!(liquidation_configuration.liquidation_bonus, 500000);
+
+        // only admin can change liquidation configuration
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::SetLiquidationConfiguration {
+                denom: "eth".to_string(),
+                close_factor: CLOSE_FACTOR_ETH,
+                liquidation_bonus: LIQUIDATION_BONUS_ETH,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetLiquidationConfiguration {
+                denom: "eth".to_string(),
+                close_factor: CLOSE_FACTOR_ETH,
+                liquidation_bonus: LIQUIDATION_BONUS_ETH,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let liquidation_configuration: LiquidationConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetLiquidationConfiguration {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(liquidation_configuration.close_factor, CLOSE_FACTOR_ETH);
+        assert_eq!(
+            liquidation_configuration.liquidation_bonus,
+            LIQUIDATION_BONUS_ETH
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(BORROW_AMOUNT_ETH),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // position is healthy, so it can't be liquidated yet
+        app.execute_contract(
+            Addr::unchecked("liquidator"),
+            addr.clone(),
+            &ExecuteMsg::Liquidation {
+                user: "user".to_string(),
+                collateral_denom: "eth".to_string(),
+                receive_mm_token: false,
+            },
+            &coins(BORROW_AMOUNT_ETH, "eth"),
+        )
+        .unwrap_err();
+
+        app.set_block(BlockInfo {
+            height: 542,
+            time: Timestamp::from_seconds(now + 2 * YEAR_IN_SECONDS + 10000), // after 2 years
+            chain_id: "custom_chain_id".to_string(),
+        });
+
+        let user_borrow_amount_eth: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let liquidator_balance_eth = app
+            .wrap()
+            .query_balance("liquidator", "eth")
+            .unwrap()
+            .amount
+            .u128();
+
+        // liquidator tries to repay the whole borrowing at once
+        let liquidation_response = app
+            .execute_contract(
+                Addr::unchecked("liquidator"),
+                addr.clone(),
+                &ExecuteMsg::Liquidation {
+                    user: "user".to_string(),
+                    collateral_denom: "eth".to_string(),
+                    receive_mm_token: false,
+                },
+                &coins(user_borrow_amount_eth.u128(), "eth"),
+            )
+            .unwrap();
+
+        let liquidation_attribute = |key: &str| -> u128 {
+            liquidation_response
+                .events
+                .iter()
+                .flat_map(|event| event.attributes.iter())
+                .find(|attribute| attribute.key == key)
+                .unwrap()
+                .value
+                .parse()
+                .unwrap()
+        };
+
+        let user_borrow_amount_eth_after_liquidation: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // repaid amount is capped by the close factor
+        let repaid_amount = liquidation_attribute("repaid_amount");
+        assert_eq!(repaid_amount, user_borrow_amount_eth.u128() * 25 / 100);
+        assert_eq!(
+            user_borrow_amount_eth_after_liquidation.u128(),
+            user_borrow_amount_eth.u128() - repaid_amount
+        );
+
+        // repaid value + 10% bonus is seized
+        let seized_amount = liquidation_attribute("seized_amount");
+        assert_eq!(seized_amount, repaid_amount * 110 / 100);
+
+        // excess funds are refunded and seized collateral is transferred to the liquidator
+        let liquidator_balance_eth_after_liquidation = app
+            .wrap()
+            .query_balance("liquidator", "eth")
+            .unwrap()
+            .amount
+            .u128();
+
+        assert_eq!(
+            liquidator_balance_eth_after_liquidation,
+            liquidator_balance_eth - repaid_amount + seized_amount
+        );
+
+        let liquidator_deposit_amount_eth: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "liquidator".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(liquidator_deposit_amount_eth.balance.u128(), 0);
+    }
+}
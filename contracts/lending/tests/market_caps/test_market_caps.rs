@@ -0,0 +1,205 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_deposit_of_diff_token_with_prices;
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{ExecuteMsg, MarketCapsHeadroom, QueryMsg};
+
+    const TOKENS_DECIMALS: u32 = 18;
+    const PERCENT_DECIMALS: u32 = 5;
+
+    fn set_caps(
+        app: &mut BasicApp,
+        addr: Addr,
+        denom: &str,
+        loan_to_value_ratio: u128,
+        liquidation_threshold: u128,
+        supply_cap: Option<u128>,
+        borrow_cap: Option<u128>,
+    ) {
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::SetReserveConfiguration {
+                denom: denom.to_string(),
+                loan_to_value_ratio,
+                liquidation_threshold,
+                supply_cap: supply_cap.map(Uint128::from),
+                borrow_cap: borrow_cap.map(Uint128::from),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn get_headroom(app: &BasicApp, addr: Addr, denom: &str) -> MarketCapsHeadroom {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetMarketCapsHeadroom {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_success_market_caps() {
+        const SUPPLY_CAP_ETH: u128 = 250 * 10u128.pow(TOKENS_DECIMALS); // 250 ETH
+        const BORROW_CAP_ATOM: u128 = 50 * 10u128.pow(TOKENS_DECIMALS); // 50 ATOM
+        const DEPOSIT_AMOUNT_ETH: u128 = 50 * 10u128.pow(TOKENS_DECIMALS); // 50 ETH
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        let headroom = get_headroom(&app, addr.clone(), "eth");
+
+        assert_eq!(headroom.supply_cap, None);
+        assert_eq!(headroom.supply_headroom, None);
+        assert_eq!(headroom.borrow_headroom, None);
+
+        set_caps(
+            &mut app,
+            addr.clone(),
+            "eth",
+            85 * 10u128.pow(PERCENT_DECIMALS),
+            90 * 10u128.pow(PERCENT_DECIMALS),
+            Some(SUPPLY_CAP_ETH),
+            None,
+        );
+
+        set_caps(
+            &mut app,
+            addr.clone(),
+            "atom",
+            75 * 10u128.pow(PERCENT_DECIMALS),
+            80 * 10u128.pow(PERCENT_DECIMALS),
+            None,
+            Some(BORROW_CAP_ATOM),
+        );
+
+        let headroom = get_headroom(&app, addr.clone(), "eth");
+
+        assert_eq!(
+            headroom.total_deposited.u128(),
+            200 * 10u128.pow(TOKENS_DECIMALS)
+        );
+        assert_eq!(
+            headroom.supply_headroom,
+            Some(Uint128::from(50 * 10u128.pow(TOKENS_DECIMALS)))
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Deposit { on_behalf_of: None },
+            &coins(DEPOSIT_AMOUNT_ETH, "eth"),
+        )
+        .unwrap();
+
+        let headroom = get_headroom(&app, addr.clone(), "eth");
+
+        assert_eq!(headroom.supply_headroom, Some(Uint128::zero()));
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "eth".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Borrow {
+                denom: "atom".to_string(),
+                amount: Uint128::from(BORROW_CAP_ATOM),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let headroom = get_headroom(&app, addr.clone(), "atom");
+
+        assert_eq!(headroom.total_borrowed.u128(), BORROW_CAP_ATOM);
+        assert_eq!(headroom.borrow_headroom, Some(Uint128::zero()));
+        assert_eq!(headroom.supply_headroom, None);
+    }
+
+    #[test]
+    fn test_fail_market_caps() {
+        const SUPPLY_CAP_ETH: u128 = 250 * 10u128.pow(TOKENS_DECIMALS); // 250 ETH
+        const BORROW_CAP_ATOM: u128 = 50 * 10u128.pow(TOKENS_DECIMALS); // 50 ATOM
+        const DEPOSIT_AMOUNT_ETH: u128 = 60 * 10u128.pow(TOKENS_DECIMALS); // 60 ETH
+        const BORROW_AMOUNT_ATOM: u128 = 60 * 10u128.pow(TOKENS_DECIMALS); // 60 ATOM
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_caps(
+            &mut app,
+            addr.clone(),
+            "eth",
+            85 * 10u128.pow(PERCENT_DECIMALS),
+            90 * 10u128.pow(PERCENT_DECIMALS),
+            Some(SUPPLY_CAP_ETH),
+            None,
+        );
+
+        set_caps(
+            &mut app,
+            addr.clone(),
+            "atom",
+            75 * 10u128.pow(PERCENT_DECIMALS),
+            80 * 10u128.pow(PERCENT_DECIMALS),
+            None,
+            Some(BORROW_CAP_ATOM),
+        );
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Deposit { on_behalf_of: None },
+                &coins(DEPOSIT_AMOUNT_ETH, "eth"),
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Supply Cap Exceeded");
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "eth".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Borrow {
+                    denom: "atom".to_string(),
+                    amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                    rate_mode: None,
+                    recipient: None,
+                    on_behalf_of: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Borrow Cap Exceeded");
+    }
+}
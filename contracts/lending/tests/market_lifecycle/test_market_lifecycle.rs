@@ -0,0 +1,338 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr, Coin, Uint128};
+    use cw_multi_test::{BankSudo, BasicApp, Executor, SudoMsg};
+    use lending::msg::{
+        EModeCategory, EmissionSchedule, ExecuteMsg, GetSupportedTokensResponse, MarketStatus,
+        OracleConfiguration, QueryMsg, RewardSide,
+    };
+
+    const TOKENS_DECIMALS: u32 = 18;
+    const DEPOSIT_AMOUNT_ATOM: u128 = 300 * 10u128.pow(TOKENS_DECIMALS);
+    const PERCENT_DECIMALS: u32 = 5;
+    const EMISSION_PER_SECOND: u128 = 1000;
+    const EMISSION_DURATION: u64 = 1000;
+    const MAX_PRICE_AGE: u64 = 600;
+    const INSURANCE_FUND_ATOM: u128 = 10 * 10u128.pow(TOKENS_DECIMALS);
+
+    fn set_market_status(
+        app: &mut BasicApp,
+        addr: Addr,
+        denom: &str,
+        status: MarketStatus,
+    ) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::SetMarketStatus {
+                denom: denom.to_string(),
+                status,
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn remove_market(app: &mut BasicApp, addr: Addr, denom: &str) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::RemoveSupportedToken {
+                denom: denom.to_string(),
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn get_available_to_borrow_eth(app: &BasicApp, addr: Addr) -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetAvailableToBorrow {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_success_market_lifecycle() {
+        // user deposited 200 ETH and 300 ATOM as collateral and borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        assert_eq!(
+            set_market_status(&mut app, addr.clone(), "atom", MarketStatus::Frozen),
+            None
+        );
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Deposit { on_behalf_of: None },
+                &coins(10u128.pow(TOKENS_DECIMALS), "atom"),
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Market Is Not Active: atom");
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Borrow {
+                    denom: "atom".to_string(),
+                    amount: Uint128::from(10u128.pow(TOKENS_DECIMALS)),
+                    rate_mode: None,
+                    recipient: None,
+                    on_behalf_of: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Market Is Not Active: atom");
+
+        let available_to_borrow_before = get_available_to_borrow_eth(&app, addr.clone());
+
+        assert_eq!(
+            set_market_status(&mut app, addr.clone(), "atom", MarketStatus::WindDown),
+            None
+        );
+
+        // ATOM collateral doesn't count anymore
+        assert!(get_available_to_borrow_eth(&app, addr.clone()) < available_to_borrow_before);
+
+        assert_eq!(
+            remove_market(&mut app, addr.clone(), "atom"),
+            Some("Market Has Outstanding Balances: atom".to_string())
+        );
+
+        // configuration that refers to the market is cleaned up on removal
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetEModeCategory {
+                id: 1,
+                label: "ETH and ATOM".to_string(),
+                loan_to_value_ratio: 90 * 10u128.pow(PERCENT_DECIMALS),
+                liquidation_threshold: 95 * 10u128.pow(PERCENT_DECIMALS),
+                price_source: None,
+                denoms: vec!["eth".to_string(), "atom".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetOracleConfiguration {
+                denom: "atom".to_string(),
+                max_confidence_ratio: 5 * 10u128.pow(PERCENT_DECIMALS),
+                max_price_age: MAX_PRICE_AGE,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let total_emission = EMISSION_PER_SECOND * EMISSION_DURATION as u128;
+
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: "owner".to_string(),
+            amount: coins(total_emission, "reward"),
+        }))
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::AddEmissionSchedule {
+                denom: "atom".to_string(),
+                side: RewardSide::Supply,
+                reward_denom: "reward".to_string(),
+                emission_per_second: Uint128::from(EMISSION_PER_SECOND),
+                start: app.block_info().time,
+                end: app.block_info().time.plus_seconds(EMISSION_DURATION),
+            },
+            &coins(total_emission, "reward"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Redeem {
+                denom: "atom".to_string(),
+                amount: Uint128::from(DEPOSIT_AMOUNT_ATOM),
+                recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the insurance fund has to be withdrawn before the removal
+        app.execute_contract(
+            Addr::unchecked("liquidator"),
+            addr.clone(),
+            &ExecuteMsg::FundInsuranceFund {},
+            &coins(INSURANCE_FUND_ATOM, "atom"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            remove_market(&mut app, addr.clone(), "atom"),
+            Some("Market Has Outstanding Balances: atom".to_string())
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::WithdrawInsuranceFund {
+                denom: "atom".to_string(),
+                amount: Uint128::from(INSURANCE_FUND_ATOM),
+                recipient: "owner".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(remove_market(&mut app, addr.clone(), "atom"), None);
+
+        let supported_tokens: GetSupportedTokensResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetSupportedTokens {})
+            .unwrap();
+
+        assert!(supported_tokens
+            .supported_tokens
+            .iter()
+            .all(|token| token.denom != "atom"));
+
+        let e_mode_categories: Vec<EModeCategory> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetEModeCategories {})
+            .unwrap();
+
+        assert_eq!(e_mode_categories[0].denoms, vec!["eth".to_string()]);
+
+        let oracle_configuration: OracleConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetOracleConfiguration {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_ne!(oracle_configuration.max_price_age, MAX_PRICE_AGE);
+
+        let emission_schedules: Vec<EmissionSchedule> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetEmissionSchedules {})
+            .unwrap();
+
+        assert!(emission_schedules.is_empty());
+
+        // nothing was emitted yet, the whole emission can be swept
+        let undistributed_rewards: Vec<Coin> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetUndistributedRewards {})
+            .unwrap();
+
+        assert_eq!(undistributed_rewards, coins(total_emission, "reward"));
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SweepUndistributedRewards {
+                recipient: "owner".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("owner", "reward")
+                .unwrap()
+                .amount
+                .u128(),
+            total_emission
+        );
+
+        // remaining positions are still healthy and queryable
+        let available_to_borrow_after = get_available_to_borrow_eth(&app, addr.clone());
+
+        assert!(!available_to_borrow_after.is_zero());
+    }
+
+    #[test]
+    fn test_fail_market_lifecycle() {
+        let (mut app, addr) = success_borrow_setup();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::SetMarketStatus {
+                    denom: "atom".to_string(),
+                    status: MarketStatus::Frozen,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Risk Manager Only"
+        );
+
+        // market has to be frozen before the wind-down
+        assert_eq!(
+            set_market_status(&mut app, addr.clone(), "atom", MarketStatus::WindDown),
+            Some("Invalid Market Status Transition".to_string())
+        );
+
+        assert_eq!(
+            remove_market(&mut app, addr.clone(), "atom"),
+            Some("Market Must Be Wound Down Before Removal".to_string())
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetTimelockDelay { delay: 86400 },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            set_market_status(&mut app, addr.clone(), "atom", MarketStatus::Frozen),
+            None
+        );
+        assert_eq!(
+            set_market_status(&mut app, addr.clone(), "atom", MarketStatus::WindDown),
+            None
+        );
+
+        // freezing is immediate, the wind-down is queued
+        let status: MarketStatus = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetMarketStatus {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(status, MarketStatus::Frozen);
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{
+        legacy_lending_setup, MockLegacyExecuteMsg, LEGACY_ANNUAL_INTEREST_INCOME,
+        LEGACY_BORROWED_AMOUNT, LEGACY_INTEREST_RATE, LEGACY_TIMESTAMP,
+    };
+    use cosmwasm_std::{Addr, Event, Timestamp, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{MigrateMsg, QueryMsg, TotalBorrowData, UserBorrowingInfo};
+
+    #[test]
+    fn test_success_migrate_borrow_data() {
+        let (mut app, addr, code_id) = legacy_lending_setup("crates.io:lending", "0.0.1");
+
+        let response = app
+            .migrate_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &MigrateMsg {},
+                code_id,
+            )
+            .unwrap();
+
+        assert!(response.has_event(
+            &Event::new("wasm")
+                .add_attribute("from_version", "0.0.1")
+                .add_attribute("from_storage_version", "1")
+                .add_attribute("to_storage_version", "3")
+        ));
+
+        let user_borrowing_info: UserBorrowingInfo = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowingInfo {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            user_borrowing_info,
+            UserBorrowingInfo {
+                denom: "eth".to_string(),
+                borrowed_amount: Uint128::from(LEGACY_BORROWED_AMOUNT),
+                average_interest_rate: Uint128::from(LEGACY_INTEREST_RATE),
+                timestamp: Timestamp::from_seconds(LEGACY_TIMESTAMP),
+            }
+        );
+
+        let total_borrow_data: TotalBorrowData = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetTotalBorrowData {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            total_borrow_data,
+            TotalBorrowData {
+                denom: "eth".to_string(),
+                total_borrowed_amount: Uint128::from(LEGACY_BORROWED_AMOUNT),
+                expected_annual_interest_income: Uint128::from(LEGACY_ANNUAL_INTEREST_INCOME),
+                average_interest_rate: Uint128::from(LEGACY_INTEREST_RATE),
+                variable_borrowed_amount: Uint128::from(LEGACY_BORROWED_AMOUNT),
+                stable_borrowed_amount: Uint128::zero(),
+                stable_average_interest_rate: Uint128::zero(),
+                term_borrowed_amount: Uint128::zero(),
+                timestamp: Timestamp::from_seconds(LEGACY_TIMESTAMP),
+            }
+        );
+
+        // storage is already up to date, migration to the same version changes nothing
+        app.migrate_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &MigrateMsg {},
+            code_id,
+        )
+        .unwrap();
+
+        let total_borrow_data_after: TotalBorrowData = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetTotalBorrowData {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(total_borrow_data_after, total_borrow_data);
+    }
+
+    #[test]
+    fn test_fail_migrate() {
+        let (mut app, addr, code_id) = legacy_lending_setup("crates.io:other", "0.0.1");
+
+        let error = app
+            .migrate_contract(Addr::unchecked("owner"), addr, &MigrateMsg {}, code_id)
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Invalid Contract Name: crates.io:other"
+        );
+
+        let (mut app, addr, code_id) = legacy_lending_setup("crates.io:lending", "9.9.9");
+
+        let error = app
+            .migrate_contract(Addr::unchecked("owner"), addr, &MigrateMsg {}, code_id)
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Contract Downgrade Is Not Allowed From Version 9.9.9"
+        );
+
+        let (mut app, addr, code_id) = legacy_lending_setup("crates.io:lending", "not-a-version");
+
+        let error = app
+            .migrate_contract(Addr::unchecked("owner"), addr, &MigrateMsg {}, code_id)
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Invalid Contract Version");
+
+        let (mut app, addr, code_id) = legacy_lending_setup("crates.io:lending", "0.0.1");
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &MockLegacyExecuteMsg::SetStorageVersion { version: 99 },
+            &[],
+        )
+        .unwrap();
+
+        let error = app
+            .migrate_contract(Addr::unchecked("owner"), addr, &MigrateMsg {}, code_id)
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Storage Downgrade Is Not Allowed From Version 99"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod test_fail_transfer_mm_token;
+mod test_success_transfer_from_mm_token;
+mod test_success_transfer_mm_token;
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{mm_token_setup, success_borrow_setup};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::ExecuteMsg;
+    use mm_token::msg::ExecuteMsg as ExecuteMsgMmToken;
+
+    #[test]
+    #[should_panic(expected = "Transferred mmTokens Are Used To Collateralise The Borrowing")]
+    fn test_fail_transfer_mm_token_used_as_collateral() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const TRANSFER_AMOUNT_MM_ETH: u128 = 190 * 10u128.pow(TOKENS_DECIMALS); // 190 mmETH
+
+        // contract reserves: 1000 ETH
+        // user deposited 200 ETH and 300 ATOM
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let mm_token_addr = mm_token_setup(&mut app, addr.clone(), "eth");
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            mm_token_addr.clone(),
+            &ExecuteMsgMmToken::Transfer {
+                recipient: "receiver".to_string(),
+                amount: Uint128::from(TRANSFER_AMOUNT_MM_ETH),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Allowed for mmToken Contract Only")]
+    fn test_fail_transfer_mm_token_if_caller_is_not_mm_token() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const TRANSFER_AMOUNT_MM_ETH: u128 = 10 * 10u128.pow(TOKENS_DECIMALS); // 10 mmETH
+
+        let (mut app, addr) = success_borrow_setup();
+
+        mm_token_setup(&mut app, addr.clone(), "eth");
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::TransferMmToken {
+                sender: "user".to_string(),
+                recipient: "receiver".to_string(),
+                amount: Uint128::from(TRANSFER_AMOUNT_MM_ETH),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{mm_token_setup, success_borrow_setup};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::{AllowanceResponse, Cw20QueryMsg};
+    use cw_multi_test::Executor;
+    use lending::msg::QueryMsg;
+    use mm_token::msg::ExecuteMsg as ExecuteMsgMmToken;
+
+    #[test]
+    fn test_success_transfer_from_mm_token() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const ALLOWANCE_AMOUNT_MM_ETH: u128 = 100 * 10u128.pow(TOKENS_DECIMALS); // 100 mmETH
+        const TRANSFER_AMOUNT_MM_ETH: u128 = 60 * 10u128.pow(TOKENS_DECIMALS); // 60 mmETH
+
+        // user deposited 200 ETH and 300 ATOM
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let mm_token_addr = mm_token_setup(&mut app, addr.clone(), "eth");
+
+        // spending without an allowance fails
+        assert_eq!(
+            app.execute_contract(
+                Addr::unchecked("spender"),
+                mm_token_addr.clone(),
+                &ExecuteMsgMmToken::TransferFrom {
+                    owner: "user".to_string(),
+                    recipient: "receiver".to_string(),
+                    amount: Uint128::from(TRANSFER_AMOUNT_MM_ETH),
+                },
+                &[],
+            )
+            .err()
+            .map(|error| error.root_cause().to_string()),
+            Some("No Allowance For This Account".to_string())
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            mm_token_addr.clone(),
+            &ExecuteMsgMmToken::IncreaseAllowance {
+                spender: "spender".to_string(),
+                amount: Uint128::from(ALLOWANCE_AMOUNT_MM_ETH),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("spender"),
+            mm_token_addr.clone(),
+            &ExecuteMsgMmToken::TransferFrom {
+                owner: "user".to_string(),
+                recipient: "receiver".to_string(),
+                amount: Uint128::from(TRANSFER_AMOUNT_MM_ETH),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let receiver_mm_token_balance: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetMmTokenBalance {
+                    address: "receiver".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let allowance: AllowanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                mm_token_addr.clone(),
+                &Cw20QueryMsg::Allowance {
+                    owner: "user".to_string(),
+                    spender: "spender".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(receiver_mm_token_balance.u128(), TRANSFER_AMOUNT_MM_ETH);
+        assert_eq!(
+            allowance.allowance.u128(),
+            ALLOWANCE_AMOUNT_MM_ETH - TRANSFER_AMOUNT_MM_ETH
+        );
+
+        // the remaining allowance is not enough for another transfer
+        assert_eq!(
+            app.execute_contract(
+                Addr::unchecked("spender"),
+                mm_token_addr.clone(),
+                &ExecuteMsgMmToken::TransferFrom {
+                    owner: "user".to_string(),
+                    recipient: "receiver".to_string(),
+                    amount: Uint128::from(TRANSFER_AMOUNT_MM_ETH),
+                },
+                &[],
+            )
+            .err()
+            .map(|error| error.root_cause().to_string()),
+            Some("No Allowance For This Account".to_string())
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            mm_token_addr.clone(),
+            &ExecuteMsgMmToken::DecreaseAllowance {
+                spender: "spender".to_string(),
+                amount: Uint128::from(ALLOWANCE_AMOUNT_MM_ETH),
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let allowance: AllowanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                mm_token_addr,
+                &Cw20QueryMsg::Allowance {
+                    owner: "user".to_string(),
+                    spender: "spender".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(allowance.allowance.is_zero());
+    }
+}
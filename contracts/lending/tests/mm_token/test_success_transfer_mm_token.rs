@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{mm_token_setup, success_borrow_setup};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+    use cw_multi_test::Executor;
+    use lending::msg::{GetBalanceResponse, QueryMsg};
+    use mm_token::msg::ExecuteMsg as ExecuteMsgMmToken;
+
+    #[test]
+    fn test_success_transfer_mm_token() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const TRANSFER_AMOUNT_MM_ETH: u128 = 100 * 10u128.pow(TOKENS_DECIMALS); // 100 mmETH
+
+        // contract reserves: 1000 ETH
+        // user deposited 200 ETH and 300 ATOM
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let mm_token_addr = mm_token_setup(&mut app, addr.clone(), "eth");
+
+        let user_mm_token_balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                mm_token_addr.clone(),
+                &Cw20QueryMsg::Balance {
+                    address: "user".to_string(),
+                },
+            )
+            .unwrap();
+
+        let user_deposit_amount_eth: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // receipt token balance mirrors the mmToken balance of the lending market
+        assert!(user_mm_token_balance.balance.u128() > 0);
+        assert!(user_mm_token_balance.balance <= user_deposit_amount_eth.balance);
+
+        // the remaining collateral still covers the borrowing
+        app.execute_contract(
+            Addr::unchecked("user"),
+            mm_token_addr.clone(),
+            &ExecuteMsgMmToken::Transfer {
+                recipient: "receiver".to_string(),
+                amount: Uint128::from(TRANSFER_AMOUNT_MM_ETH),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let user_mm_token_balance_after_transfer: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                mm_token_addr.clone(),
+                &Cw20QueryMsg::Balance {
+                    address: "user".to_string(),
+                },
+            )
+            .unwrap();
+
+        let receiver_mm_token_balance: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetMmTokenBalance {
+                    address: "receiver".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            user_mm_token_balance_after_transfer.balance.u128(),
+            user_mm_token_balance.balance.u128() - TRANSFER_AMOUNT_MM_ETH
+        );
+        assert_eq!(receiver_mm_token_balance.u128(), TRANSFER_AMOUNT_MM_ETH);
+
+        let token_info: TokenInfoResponse = app
+            .wrap()
+            .query_wasm_smart(mm_token_addr.clone(), &Cw20QueryMsg::TokenInfo {})
+            .unwrap();
+
+        // transfers don't change the supply
+        assert_eq!(token_info.symbol, "mmETH");
+        assert_eq!(token_info.total_supply, user_mm_token_balance.balance);
+
+        let receiver_deposit_amount_eth: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "receiver".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // the position moved together with the accrued interest
+        assert!(receiver_deposit_amount_eth.balance.u128() >= TRANSFER_AMOUNT_MM_ETH);
+    }
+}
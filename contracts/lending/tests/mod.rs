@@ -1,10 +1,28 @@
+mod access_control;
+mod bad_debt;
 mod borrow;
+mod delegation;
 mod deposit;
 mod deposit_as_collateral;
+mod e_mode;
+mod flash_loan;
+mod isolation_mode;
 mod liquidation;
+mod market_caps;
+mod market_lifecycle;
+mod migration;
+mod mm_token;
+mod oracle;
+mod pause;
+mod protocol_reserves;
+mod rate_strategy;
 mod redeem;
 mod repay;
 mod reserve_configuration;
+mod rewards;
+mod stable_rate;
+mod term_loan;
 mod test_utility;
+mod timelock;
 mod token_interest_rate_model_params;
 pub mod utils;
@@ -0,0 +1,4 @@
+mod test_fail_pyth_price;
+mod test_fail_stale_price;
+mod test_price_sources;
+mod test_pyth_price_normalization;
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{set_pyth_price, success_pyth_price_setup, PRICE_ID_ATOM, PRICE_ID_ETH};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, OracleConfiguration, QueryMsg};
+    use pyth_sdk_cw::Price;
+
+    #[test]
+    fn test_fail_pyth_price_is_negative() {
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        let now = app.block_info().time.seconds() as i64;
+
+        set_pyth_price(
+            &mut app,
+            pyth_addr.clone(),
+            PRICE_ID_ETH,
+            Price {
+                price: -200000000000,
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        set_pyth_price(
+            &mut app,
+            pyth_addr.clone(),
+            PRICE_ID_ATOM,
+            Price {
+                price: 1000000000,
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        let price_eth = app.wrap().query_wasm_smart::<Uint128>(
+            addr.clone(),
+            &QueryMsg::GetPrice {
+                denom: "eth".to_string(),
+            },
+        );
+
+        assert!(price_eth
+            .unwrap_err()
+            .to_string()
+            .contains("Non-Positive Price From Oracle"));
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("price_updater"),
+                addr.clone(),
+                &ExecuteMsg::UpdatePrice {
+                    denom: Some("eth".to_string()),
+                    price: Some(0),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Non-Positive Price From Oracle"
+        );
+    }
+
+    #[test]
+    fn test_fail_pyth_price_confidence_is_too_wide() {
+        const PERCENT_DECIMALS: u32 = 5;
+        const MAX_CONFIDENCE_RATIO_ETH: u128 = 10 * 10u128.pow(PERCENT_DECIMALS); // 10%
+
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        let now = app.block_info().time.seconds() as i64;
+
+        // 2000$ +- 160$
+        set_pyth_price(
+            &mut app,
+            pyth_addr.clone(),
+            PRICE_ID_ETH,
+            Price {
+                price: 200000000000,
+                conf: 16000000000,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        let oracle_configuration: OracleConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetOracleConfiguration {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // default configuration: 5% confidence ratio
+        assert_eq!(oracle_configuration.max_confidence_ratio, 500000);
+
+        let price_eth = app.wrap().query_wasm_smart::<Uint128>(
+            addr.clone(),
+            &QueryMsg::GetPrice {
+                denom: "eth".to_string(),
+            },
+        );
+
+        assert!(price_eth
+            .unwrap_err()
+            .to_string()
+            .contains("Price Confidence Interval Is Too Wide"));
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetOracleConfiguration {
+                denom: "eth".to_string(),
+                max_confidence_ratio: MAX_CONFIDENCE_RATIO_ETH,
+                max_price_age: 60,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let price_eth: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // 8% confidence interval is accepted with 10% max ratio
+        assert_eq!(price_eth.u128(), 200000000000);
+    }
+
+    #[test]
+    fn test_fail_pyth_price_is_below_usd_decimals() {
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        let now = app.block_info().time.seconds() as i64;
+
+        // 0.000000005$ is rounded down to zero with 8 decimals
+        set_pyth_price(
+            &mut app,
+            pyth_addr.clone(),
+            PRICE_ID_ETH,
+            Price {
+                price: 5,
+                conf: 0,
+                expo: -9,
+                publish_time: now,
+            },
+        );
+
+        let price_eth = app.wrap().query_wasm_smart::<Uint128>(
+            addr.clone(),
+            &QueryMsg::GetPrice {
+                denom: "eth".to_string(),
+            },
+        );
+
+        assert!(price_eth
+            .unwrap_err()
+            .to_string()
+            .contains("Non-Positive Price From Oracle"));
+    }
+}
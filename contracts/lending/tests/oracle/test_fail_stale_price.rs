@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{
+        mm_token_setup, set_pyth_price, success_pyth_price_setup, PRICE_ID_ATOM, PRICE_ID_ETH,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{ExecuteMsg, OracleConfiguration, QueryMsg};
+    use mm_token::msg::ExecuteMsg as ExecuteMsgMmToken;
+    use pyth_sdk_cw::Price;
+
+    fn set_prices(app: &mut BasicApp, pyth_addr: Addr) {
+        let now = app.block_info().time.seconds() as i64;
+
+        set_pyth_price(
+            app,
+            pyth_addr.clone(),
+            PRICE_ID_ETH,
+            Price {
+                price: 200000000000, // 2000$
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        set_pyth_price(
+            app,
+            pyth_addr,
+            PRICE_ID_ATOM,
+            Price {
+                price: 1000000000, // 10$
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+    }
+
+    #[test]
+    fn test_fail_stale_price() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const DEPOSIT_AMOUNT_ETH: u128 = 200 * 10u128.pow(TOKENS_DECIMALS); // 200 ETH
+        const BORROW_AMOUNT_ATOM: u128 = 100 * 10u128.pow(TOKENS_DECIMALS); // 100 ATOM
+        const REDEEM_AMOUNT_ETH: u128 = 10 * 10u128.pow(TOKENS_DECIMALS); // 10 ETH
+        const MAX_PRICE_AGE: u64 = 3600; // 1 hour
+
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        set_prices(&mut app, pyth_addr.clone());
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Deposit { on_behalf_of: None },
+            &coins(DEPOSIT_AMOUNT_ETH, "eth"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "eth".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Pyth prices are older than the default 60 seconds
+        app.update_block(|block| block.time = block.time.plus_seconds(120));
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Borrow {
+                    denom: "atom".to_string(),
+                    amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                    rate_mode: None,
+                    recipient: None,
+                    on_behalf_of: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Stale Price: atom");
+
+        set_prices(&mut app, pyth_addr.clone());
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Borrow {
+                denom: "atom".to_string(),
+                amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // cached prices expire as well
+        app.execute_contract(
+            Addr::unchecked("price_updater"),
+            addr.clone(),
+            &ExecuteMsg::UpdatePrice {
+                denom: Some("eth".to_string()),
+                price: Some(0),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.update_block(|block| block.time = block.time.plus_seconds(600));
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Redeem {
+                    denom: "eth".to_string(),
+                    amount: Uint128::from(REDEEM_AMOUNT_ETH),
+                    recipient: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Stale Price: atom");
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::ToggleCollateralSetting {
+                    denom: "eth".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Stale Price: atom");
+
+        let mm_token_addr = mm_token_setup(&mut app, addr.clone(), "eth");
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                mm_token_addr,
+                &ExecuteMsgMmToken::Transfer {
+                    recipient: "receiver".to_string(),
+                    amount: Uint128::from(REDEEM_AMOUNT_ETH),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Stale Price: atom");
+
+        for denom in ["atom", "eth"] {
+            app.execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::SetOracleConfiguration {
+                    denom: denom.to_string(),
+                    max_confidence_ratio: 500000,
+                    max_price_age: MAX_PRICE_AGE,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let oracle_configuration: OracleConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetOracleConfiguration {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(oracle_configuration.max_price_age, MAX_PRICE_AGE);
+
+        // 10 minutes old prices are fresh with 1 hour max age
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Redeem {
+                denom: "eth".to_string(),
+                amount: Uint128::from(REDEEM_AMOUNT_ETH),
+                recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,539 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{
+        band_setup, set_band_price, set_pyth_price, success_pyth_price_setup, PRICE_ID_ATOM,
+        PRICE_ID_ETH,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128, Uint256};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, PriceSource, PriceSourcesResponse, QueryMsg};
+    use pyth_sdk_cw::Price;
+
+    const BAND_RATE_DECIMALS: u32 = 18;
+    const USD_DECIMALS: u32 = 8;
+    const MAX_DEVIATION: u128 = 500000; // 5%
+
+    #[test]
+    fn test_success_price_sources() {
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        let band_addr = band_setup(&mut app);
+
+        let now = app.block_info().time.seconds() as i64;
+
+        set_pyth_price(
+            &mut app,
+            pyth_addr,
+            PRICE_ID_ATOM,
+            Price {
+                price: 1000000000, // 10$
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        // 10.2$
+        set_band_price(
+            &mut app,
+            band_addr.clone(),
+            "ATOM",
+            Uint256::from(102u128 * 10u128.pow(BAND_RATE_DECIMALS - 1)),
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetPriceSources {
+                denom: "atom".to_string(),
+                primary: PriceSource::Pyth {},
+                secondary: Some(PriceSource::Band {
+                    contract_addr: band_addr.to_string(),
+                    base_symbol: "ATOM".to_string(),
+                }),
+                max_deviation: MAX_DEVIATION,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let price_sources: PriceSourcesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPriceSources {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        // prices within the deviation band are served by the primary source
+        assert_eq!(price_sources.served_by, Some(PriceSource::Pyth {}));
+        assert_eq!(price_sources.price.u128(), 10 * 10u128.pow(USD_DECIMALS));
+        assert_eq!(
+            price_sources.secondary_price.unwrap().price.u128(),
+            1020000000
+        );
+        assert_eq!(price_sources.deviation, Some(200000)); // 2%
+
+        let price: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(price.u128(), 10 * 10u128.pow(USD_DECIMALS));
+
+        // 11$
+        set_band_price(
+            &mut app,
+            band_addr.clone(),
+            "ATOM",
+            Uint256::from(11u128 * 10u128.pow(BAND_RATE_DECIMALS)),
+        );
+
+        let error = app
+            .wrap()
+            .query_wasm_smart::<Uint128>(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("Price Sources Deviation Is Too High: atom"));
+
+        // Pyth price becomes stale, secondary source takes over
+        app.update_block(|block| block.time = block.time.plus_seconds(120));
+
+        set_band_price(
+            &mut app,
+            band_addr.clone(),
+            "ATOM",
+            Uint256::from(11u128 * 10u128.pow(BAND_RATE_DECIMALS)),
+        );
+
+        let price_sources: PriceSourcesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPriceSources {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            price_sources.served_by,
+            Some(PriceSource::Band {
+                contract_addr: band_addr.to_string(),
+                base_symbol: "ATOM".to_string(),
+            })
+        );
+        assert!(!price_sources.primary_price.unwrap().is_fresh);
+        assert_eq!(price_sources.deviation, None);
+        assert_eq!(price_sources.price.u128(), 11 * 10u128.pow(USD_DECIMALS));
+
+        // pegged price is served without any feed
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetPriceSources {
+                denom: "eth".to_string(),
+                primary: PriceSource::Fixed {
+                    price: Uint128::from(2000 * 10u128.pow(USD_DECIMALS)),
+                },
+                secondary: None,
+                max_deviation: MAX_DEVIATION,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let price: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(price.u128(), 2000 * 10u128.pow(USD_DECIMALS));
+    }
+
+    #[test]
+    fn test_success_failed_secondary_source_is_reported() {
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        let band_addr = band_setup(&mut app);
+
+        let now = app.block_info().time.seconds() as i64;
+
+        set_pyth_price(
+            &mut app,
+            pyth_addr,
+            PRICE_ID_ATOM,
+            Price {
+                price: 1000000000, // 10$
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        // the Band contract has no reference data for the symbol, so the query fails
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetPriceSources {
+                denom: "atom".to_string(),
+                primary: PriceSource::Pyth {},
+                secondary: Some(PriceSource::Band {
+                    contract_addr: band_addr.to_string(),
+                    base_symbol: "ATOM".to_string(),
+                }),
+                max_deviation: MAX_DEVIATION,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let price_sources: PriceSourcesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPriceSources {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(price_sources.served_by, Some(PriceSource::Pyth {}));
+        assert_eq!(price_sources.price.u128(), 10 * 10u128.pow(USD_DECIMALS));
+        assert_eq!(price_sources.deviation, None);
+
+        let secondary_price = price_sources.secondary_price.unwrap();
+
+        assert!(!secondary_price.is_fresh);
+        assert!(secondary_price.error.is_some());
+    }
+
+    #[test]
+    fn test_fail_set_price_sources() {
+        let (mut app, addr, _) = success_pyth_price_setup();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::SetPriceSources {
+                    denom: "atom".to_string(),
+                    primary: PriceSource::Pyth {},
+                    secondary: None,
+                    max_deviation: MAX_DEVIATION,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Oracle Manager Only"
+        );
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::SetPriceSources {
+                    denom: "atom".to_string(),
+                    primary: PriceSource::Fixed {
+                        price: Uint128::zero(),
+                    },
+                    secondary: None,
+                    max_deviation: MAX_DEVIATION,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Invalid Price Sources");
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::SetPriceSources {
+                    denom: "atom".to_string(),
+                    primary: PriceSource::Pyth {},
+                    secondary: None,
+                    max_deviation: 10000001, // more than 100%
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Invalid Price Sources");
+    }
+
+    #[test]
+    fn test_fail_deviating_debt_price_blocks_collateral_release() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const DEPOSIT_AMOUNT_ETH: u128 = 200 * 10u128.pow(TOKENS_DECIMALS); // 200 ETH
+        const BORROW_AMOUNT_ATOM: u128 = 100 * 10u128.pow(TOKENS_DECIMALS); // 100 ATOM
+        const REDEEM_AMOUNT_ETH: u128 = 10 * 10u128.pow(TOKENS_DECIMALS); // 10 ETH
+
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        let band_addr = band_setup(&mut app);
+
+        let now = app.block_info().time.seconds() as i64;
+
+        set_pyth_price(
+            &mut app,
+            pyth_addr.clone(),
+            PRICE_ID_ETH,
+            Price {
+                price: 200000000000, // 2000$
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        set_pyth_price(
+            &mut app,
+            pyth_addr,
+            PRICE_ID_ATOM,
+            Price {
+                price: 1000000000, // 10$
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Deposit { on_behalf_of: None },
+            &coins(DEPOSIT_AMOUNT_ETH, "eth"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "eth".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Borrow {
+                denom: "atom".to_string(),
+                amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // 11$ deviates from the Pyth price by 10%
+        set_band_price(
+            &mut app,
+            band_addr.clone(),
+            "ATOM",
+            Uint256::from(11u128 * 10u128.pow(BAND_RATE_DECIMALS)),
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetPriceSources {
+                denom: "atom".to_string(),
+                primary: PriceSource::Pyth {},
+                secondary: Some(PriceSource::Band {
+                    contract_addr: band_addr.to_string(),
+                    base_symbol: "ATOM".to_string(),
+                }),
+                max_deviation: MAX_DEVIATION,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the debt can't be valued, so no collateral is released
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Redeem {
+                    denom: "eth".to_string(),
+                    amount: Uint128::from(REDEEM_AMOUNT_ETH),
+                    recipient: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert!(error
+            .root_cause()
+            .to_string()
+            .contains("Price Sources Deviation Is Too High: atom"));
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::ToggleCollateralSetting {
+                    denom: "eth".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert!(error
+            .root_cause()
+            .to_string()
+            .contains("Price Sources Deviation Is Too High: atom"));
+    }
+
+    #[test]
+    fn test_fail_deviating_collateral_price_returns_error() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const DEPOSIT_AMOUNT_ETH: u128 = 200 * 10u128.pow(TOKENS_DECIMALS); // 200 ETH
+        const BORROW_AMOUNT_ATOM: u128 = 100 * 10u128.pow(TOKENS_DECIMALS); // 100 ATOM
+
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        let band_addr = band_setup(&mut app);
+
+        let now = app.block_info().time.seconds() as i64;
+
+        set_pyth_price(
+            &mut app,
+            pyth_addr.clone(),
+            PRICE_ID_ETH,
+            Price {
+                price: 200000000000, // 2000$
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        set_pyth_price(
+            &mut app,
+            pyth_addr,
+            PRICE_ID_ATOM,
+            Price {
+                price: 1000000000, // 10$
+                conf: 0,
+                expo: -8,
+                publish_time: now,
+            },
+        );
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Deposit { on_behalf_of: None },
+            &coins(DEPOSIT_AMOUNT_ETH, "eth"),
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ToggleCollateralSetting {
+                denom: "eth".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // 2200$ deviates from the Pyth price by 10%
+        set_band_price(
+            &mut app,
+            band_addr.clone(),
+            "ETH",
+            Uint256::from(2200u128 * 10u128.pow(BAND_RATE_DECIMALS)),
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetPriceSources {
+                denom: "eth".to_string(),
+                primary: PriceSource::Pyth {},
+                secondary: Some(PriceSource::Band {
+                    contract_addr: band_addr.to_string(),
+                    base_symbol: "ETH".to_string(),
+                }),
+                max_deviation: MAX_DEVIATION,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // the collateral can't be valued, the queries fail instead of panicking
+        for query_msg in [
+            QueryMsg::GetUserCollateralUsd {
+                address: "user".to_string(),
+            },
+            QueryMsg::GetAvailableToBorrow {
+                address: "user".to_string(),
+                denom: "atom".to_string(),
+            },
+            QueryMsg::GetAvailableToRedeem {
+                address: "user".to_string(),
+                denom: "eth".to_string(),
+            },
+        ] {
+            let error = app
+                .wrap()
+                .query_wasm_smart::<Uint128>(addr.clone(), &query_msg)
+                .unwrap_err();
+
+            assert!(error
+                .to_string()
+                .contains("Price Sources Deviation Is Too High: eth"));
+        }
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Borrow {
+                    denom: "atom".to_string(),
+                    amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                    rate_mode: None,
+                    recipient: None,
+                    on_behalf_of: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert!(error
+            .root_cause()
+            .to_string()
+            .contains("Price Sources Deviation Is Too High: eth"));
+    }
+}
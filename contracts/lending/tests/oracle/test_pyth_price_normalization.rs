@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{set_pyth_price, success_pyth_price_setup, PRICE_ID_ATOM, PRICE_ID_ETH};
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, QueryMsg};
+    use pyth_sdk_cw::Price;
+
+    #[test]
+    fn test_pyth_price_normalization() {
+        let (mut app, addr, pyth_addr) = success_pyth_price_setup();
+
+        let now = app.block_info().time.seconds() as i64;
+
+        // 2000.00000$ with 5 decimals
+        set_pyth_price(
+            &mut app,
+            pyth_addr.clone(),
+            PRICE_ID_ETH,
+            Price {
+                price: 200000000,
+                conf: 100000,
+                expo: -5,
+                publish_time: now,
+            },
+        );
+
+        // 10.0000000000$ with 10 decimals
+        set_pyth_price(
+            &mut app,
+            pyth_addr.clone(),
+            PRICE_ID_ATOM,
+            Price {
+                price: 100000000000,
+                conf: 10000000,
+                expo: -10,
+                publish_time: now,
+            },
+        );
+
+        let price_eth: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let price_atom: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        // both prices are converted into 8 decimals
+        assert_eq!(price_eth.u128(), 200000000000); // 2000$
+        assert_eq!(price_atom.u128(), 1000000000); // 10$
+
+        // price updater stores normalized prices as well
+        app.execute_contract(
+            Addr::unchecked("price_updater"),
+            addr.clone(),
+            &ExecuteMsg::UpdatePrice {
+                denom: Some("eth".to_string()),
+                price: Some(0),
+            },
+            &[],
+        )
+        .unwrap();
+
+        // Pyth price becomes outdated, the stored one is used
+        app.update_block(|block| block.time = block.time.plus_seconds(3600));
+
+        let price_eth: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(price_eth.u128(), 200000000000); // 2000$
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{ExecuteMsg, PauseAction, PauseState, PausedActions, QueryMsg, Role};
+
+    const TOKENS_DECIMALS: u32 = 18;
+
+    fn grant_pause_guardian(app: &mut BasicApp, addr: Addr) {
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::GrantRole {
+                role: Role::PauseGuardian,
+                address: "guardian".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn borrow(app: &mut BasicApp, addr: Addr, denom: &str) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::Borrow {
+                denom: denom.to_string(),
+                amount: Uint128::from(10u128.pow(TOKENS_DECIMALS)),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn repay(app: &mut BasicApp, addr: Addr) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::Repay { on_behalf_of: None },
+            &coins(10u128.pow(TOKENS_DECIMALS), "eth"),
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    #[test]
+    fn test_success_global_pause_keeps_repay_open() {
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        grant_pause_guardian(&mut app, addr.clone());
+
+        app.execute_contract(
+            Addr::unchecked("guardian"),
+            addr.clone(),
+            &ExecuteMsg::SetPause { value: true },
+            &[],
+        )
+        .unwrap();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Deposit { on_behalf_of: None },
+                &coins(10u128.pow(TOKENS_DECIMALS), "eth"),
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Protocol Is Paused");
+        assert_eq!(
+            borrow(&mut app, addr.clone(), "eth"),
+            Some("Protocol Is Paused".to_string())
+        );
+        assert_eq!(repay(&mut app, addr.clone()), None);
+
+        let pause_state: PauseState = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPauseState {})
+            .unwrap();
+
+        assert!(pause_state.is_paused);
+        assert_eq!(pause_state.paused_actions, PausedActions::default());
+
+        let eth_pause_state = pause_state
+            .markets
+            .iter()
+            .find(|market| market.denom == "eth")
+            .unwrap();
+
+        assert_eq!(eth_pause_state.paused_actions, PausedActions::default());
+        assert_eq!(
+            eth_pause_state.effective,
+            PausedActions {
+                deposit: true,
+                redeem: true,
+                borrow: true,
+                repay: false,
+                liquidate: false,
+                flash_loan: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_success_pause_action_per_market() {
+        let (mut app, addr) = success_borrow_setup();
+
+        grant_pause_guardian(&mut app, addr.clone());
+
+        app.execute_contract(
+            Addr::unchecked("guardian"),
+            addr.clone(),
+            &ExecuteMsg::SetActionPause {
+                denom: Some("eth".to_string()),
+                action: PauseAction::Borrow,
+                value: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            borrow(&mut app, addr.clone(), "eth"),
+            Some("Borrow Is Paused: eth".to_string())
+        );
+        assert_eq!(borrow(&mut app, addr.clone(), "atom"), None);
+
+        // flags for all markets apply to every market
+        app.execute_contract(
+            Addr::unchecked("guardian"),
+            addr.clone(),
+            &ExecuteMsg::SetActionPause {
+                denom: None,
+                action: PauseAction::Repay,
+                value: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            repay(&mut app, addr.clone()),
+            Some("Repay Is Paused: eth".to_string())
+        );
+
+        let pause_state: PauseState = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPauseState {})
+            .unwrap();
+
+        assert!(!pause_state.is_paused);
+        assert!(pause_state.paused_actions.repay);
+
+        let atom_pause_state = pause_state
+            .markets
+            .iter()
+            .find(|market| market.denom == "atom")
+            .unwrap();
+
+        assert!(!atom_pause_state.effective.borrow);
+        assert!(atom_pause_state.effective.repay);
+
+        for (denom, action) in [
+            (Some("eth".to_string()), PauseAction::Borrow),
+            (None, PauseAction::Repay),
+        ] {
+            app.execute_contract(
+                Addr::unchecked("guardian"),
+                addr.clone(),
+                &ExecuteMsg::SetActionPause {
+                    denom,
+                    action,
+                    value: false,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        assert_eq!(borrow(&mut app, addr.clone(), "eth"), None);
+        assert_eq!(repay(&mut app, addr.clone()), None);
+    }
+
+    #[test]
+    fn test_fail_set_action_pause() {
+        let (mut app, addr) = success_borrow_setup();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::SetActionPause {
+                    denom: None,
+                    action: PauseAction::Deposit,
+                    value: true,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Pause Guardian Only"
+        );
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::SetActionPause {
+                    denom: Some("usdt".to_string()),
+                    action: PauseAction::Deposit,
+                    value: true,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Token Not Supported");
+    }
+}
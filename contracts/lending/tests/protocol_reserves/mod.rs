@@ -0,0 +1,2 @@
+mod test_fail_withdraw_reserves;
+mod test_success_withdraw_reserves;
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, ProtocolReserves, QueryMsg};
+
+    #[test]
+    #[should_panic(expected = "Allowed for Admin or Treasury Only")]
+    fn test_fail_withdraw_reserves_if_caller_is_not_owner() {
+        let (mut app, addr) = success_borrow_setup();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::WithdrawReserves {
+                denom: "eth".to_string(),
+                amount: Uint128::from(1u128),
+                recipient: "user".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Reserve Factor")]
+    fn test_fail_set_reserve_factor_of_hundred_percent() {
+        const PERCENT_DECIMALS: u32 = 5;
+        const RESERVE_FACTOR_ETH: u128 = 100 * 10u128.pow(PERCENT_DECIMALS); // 100%
+
+        let (mut app, addr) = success_borrow_setup();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetReserveFactor {
+                denom: "eth".to_string(),
+                reserve_factor: RESERVE_FACTOR_ETH,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Not Enough Protocol Reserves To Withdraw")]
+    fn test_fail_withdraw_more_than_accrued_reserves() {
+        const PERCENT_DECIMALS: u32 = 5;
+        const RESERVE_FACTOR_ETH: u128 = 10 * 10u128.pow(PERCENT_DECIMALS); // 10%
+        const YEAR_IN_SECONDS: u64 = 31536000;
+
+        let (mut app, addr) = success_borrow_setup();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetReserveFactor {
+                denom: "eth".to_string(),
+                reserve_factor: RESERVE_FACTOR_ETH,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let block_time = app.block_info().time.seconds();
+
+        app.set_block(BlockInfo {
+            height: 542,
+            time: Timestamp::from_seconds(block_time + YEAR_IN_SECONDS), // after 1 year
+            chain_id: "custom_chain_id".to_string(),
+        });
+
+        let protocol_reserves: ProtocolReserves = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetProtocolReserves {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // deposited funds of the users can't be withdrawn as reserves
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::WithdrawReserves {
+                denom: "eth".to_string(),
+                amount: protocol_reserves.accrued_reserves + Uint128::from(1u128),
+                recipient: "treasury".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+}
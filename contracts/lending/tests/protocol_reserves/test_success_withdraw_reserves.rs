@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, ProtocolReserves, QueryMsg, TotalBorrowData};
+
+    #[test]
+    fn test_success_withdraw_reserves() {
+        const PERCENT_DECIMALS: u32 = 5;
+        const RESERVE_FACTOR_ETH: u128 = 20 * 10u128.pow(PERCENT_DECIMALS); // 20%
+        const INTEREST_RATE_DECIMALS: u32 = 18;
+        const TOKENS_DECIMALS: u32 = 18;
+        const YEAR_IN_SECONDS: u64 = 31536000;
+
+        // contract reserves: 1000 ETH
+        // user deposited 200 ETH and 300 ATOM
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let liquidity_rate_before: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetLiquidityRate {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetReserveFactor {
+                denom: "eth".to_string(),
+                reserve_factor: RESERVE_FACTOR_ETH,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let liquidity_rate_after: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetLiquidityRate {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // depositors receive 80% of the interest income
+        assert_eq!(
+            liquidity_rate_after.u128(),
+            liquidity_rate_before.u128() * 80 / 100
+        );
+
+        let total_borrow_data: TotalBorrowData = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetTotalBorrowData {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let block_time = app.block_info().time.seconds();
+
+        app.set_block(BlockInfo {
+            height: 542,
+            time: Timestamp::from_seconds(block_time + YEAR_IN_SECONDS), // after 1 year
+            chain_id: "custom_chain_id".to_string(),
+        });
+
+        let protocol_reserves: ProtocolReserves = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetProtocolReserves {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // 20% of the annual interest income is kept by the protocol
+        let expected_reserves = total_borrow_data.expected_annual_interest_income.u128()
+            / 10u128.pow(INTEREST_RATE_DECIMALS - TOKENS_DECIMALS)
+            * 20
+            / 100;
+
+        assert_eq!(protocol_reserves.reserve_factor, RESERVE_FACTOR_ETH);
+        assert!(
+            protocol_reserves
+                .accrued_reserves
+                .u128()
+                .abs_diff(expected_reserves)
+                <= 10
+        );
+
+        let contract_balance_eth = app
+            .wrap()
+            .query_balance(addr.clone(), "eth")
+            .unwrap()
+            .amount
+            .u128();
+
+        let available_liquidity_eth: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetAvailableLiquidityByToken {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // accrued reserves are excluded from the liquidity available to users
+        assert_eq!(
+            available_liquidity_eth.u128(),
+            contract_balance_eth - protocol_reserves.accrued_reserves.u128()
+        );
+
+        let withdraw_amount = protocol_reserves.accrued_reserves.u128() / 2;
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::WithdrawReserves {
+                denom: "eth".to_string(),
+                amount: Uint128::from(withdraw_amount),
+                recipient: "treasury".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let treasury_balance_eth = app
+            .wrap()
+            .query_balance("treasury", "eth")
+            .unwrap()
+            .amount
+            .u128();
+
+        assert_eq!(treasury_balance_eth, withdraw_amount);
+
+        let protocol_reserves_after_withdraw: ProtocolReserves = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetProtocolReserves {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            protocol_reserves_after_withdraw.accrued_reserves.u128(),
+            protocol_reserves.accrued_reserves.u128() - withdraw_amount
+        );
+    }
+}
@@ -0,0 +1 @@
+mod test_rate_strategy;
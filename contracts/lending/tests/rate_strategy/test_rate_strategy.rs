@@ -0,0 +1,320 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{Addr, BlockInfo, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{ExecuteMsg, QueryMsg, RateStrategy};
+
+    const PERCENT_DECIMALS: u32 = 5;
+    const HUNDRED_PERCENT: u128 = 100 * 10u128.pow(PERCENT_DECIMALS);
+    const INTEREST_RATE_DECIMALS: u32 = 18;
+
+    const YEAR_IN_SECONDS: u64 = 31536000;
+
+    fn set_rate_strategy(
+        app: &mut BasicApp,
+        addr: Addr,
+        sender: &str,
+        denom: &str,
+        strategy: RateStrategy,
+    ) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            addr,
+            &ExecuteMsg::SetRateStrategy {
+                denom: denom.to_string(),
+                strategy,
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn get_interest_rate(app: &BasicApp, addr: Addr, denom: &str) -> u128 {
+        let interest_rate: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetInterestRate {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap();
+
+        interest_rate.u128()
+    }
+
+    fn skip_time(app: &mut BasicApp, seconds: u64) {
+        app.set_block(BlockInfo {
+            height: app.block_info().height + 1,
+            time: app.block_info().time.plus_seconds(seconds),
+            chain_id: app.block_info().chain_id,
+        });
+    }
+
+    fn adaptive_strategy(target_utilisation_ratio: u128, adjustment_speed: u128) -> RateStrategy {
+        RateStrategy::Adaptive {
+            target_utilisation_ratio,
+            adjustment_speed,
+            min_interest_rate: 10u128.pow(INTEREST_RATE_DECIMALS), // 1%
+            max_interest_rate: 50 * 10u128.pow(INTEREST_RATE_DECIMALS), // 50%
+        }
+    }
+
+    #[test]
+    fn test_success_set_fixed_and_three_slope_strategies() {
+        // contract reserves: 1000 ETH, user deposited 200 ETH and borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let rate_strategy: RateStrategy = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetRateStrategy {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(rate_strategy, RateStrategy::TwoSlope);
+
+        let fixed_rate = 3 * 10u128.pow(INTEREST_RATE_DECIMALS); // 3%
+
+        assert_eq!(
+            set_rate_strategy(
+                &mut app,
+                addr.clone(),
+                "owner",
+                "eth",
+                RateStrategy::Fixed { rate: fixed_rate },
+            ),
+            None
+        );
+
+        assert_eq!(get_interest_rate(&app, addr.clone(), "eth"), fixed_rate);
+
+        let three_slope = RateStrategy::ThreeSlope {
+            min_interest_rate: 2 * 10u128.pow(INTEREST_RATE_DECIMALS), // 2%
+            first_kink_utilisation_ratio: 2 * 10u128.pow(PERCENT_DECIMALS), // 2%
+            first_kink_rate: 4 * 10u128.pow(INTEREST_RATE_DECIMALS),   // 4%
+            second_kink_utilisation_ratio: 50 * 10u128.pow(PERCENT_DECIMALS), // 50%
+            second_kink_rate: 20 * 10u128.pow(INTEREST_RATE_DECIMALS), // 20%
+            max_interest_rate: 100 * 10u128.pow(INTEREST_RATE_DECIMALS), // 100%
+        };
+
+        assert_eq!(
+            set_rate_strategy(&mut app, addr.clone(), "owner", "eth", three_slope.clone()),
+            None
+        );
+
+        let rate_strategy: RateStrategy = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetRateStrategy {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(rate_strategy, three_slope);
+
+        let utilization_rate: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUtilizationRateByToken {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // utilisation is on the middle slope
+        assert!(utilization_rate.u128() > 2 * 10u128.pow(PERCENT_DECIMALS));
+        assert!(utilization_rate.u128() <= 50 * 10u128.pow(PERCENT_DECIMALS));
+
+        assert_eq!(
+            get_interest_rate(&app, addr.clone(), "eth"),
+            4 * 10u128.pow(INTEREST_RATE_DECIMALS)
+                + (utilization_rate.u128() - 2 * 10u128.pow(PERCENT_DECIMALS))
+                    * (16 * 10u128.pow(INTEREST_RATE_DECIMALS))
+                    / (48 * 10u128.pow(PERCENT_DECIMALS))
+        );
+
+        // other markets keep the two-slope curve
+        assert_eq!(
+            get_interest_rate(&app, addr.clone(), "atom"),
+            5 * 10u128.pow(INTEREST_RATE_DECIMALS)
+        );
+    }
+
+    #[test]
+    fn test_success_adaptive_strategy_drifts_toward_target() {
+        let (mut app, addr) = success_borrow_setup();
+
+        skip_time(&mut app, 1000);
+
+        let rate_before_switch = get_interest_rate(&app, addr.clone(), "eth");
+
+        // utilisation is below the target, so the rate goes down over time
+        assert_eq!(
+            set_rate_strategy(
+                &mut app,
+                addr.clone(),
+                "owner",
+                "eth",
+                adaptive_strategy(
+                    90 * 10u128.pow(PERCENT_DECIMALS),
+                    40 * 10u128.pow(INTEREST_RATE_DECIMALS), // 40% per year
+                ),
+            ),
+            None
+        );
+
+        // the adaptive strategy starts from the rate of the previous strategy
+        assert_eq!(
+            get_interest_rate(&app, addr.clone(), "eth"),
+            rate_before_switch
+        );
+
+        // interest is accrued up to the switch
+        let stored_liquidity_index_ln: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetLiquidityIndexLastUpdate {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let current_liquidity_index_ln: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetCurrentLiquidityIndexLn {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(stored_liquidity_index_ln, current_liquidity_index_ln);
+
+        skip_time(&mut app, YEAR_IN_SECONDS / 12);
+
+        let rate_after_month = get_interest_rate(&app, addr.clone(), "eth");
+
+        assert!(rate_after_month < rate_before_switch);
+
+        // the rate reached so far is kept when the market is updated
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Redeem {
+                denom: "eth".to_string(),
+                amount: Uint128::from(10u128.pow(18)),
+                recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert!(get_interest_rate(&app, addr.clone(), "eth") <= rate_after_month);
+
+        skip_time(&mut app, 2 * YEAR_IN_SECONDS);
+
+        assert_eq!(
+            get_interest_rate(&app, addr.clone(), "eth"),
+            10u128.pow(INTEREST_RATE_DECIMALS)
+        );
+
+        // utilisation of about 4% is above the target, so the rate goes up to the max
+        assert_eq!(
+            set_rate_strategy(
+                &mut app,
+                addr.clone(),
+                "owner",
+                "eth",
+                adaptive_strategy(
+                    10u128.pow(PERCENT_DECIMALS),
+                    1000 * 10u128.pow(INTEREST_RATE_DECIMALS), // 1000% per year
+                ),
+            ),
+            None
+        );
+
+        assert_eq!(
+            get_interest_rate(&app, addr.clone(), "eth"),
+            10u128.pow(INTEREST_RATE_DECIMALS)
+        );
+
+        skip_time(&mut app, YEAR_IN_SECONDS / 12);
+
+        assert!(get_interest_rate(&app, addr.clone(), "eth") > 10u128.pow(INTEREST_RATE_DECIMALS));
+
+        skip_time(&mut app, 2 * YEAR_IN_SECONDS);
+
+        assert_eq!(
+            get_interest_rate(&app, addr.clone(), "eth"),
+            50 * 10u128.pow(INTEREST_RATE_DECIMALS)
+        );
+    }
+
+    #[test]
+    fn test_fail_set_rate_strategy() {
+        let (mut app, addr) = success_borrow_setup();
+
+        assert_eq!(
+            set_rate_strategy(
+                &mut app,
+                addr.clone(),
+                "user",
+                "eth",
+                RateStrategy::Fixed { rate: 0 },
+            ),
+            Some("Allowed for Admin or Risk Manager Only".to_string())
+        );
+
+        assert_eq!(
+            set_rate_strategy(
+                &mut app,
+                addr.clone(),
+                "owner",
+                "usdt",
+                RateStrategy::Fixed { rate: 0 },
+            ),
+            Some("Token Not Supported".to_string())
+        );
+
+        // kinks are not in ascending order
+        assert_eq!(
+            set_rate_strategy(
+                &mut app,
+                addr.clone(),
+                "owner",
+                "eth",
+                RateStrategy::ThreeSlope {
+                    min_interest_rate: 0,
+                    first_kink_utilisation_ratio: 80 * 10u128.pow(PERCENT_DECIMALS),
+                    first_kink_rate: 10 * 10u128.pow(INTEREST_RATE_DECIMALS),
+                    second_kink_utilisation_ratio: 60 * 10u128.pow(PERCENT_DECIMALS),
+                    second_kink_rate: 20 * 10u128.pow(INTEREST_RATE_DECIMALS),
+                    max_interest_rate: 100 * 10u128.pow(INTEREST_RATE_DECIMALS),
+                },
+            ),
+            Some("Invalid Rate Strategy".to_string())
+        );
+
+        assert_eq!(
+            set_rate_strategy(
+                &mut app,
+                addr.clone(),
+                "owner",
+                "eth",
+                adaptive_strategy(HUNDRED_PERCENT, 0),
+            ),
+            Some("Invalid Rate Strategy".to_string())
+        );
+    }
+}
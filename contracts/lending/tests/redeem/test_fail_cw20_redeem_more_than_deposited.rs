@@ -1,17 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::utils::success_native_and_cw20_setup;
     use cosmwasm_std::{to_json_binary, Addr, Uint128};
-    use cw20::{BalanceResponse, Cw20QueryMsg, Cw20ReceiveMsg};
-    use cw20_base::msg::{
-        ExecuteMsg as ExecuteMsgCW20, InstantiateMsg as InstantiateMsgCW20,
-        QueryMsg as QueryMsgCW20,
-    };
+    use cw20::{BalanceResponse, Cw20QueryMsg};
+    use cw20_base::msg::ExecuteMsg as ExecuteMsgCW20;
     use cw_multi_test::Executor;
     use lending::msg::{
         Cw20HookMsg, ExecuteMsg, GetBalanceResponse, GetSupportedTokensResponse, QueryMsg,
-        UserDataByToken,
     };
 
     #[test]
@@ -89,6 +84,7 @@ mod tests {
 
         let hook = Cw20HookMsg::Deposit {
             denom: "ilend-denom".to_string(),
+            on_behalf_of: None,
         };
 
         let send_msg = ExecuteMsgCW20::Send {
@@ -158,6 +154,7 @@ mod tests {
                 &ExecuteMsg::Redeem {
                     denom: "ilend-denom".to_string(),
                     amount: Uint128::from(10000000000u128),
+                    recipient: None,
                 },
                 &[],
             )
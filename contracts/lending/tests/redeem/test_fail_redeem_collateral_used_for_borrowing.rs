@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, GetBalanceResponse, QueryMsg};
+
+    use crate::utils::success_borrow_setup;
+
+    #[test]
+    fn test_fail_redeem_collateral_used_for_borrowing() {
+        const TOKENS_DECIMALS: u32 = 18;
+        const DEPOSIT_AMOUNT_ETH: u128 = 200 * 10u128.pow(TOKENS_DECIMALS); // 200 ETH
+        const REDEEM_AMOUNT_ETH: u128 = 10 * 10u128.pow(TOKENS_DECIMALS); // 10 ETH
+
+        // having 50 ETH borrowed, the user can't take back the whole collateral
+        let (mut app, addr) = success_borrow_setup();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Redeem {
+                    denom: "eth".to_string(),
+                    amount: Uint128::from(DEPOSIT_AMOUNT_ETH),
+                    recipient: None,
+                },
+                &[],
+            )
+            .err()
+            .map(|error| error.root_cause().to_string());
+
+        assert_eq!(
+            error,
+            Some("Redeemed Tokens Are Used To Collateralise The Borrowing".to_string())
+        );
+
+        let user_deposited_balance: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(user_deposited_balance.balance.u128(), DEPOSIT_AMOUNT_ETH);
+
+        // the part of the collateral that isn't needed for the borrowing is still redeemable
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Redeem {
+                denom: "eth".to_string(),
+                amount: Uint128::from(REDEEM_AMOUNT_ETH),
+                recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+}
@@ -27,6 +27,7 @@ mod tests {
             &ExecuteMsg::Redeem {
                 denom: "eth".to_string(),
                 amount: Uint128::from(WITHDRAW_AMOUNT),
+                recipient: None,
             },
             &[],
         )
@@ -28,6 +28,7 @@ mod tests {
             &ExecuteMsg::Redeem {
                 denom: "eth".to_string(),
                 amount: Uint128::from(WITHDRAW_AMOUNT_FIRST_TOKEN),
+                recipient: None,
             },
             &[],
         )
@@ -39,6 +40,7 @@ mod tests {
             &ExecuteMsg::Redeem {
                 denom: "atom".to_string(),
                 amount: Uint128::from(WITHDRAW_AMOUNT_SECOND_TOKEN),
+                recipient: None,
             },
             &[],
         )
@@ -27,6 +27,7 @@ mod tests {
             &ExecuteMsg::Redeem {
                 denom: "eth".to_string(),
                 amount: Uint128::from(SECOND_DEPOSIT_AMOUNT),
+                recipient: None,
             },
             &[],
         )
@@ -63,6 +64,7 @@ mod tests {
             &ExecuteMsg::Redeem {
                 denom: "eth".to_string(),
                 amount: Uint128::from(FIRST_DEPOSIT_AMOUNT),
+                recipient: None,
             },
             &[],
         )
@@ -27,6 +27,7 @@ mod tests {
             &ExecuteMsg::Redeem {
                 denom: "eth".to_string(),
                 amount: Uint128::from(FIRST_DEPOSIT_AMOUNT + SECOND_DEPOSIT_AMOUNT),
+                recipient: None,
             },
             &[],
         )
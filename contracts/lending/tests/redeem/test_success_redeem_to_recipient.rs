@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Addr, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, GetBalanceResponse, QueryMsg};
+
+    use crate::utils::success_deposit_of_one_token_setup;
+
+    #[test]
+    fn test_success_redeem_to_recipient() {
+        const REDEEM_AMOUNT: u128 = 100 * 10u128.pow(18);
+
+        // user deposited 500 ETH
+        let (mut app, addr) = success_deposit_of_one_token_setup();
+
+        let user_balance_before_redeem = app.wrap().query_balance("user", "eth").unwrap().amount;
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Redeem {
+                denom: "eth".to_string(),
+                amount: Uint128::from(REDEEM_AMOUNT),
+                recipient: Some("recipient".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let user_deposited_balance: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(user_deposited_balance.balance.u128(), 400 * 10u128.pow(18));
+
+        // redeemed tokens go to the recipient, the user balance is not touched
+        assert_eq!(
+            app.wrap()
+                .query_balance("recipient", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            REDEEM_AMOUNT
+        );
+        assert_eq!(
+            app.wrap().query_balance("user", "eth").unwrap().amount,
+            user_balance_before_redeem
+        );
+    }
+}
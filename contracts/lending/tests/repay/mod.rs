@@ -4,4 +4,5 @@ mod test_success_repay_cw20_by_parts;
 mod test_success_repay_cw20_more_than_needed;
 mod test_success_repay_cw20_whole_amount;
 mod test_success_repay_more_than_needed;
+mod test_success_repay_on_behalf_of;
 mod test_success_repay_whole_amount;
@@ -14,7 +14,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Repay {},
+            &ExecuteMsg::Repay { on_behalf_of: None },
             &[],
         )
         .unwrap();
@@ -33,7 +33,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Repay {},
+            &ExecuteMsg::Repay { on_behalf_of: None },
             &[
                 coin(REPAY_AMOUNT_ETH, "eth"),
                 coin(REPAY_AMOUNT_ATOM, "atom"),
@@ -51,7 +51,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Repay {},
+            &ExecuteMsg::Repay { on_behalf_of: None },
             &[coin(0, "eth")],
         )
         .unwrap();
@@ -69,7 +69,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Repay {},
+            &ExecuteMsg::Repay { on_behalf_of: None },
             &[coin(BORROW_AMOUNT_UNSUPPORTED_TOKEN, "usdt")],
         )
         .unwrap();
@@ -45,12 +45,12 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Repay {},
+            &ExecuteMsg::Repay { on_behalf_of: None },
             &coins(borrow_info_before_first_repay.u128() / 2, "eth"),
         )
         .unwrap();
 
-        let borrow_info_after_first_repay: Uint128 = app
+        let _borrow_info_after_first_repay: Uint128 = app
             .wrap()
             .query_wasm_smart(
                 addr.clone(),
@@ -64,7 +64,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Repay {},
+            &ExecuteMsg::Repay { on_behalf_of: None },
             &coins(borrow_info_before_first_repay.u128(), "eth"),
         )
         .unwrap();
@@ -45,7 +45,7 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("user"),
             addr.clone(),
-            &ExecuteMsg::Repay {},
+            &ExecuteMsg::Repay { on_behalf_of: None },
             &coins(amount_to_repay_with_interest * 2, "eth"),
         )
         .unwrap();
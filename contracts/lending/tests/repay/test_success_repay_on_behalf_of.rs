@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr, BlockInfo, Timestamp, Uint128};
+    use cw_multi_test::Executor;
+    use lending::msg::{ExecuteMsg, QueryMsg};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_success_repay_on_behalf_of() {
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        app.set_block(BlockInfo {
+            height: 542,
+            time: Timestamp::from_seconds(now + 31536000 + 10000),
+            chain_id: "custom_chain_id".to_string(),
+        });
+
+        let user_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let amount_to_repay_with_interest = user_borrow_amount_with_interest.u128();
+
+        let user_balance_before_repay = app.wrap().query_balance("user", "eth").unwrap().amount;
+        let payer_balance_before_repay = app
+            .wrap()
+            .query_balance("liquidator", "eth")
+            .unwrap()
+            .amount
+            .u128();
+
+        // liquidator pays down the debt of the user and sends twice as much as needed
+        app.execute_contract(
+            Addr::unchecked("liquidator"),
+            addr.clone(),
+            &ExecuteMsg::Repay {
+                on_behalf_of: Some("user".to_string()),
+            },
+            &coins(amount_to_repay_with_interest * 2, "eth"),
+        )
+        .unwrap();
+
+        let user_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(user_borrow_amount_with_interest.u128(), 0);
+
+        // overpayment is returned to the payer, the borrower balance is not touched
+        assert_eq!(
+            app.wrap()
+                .query_balance("liquidator", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            payer_balance_before_repay - amount_to_repay_with_interest
+        );
+        assert_eq!(
+            app.wrap().query_balance("user", "eth").unwrap().amount,
+            user_balance_before_repay
+        );
+    }
+}
@@ -6,7 +6,7 @@ mod tests {
     use lending::msg::ExecuteMsg;
 
     #[test]
-    #[should_panic(expected = "Allowed for Admin Only")]
+    #[should_panic(expected = "Allowed for Admin or Risk Manager Only")]
     fn test_fail_set_reserve_configuration_if_caller_is_not_owner() {
         const PERCENT_DECIMALS: u32 = 5;
         const LTV_ETH: u128 = 92 * 10u128.pow(PERCENT_DECIMALS); // 92%
@@ -21,6 +21,8 @@ mod tests {
                 denom: "eth".to_string(),
                 loan_to_value_ratio: LTV_ETH,
                 liquidation_threshold: LIQUIDATION_THRESHOLD_ETH,
+                supply_cap: None,
+                borrow_cap: None,
             },
             &[],
         )
@@ -43,6 +45,8 @@ mod tests {
                 denom: "usdt".to_string(),
                 loan_to_value_ratio: LTV_ETH,
                 liquidation_threshold: LIQUIDATION_THRESHOLD_ETH,
+                supply_cap: None,
+                borrow_cap: None,
             },
             &[],
         )
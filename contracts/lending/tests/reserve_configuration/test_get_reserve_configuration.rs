@@ -20,10 +20,7 @@ mod tests {
             .query_wasm_smart(addr.clone(), &QueryMsg::GetReserveConfiguration {})
             .unwrap();
 
-        println!(
-            "{}",
-            format!("{:?}", reserve_configuration_response.reserve_configuration)
-        );
+        println!("{:?}", reserve_configuration_response.reserve_configuration);
 
         assert_eq!(
             reserve_configuration_response.reserve_configuration[0].denom,
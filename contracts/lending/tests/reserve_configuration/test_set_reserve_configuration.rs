@@ -24,6 +24,8 @@ mod tests {
                 denom: "eth".to_string(),
                 loan_to_value_ratio: LTV_ETH,
                 liquidation_threshold: LIQUIDATION_THRESHOLD_ETH,
+                supply_cap: None,
+                borrow_cap: None,
             },
             &[],
         )
@@ -36,6 +38,8 @@ mod tests {
                 denom: "atom".to_string(),
                 loan_to_value_ratio: LTV_ATOM,
                 liquidation_threshold: LIQUIDATION_THRESHOLD_ATOM,
+                supply_cap: None,
+                borrow_cap: None,
             },
             &[],
         )
@@ -46,10 +50,7 @@ mod tests {
             .query_wasm_smart(addr.clone(), &QueryMsg::GetReserveConfiguration {})
             .unwrap();
 
-        println!(
-            "{}",
-            format!("{:?}", reserve_configuration_response.reserve_configuration)
-        );
+        println!("{:?}", reserve_configuration_response.reserve_configuration);
 
         assert_eq!(
             reserve_configuration_response.reserve_configuration[0].denom,
@@ -0,0 +1,337 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr, BlockInfo, Coin, Uint128};
+    use cw_multi_test::{BankSudo, BasicApp, Executor, SudoMsg};
+    use lending::msg::{EmissionSchedule, ExecuteMsg, QueryMsg, RewardSide};
+
+    const EMISSION_PER_SECOND: u128 = 1_000_000;
+    const DURATION: u64 = 1000;
+
+    fn add_emission_schedule(
+        app: &mut BasicApp,
+        addr: Addr,
+        sender: &str,
+        side: RewardSide,
+        reward_denom: &str,
+        funds: u128,
+    ) -> Option<String> {
+        let start = app.block_info().time;
+
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: sender.to_string(),
+            amount: coins(funds, reward_denom),
+        }))
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked(sender),
+            addr,
+            &ExecuteMsg::AddEmissionSchedule {
+                denom: "eth".to_string(),
+                side,
+                reward_denom: reward_denom.to_string(),
+                emission_per_second: Uint128::from(EMISSION_PER_SECOND),
+                start,
+                end: start.plus_seconds(DURATION),
+            },
+            &coins(funds, reward_denom),
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn get_pending_rewards(app: &BasicApp, addr: Addr, address: &str) -> u128 {
+        let pending_rewards: Vec<Coin> = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetPendingRewards {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+
+        pending_rewards
+            .iter()
+            .find(|reward| reward.denom == "reward")
+            .map(|reward| reward.amount.u128())
+            .unwrap_or_default()
+    }
+
+    fn get_mm_token_share(app: &BasicApp, addr: Addr) -> (u128, u128) {
+        let user_mm_token_balance: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetMmTokenBalance {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let total_mm_token_supply: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetTotalMmTokenSupply {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        (user_mm_token_balance.u128(), total_mm_token_supply.u128())
+    }
+
+    fn skip_time(app: &mut BasicApp, seconds: u64) {
+        app.set_block(BlockInfo {
+            height: app.block_info().height + 1,
+            time: app.block_info().time.plus_seconds(seconds),
+            chain_id: app.block_info().chain_id,
+        });
+    }
+
+    #[test]
+    fn test_success_borrow_rewards_claim() {
+        // user is the only variable borrower of ETH with 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let total_emission = EMISSION_PER_SECOND * DURATION as u128;
+
+        assert_eq!(
+            add_emission_schedule(
+                &mut app,
+                addr.clone(),
+                "owner",
+                RewardSide::Borrow,
+                "reward",
+                total_emission,
+            ),
+            None
+        );
+
+        let emission_schedules: Vec<EmissionSchedule> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetEmissionSchedules {})
+            .unwrap();
+
+        assert_eq!(emission_schedules.len(), 1);
+        assert_eq!(emission_schedules[0].side, RewardSide::Borrow);
+
+        skip_time(&mut app, DURATION / 2);
+
+        // half of the emission is accrued, rounding is in favor of the protocol
+        let pending_rewards = get_pending_rewards(&app, addr.clone(), "user");
+
+        assert!(pending_rewards <= total_emission / 2);
+        assert!(pending_rewards >= total_emission / 2 - 10);
+
+        // emission stops at the end of the schedule
+        skip_time(&mut app, DURATION);
+
+        let pending_rewards = get_pending_rewards(&app, addr.clone(), "user");
+
+        assert!(pending_rewards <= total_emission);
+        assert!(pending_rewards >= total_emission - 10);
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ClaimRewards {},
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("user", "reward")
+                .unwrap()
+                .amount
+                .u128(),
+            pending_rewards
+        );
+        assert_eq!(get_pending_rewards(&app, addr.clone(), "user"), 0);
+
+        // the finished schedule is removed once its emission is in the reward index
+        let emission_schedules: Vec<EmissionSchedule> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetEmissionSchedules {})
+            .unwrap();
+
+        assert!(emission_schedules.is_empty());
+    }
+
+    #[test]
+    fn test_success_sweep_undistributed_rewards() {
+        // nobody borrows ATOM, so the emission can't be earned
+        let (mut app, addr) = success_borrow_setup();
+
+        let total_emission = EMISSION_PER_SECOND * DURATION as u128;
+        let start = app.block_info().time;
+
+        app.sudo(SudoMsg::Bank(BankSudo::Mint {
+            to_address: "owner".to_string(),
+            amount: coins(total_emission, "reward"),
+        }))
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::AddEmissionSchedule {
+                denom: "atom".to_string(),
+                side: RewardSide::Borrow,
+                reward_denom: "reward".to_string(),
+                emission_per_second: Uint128::from(EMISSION_PER_SECOND),
+                start,
+                end: start.plus_seconds(DURATION),
+            },
+            &coins(total_emission, "reward"),
+        )
+        .unwrap();
+
+        skip_time(&mut app, DURATION);
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::SweepUndistributedRewards {
+                    recipient: "user".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Treasury Only"
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SweepUndistributedRewards {
+                recipient: "treasury".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("treasury", "reward")
+                .unwrap()
+                .amount
+                .u128(),
+            total_emission
+        );
+
+        let undistributed_rewards: Vec<Coin> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetUndistributedRewards {})
+            .unwrap();
+
+        assert!(undistributed_rewards.is_empty());
+
+        let emission_schedules: Vec<EmissionSchedule> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetEmissionSchedules {})
+            .unwrap();
+
+        assert!(emission_schedules.is_empty());
+    }
+
+    #[test]
+    fn test_success_supply_rewards_checkpoint() {
+        let (mut app, addr) = success_borrow_setup();
+
+        add_emission_schedule(
+            &mut app,
+            addr.clone(),
+            "owner",
+            RewardSide::Supply,
+            "reward",
+            EMISSION_PER_SECOND * DURATION as u128,
+        );
+
+        let (user_mm_token_balance, total_mm_token_supply) = get_mm_token_share(&app, addr.clone());
+
+        skip_time(&mut app, 100);
+
+        // rewards are shared in proportion to the mmToken balances
+        let pending_rewards = get_pending_rewards(&app, addr.clone(), "user");
+        let expected_rewards =
+            EMISSION_PER_SECOND * 100 * user_mm_token_balance / total_mm_token_supply;
+
+        assert!(pending_rewards <= expected_rewards);
+        assert!(pending_rewards >= expected_rewards - 10);
+
+        // the redeem checkpoints the rewards accrued on the previous balance
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Redeem {
+                denom: "eth".to_string(),
+                amount: Uint128::from(10u128.pow(18)),
+                recipient: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_pending_rewards(&app, addr.clone(), "user"),
+            pending_rewards
+        );
+
+        skip_time(&mut app, 100);
+
+        assert!(get_pending_rewards(&app, addr.clone(), "user") > pending_rewards);
+    }
+
+    #[test]
+    fn test_fail_add_emission_schedule() {
+        let (mut app, addr) = success_borrow_setup();
+
+        let total_emission = EMISSION_PER_SECOND * DURATION as u128;
+
+        assert_eq!(
+            add_emission_schedule(
+                &mut app,
+                addr.clone(),
+                "user",
+                RewardSide::Supply,
+                "reward",
+                total_emission,
+            ),
+            Some("Allowed for Admin Only".to_string())
+        );
+
+        // rewards in the token of a market would be mixed with its liquidity
+        assert_eq!(
+            add_emission_schedule(
+                &mut app,
+                addr.clone(),
+                "owner",
+                RewardSide::Supply,
+                "atom",
+                total_emission,
+            ),
+            Some("Invalid Emission Schedule".to_string())
+        );
+
+        assert_eq!(
+            add_emission_schedule(
+                &mut app,
+                addr.clone(),
+                "owner",
+                RewardSide::Supply,
+                "reward",
+                total_emission / 2,
+            ),
+            Some("InvalidFunds: Funds must cover the whole emission of the schedule".to_string())
+        );
+    }
+}
@@ -0,0 +1,374 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{ExecuteMsg, QueryMsg, RateMode, TotalBorrowData, UserBorrowingInfo};
+
+    const PERCENT_DECIMALS: u32 = 5;
+    const INTEREST_RATE_DECIMALS: u32 = 18;
+    const TOKENS_DECIMALS: u32 = 18;
+
+    const BORROW_AMOUNT_ETH: u128 = 50 * 10u128.pow(TOKENS_DECIMALS);
+    const STABLE_BORROW_AMOUNT_ETH: u128 = 10 * 10u128.pow(TOKENS_DECIMALS);
+    const TERM_BORROW_AMOUNT_ETH: u128 = 5 * 10u128.pow(TOKENS_DECIMALS);
+
+    const DAY_IN_SECONDS: u64 = 24 * 60 * 60;
+
+    fn set_stable_rate_configuration(
+        app: &mut BasicApp,
+        addr: Addr,
+        sender: &str,
+        min_interest_rate: u128,
+        rebalance_utilisation_ratio: u128,
+    ) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            addr,
+            &ExecuteMsg::SetStableRateConfiguration {
+                denom: "eth".to_string(),
+                min_interest_rate,
+                safe_borrow_max_rate: 40 * 10u128.pow(INTEREST_RATE_DECIMALS),
+                rate_growth_factor: 100 * 10u128.pow(INTEREST_RATE_DECIMALS),
+                optimal_utilisation_ratio: 80 * 10u128.pow(PERCENT_DECIMALS),
+                rebalance_utilisation_ratio,
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn stable_borrow(app: &mut BasicApp, addr: Addr, amount: u128) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(amount),
+                rate_mode: Some(RateMode::Stable),
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn get_stable_interest_rate(app: &BasicApp, addr: Addr) -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetStableInterestRate {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    fn get_user_stable_borrowing_info(app: &BasicApp, addr: Addr) -> UserBorrowingInfo {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetUserStableBorrowingInfo {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    fn get_user_borrowing_info(app: &BasicApp, addr: Addr) -> UserBorrowingInfo {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetUserBorrowingInfo {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_success_stable_borrow() {
+        // user deposited 200 ETH and 300 ATOM as collateral and borrowed 50 ETH at variable rate
+        let (mut app, addr) = success_borrow_setup();
+
+        assert_eq!(
+            set_stable_rate_configuration(
+                &mut app,
+                addr.clone(),
+                "owner",
+                8 * 10u128.pow(INTEREST_RATE_DECIMALS),
+                90 * 10u128.pow(PERCENT_DECIMALS),
+            ),
+            None
+        );
+
+        let utilization_rate: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUtilizationRateByToken {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let stable_interest_rate = get_stable_interest_rate(&app, addr.clone());
+
+        assert_eq!(
+            stable_interest_rate.u128(),
+            8 * 10u128.pow(INTEREST_RATE_DECIMALS)
+                + utilization_rate.u128() * (32 * 10u128.pow(INTEREST_RATE_DECIMALS))
+                    / (80 * 10u128.pow(PERCENT_DECIMALS))
+        );
+
+        assert_eq!(
+            stable_borrow(&mut app, addr.clone(), STABLE_BORROW_AMOUNT_ETH),
+            None
+        );
+
+        let user_stable_borrowing_info = get_user_stable_borrowing_info(&app, addr.clone());
+
+        assert_eq!(
+            user_stable_borrowing_info.borrowed_amount.u128(),
+            STABLE_BORROW_AMOUNT_ETH
+        );
+        assert_eq!(
+            user_stable_borrowing_info.average_interest_rate,
+            stable_interest_rate
+        );
+
+        // variable position is not changed by the stable borrow
+        assert_eq!(
+            get_user_borrowing_info(&app, addr.clone())
+                .borrowed_amount
+                .u128(),
+            BORROW_AMOUNT_ETH
+        );
+
+        let user_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            user_borrow_amount_with_interest.u128(),
+            BORROW_AMOUNT_ETH + STABLE_BORROW_AMOUNT_ETH
+        );
+
+        // term loans are part of the total but not of the stable debt
+        let maturity = app.block_info().time.plus_seconds(30 * DAY_IN_SECONDS);
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(TERM_BORROW_AMOUNT_ETH),
+                rate_mode: Some(RateMode::Term { maturity }),
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let total_borrow_data: TotalBorrowData = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetTotalBorrowData {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            total_borrow_data.total_borrowed_amount.u128(),
+            BORROW_AMOUNT_ETH + STABLE_BORROW_AMOUNT_ETH + TERM_BORROW_AMOUNT_ETH
+        );
+        assert_eq!(
+            total_borrow_data.variable_borrowed_amount.u128(),
+            BORROW_AMOUNT_ETH
+        );
+        assert_eq!(
+            total_borrow_data.term_borrowed_amount.u128(),
+            TERM_BORROW_AMOUNT_ETH
+        );
+        assert_eq!(
+            total_borrow_data.stable_borrowed_amount.u128(),
+            STABLE_BORROW_AMOUNT_ETH
+        );
+        assert_eq!(
+            total_borrow_data.stable_average_interest_rate,
+            stable_interest_rate
+        );
+
+        // the rate of the position stays locked while the stable rate follows utilisation
+        assert!(get_stable_interest_rate(&app, addr.clone()) > stable_interest_rate);
+        assert_eq!(
+            get_user_stable_borrowing_info(&app, addr.clone()).average_interest_rate,
+            stable_interest_rate
+        );
+    }
+
+    #[test]
+    fn test_success_repay_stable_debt_first() {
+        let (mut app, addr) = success_borrow_setup();
+
+        set_stable_rate_configuration(
+            &mut app,
+            addr.clone(),
+            "owner",
+            8 * 10u128.pow(INTEREST_RATE_DECIMALS),
+            90 * 10u128.pow(PERCENT_DECIMALS),
+        );
+
+        stable_borrow(&mut app, addr.clone(), STABLE_BORROW_AMOUNT_ETH);
+
+        const REPAY_AMOUNT_ETH: u128 = 15 * 10u128.pow(TOKENS_DECIMALS);
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Repay { on_behalf_of: None },
+            &coins(REPAY_AMOUNT_ETH, "eth"),
+        )
+        .unwrap();
+
+        assert!(get_user_stable_borrowing_info(&app, addr.clone())
+            .borrowed_amount
+            .is_zero());
+
+        assert_eq!(
+            get_user_borrowing_info(&app, addr.clone())
+                .borrowed_amount
+                .u128(),
+            BORROW_AMOUNT_ETH + STABLE_BORROW_AMOUNT_ETH - REPAY_AMOUNT_ETH
+        );
+
+        let total_borrow_data: TotalBorrowData = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetTotalBorrowData {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(total_borrow_data.stable_borrowed_amount.is_zero());
+        assert_eq!(
+            total_borrow_data.variable_borrowed_amount,
+            total_borrow_data.total_borrowed_amount
+        );
+    }
+
+    #[test]
+    fn test_rebalance_stable_rate() {
+        let (mut app, addr) = success_borrow_setup();
+
+        set_stable_rate_configuration(
+            &mut app,
+            addr.clone(),
+            "owner",
+            8 * 10u128.pow(INTEREST_RATE_DECIMALS),
+            90 * 10u128.pow(PERCENT_DECIMALS),
+        );
+
+        stable_borrow(&mut app, addr.clone(), STABLE_BORROW_AMOUNT_ETH);
+
+        let rebalance = |app: &mut BasicApp| {
+            app.execute_contract(
+                Addr::unchecked("anyone"),
+                addr.clone(),
+                &ExecuteMsg::RebalanceStableRate {
+                    user: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+                &[],
+            )
+            .err()
+            .map(|error| error.root_cause().to_string())
+        };
+
+        // utilisation is below the rebalance threshold
+        assert_eq!(
+            rebalance(&mut app),
+            Some("Stable Rate Rebalance Is Not Allowed".to_string())
+        );
+
+        // stable curve is raised and the threshold is passed
+        set_stable_rate_configuration(
+            &mut app,
+            addr.clone(),
+            "owner",
+            20 * 10u128.pow(INTEREST_RATE_DECIMALS),
+            10u128.pow(PERCENT_DECIMALS),
+        );
+
+        let stable_interest_rate = get_stable_interest_rate(&app, addr.clone());
+
+        assert_eq!(rebalance(&mut app), None);
+
+        let user_stable_borrowing_info = get_user_stable_borrowing_info(&app, addr.clone());
+
+        assert_eq!(
+            user_stable_borrowing_info.average_interest_rate,
+            stable_interest_rate
+        );
+        assert_eq!(
+            user_stable_borrowing_info.borrowed_amount.u128(),
+            STABLE_BORROW_AMOUNT_ETH
+        );
+
+        // position is already at the current stable rate
+        assert_eq!(
+            rebalance(&mut app),
+            Some("Stable Rate Rebalance Is Not Allowed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fail_stable_borrow() {
+        let (mut app, addr) = success_borrow_setup();
+
+        assert_eq!(
+            stable_borrow(&mut app, addr.clone(), STABLE_BORROW_AMOUNT_ETH),
+            Some("Stable Rate Borrowing Is Not Enabled".to_string())
+        );
+
+        assert_eq!(
+            set_stable_rate_configuration(
+                &mut app,
+                addr.clone(),
+                "user",
+                8 * 10u128.pow(INTEREST_RATE_DECIMALS),
+                90 * 10u128.pow(PERCENT_DECIMALS),
+            ),
+            Some("Allowed for Admin or Risk Manager Only".to_string())
+        );
+
+        // min rate is above the rate at the optimal utilisation
+        assert_eq!(
+            set_stable_rate_configuration(
+                &mut app,
+                addr.clone(),
+                "owner",
+                50 * 10u128.pow(INTEREST_RATE_DECIMALS),
+                90 * 10u128.pow(PERCENT_DECIMALS),
+            ),
+            Some("Invalid Stable Rate Configuration".to_string())
+        );
+    }
+}
@@ -0,0 +1,295 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{coins, Addr, BlockInfo, Timestamp, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{
+        ExecuteMsg, GetBalanceResponse, QueryMsg, RateMode, TermLoan, TotalBorrowData,
+    };
+
+    const TOKENS_DECIMALS: u32 = 18;
+
+    const BORROW_AMOUNT_ETH: u128 = 50 * 10u128.pow(TOKENS_DECIMALS);
+    const TERM_BORROW_AMOUNT_ETH: u128 = 10 * 10u128.pow(TOKENS_DECIMALS);
+
+    const DAY_IN_SECONDS: u64 = 24 * 60 * 60;
+
+    fn term_borrow(app: &mut BasicApp, addr: Addr, maturity: Timestamp) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::Borrow {
+                denom: "eth".to_string(),
+                amount: Uint128::from(TERM_BORROW_AMOUNT_ETH),
+                rate_mode: Some(RateMode::Term { maturity }),
+                recipient: None,
+                on_behalf_of: None,
+            },
+            &[],
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn liquidate_term_loan(app: &mut BasicApp, addr: Addr, loan_id: u64) -> Option<String> {
+        app.execute_contract(
+            Addr::unchecked("liquidator"),
+            addr,
+            &ExecuteMsg::LiquidateTermLoan {
+                loan_id,
+                collateral_denom: "eth".to_string(),
+                receive_mm_token: false,
+            },
+            &coins(2 * TERM_BORROW_AMOUNT_ETH, "eth"),
+        )
+        .err()
+        .map(|error| error.root_cause().to_string())
+    }
+
+    fn get_user_term_loans(app: &BasicApp, addr: Addr) -> Vec<TermLoan> {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetUserTermLoans {
+                    address: "user".to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    fn get_total_borrow_data(app: &BasicApp, addr: Addr) -> TotalBorrowData {
+        app.wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::GetTotalBorrowData {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    fn skip_time(app: &mut BasicApp, seconds: u64) {
+        app.set_block(BlockInfo {
+            height: app.block_info().height + 1,
+            time: app.block_info().time.plus_seconds(seconds),
+            chain_id: app.block_info().chain_id,
+        });
+    }
+
+    #[test]
+    fn test_success_term_borrow_and_repay() {
+        // user deposited 200 ETH and 300 ATOM as collateral and borrowed 50 ETH at variable rate
+        let (mut app, addr) = success_borrow_setup();
+
+        let interest_rate: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetInterestRate {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        let maturity = app.block_info().time.plus_seconds(30 * DAY_IN_SECONDS);
+
+        assert_eq!(term_borrow(&mut app, addr.clone(), maturity), None);
+
+        let user_term_loans = get_user_term_loans(&app, addr.clone());
+
+        assert_eq!(user_term_loans.len(), 1);
+        assert_eq!(user_term_loans[0].borrower, "user".to_string());
+        assert_eq!(
+            user_term_loans[0].borrowed_amount.u128(),
+            TERM_BORROW_AMOUNT_ETH
+        );
+        assert_eq!(user_term_loans[0].interest_rate, interest_rate);
+        assert_eq!(user_term_loans[0].maturity, maturity);
+
+        let user_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            user_borrow_amount_with_interest.u128(),
+            BORROW_AMOUNT_ETH + TERM_BORROW_AMOUNT_ETH
+        );
+
+        // term loans are tracked apart from the stable debt of the market
+        let total_borrow_data = get_total_borrow_data(&app, addr.clone());
+
+        assert_eq!(
+            total_borrow_data.term_borrowed_amount.u128(),
+            TERM_BORROW_AMOUNT_ETH
+        );
+        assert!(total_borrow_data.stable_borrowed_amount.is_zero());
+        assert_eq!(
+            total_borrow_data.variable_borrowed_amount.u128(),
+            BORROW_AMOUNT_ETH
+        );
+
+        skip_time(&mut app, 10 * DAY_IN_SECONDS);
+
+        let loan_id = user_term_loans[0].id;
+
+        let term_loan_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetTermLoanAmountWithInterest { loan_id },
+            )
+            .unwrap();
+
+        assert!(term_loan_amount_with_interest.u128() > TERM_BORROW_AMOUNT_ETH);
+
+        let user_balance_before_repay = app.wrap().query_balance("user", "eth").unwrap().amount;
+
+        // repay before maturity, the excess is returned
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::RepayTermLoan { loan_id },
+            &coins(2 * TERM_BORROW_AMOUNT_ETH, "eth"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap().query_balance("user", "eth").unwrap().amount,
+            user_balance_before_repay - term_loan_amount_with_interest
+        );
+
+        assert!(get_user_term_loans(&app, addr.clone()).is_empty());
+        assert!(get_total_borrow_data(&app, addr.clone())
+            .term_borrowed_amount
+            .is_zero());
+    }
+
+    #[test]
+    fn test_success_liquidate_overdue_term_loan() {
+        let (mut app, addr) = success_borrow_setup();
+
+        let maturity = app.block_info().time.plus_seconds(DAY_IN_SECONDS);
+
+        term_borrow(&mut app, addr.clone(), maturity);
+
+        let loan_id = get_user_term_loans(&app, addr.clone())[0].id;
+
+        // healthy position is not liquidatable before maturity
+        assert_eq!(
+            liquidate_term_loan(&mut app, addr.clone(), loan_id),
+            Some("User Borrowing Has Not Reached The Liquidation Threshold".to_string())
+        );
+
+        let overdue_term_loans: Vec<TermLoan> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetOverdueTermLoans {})
+            .unwrap();
+
+        assert!(overdue_term_loans.is_empty());
+
+        skip_time(&mut app, 2 * DAY_IN_SECONDS);
+
+        let overdue_term_loans: Vec<TermLoan> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetOverdueTermLoans {})
+            .unwrap();
+
+        assert_eq!(overdue_term_loans.len(), 1);
+        assert_eq!(overdue_term_loans[0].id, loan_id);
+
+        let user_deposit_before: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        // overdue loan is repaid in full, the close factor does not apply
+        assert_eq!(liquidate_term_loan(&mut app, addr.clone(), loan_id), None);
+
+        assert!(get_user_term_loans(&app, addr.clone()).is_empty());
+
+        let user_deposit_after: GetBalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetDeposit {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(
+            user_deposit_before.balance.u128() - user_deposit_after.balance.u128()
+                > TERM_BORROW_AMOUNT_ETH
+        );
+
+        // variable debt of the user is not touched
+        let user_borrow_amount_with_interest: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetUserBorrowAmountWithInterest {
+                    address: "user".to_string(),
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(user_borrow_amount_with_interest.u128() >= BORROW_AMOUNT_ETH);
+        assert!(
+            user_borrow_amount_with_interest.u128() < BORROW_AMOUNT_ETH + TERM_BORROW_AMOUNT_ETH
+        );
+    }
+
+    #[test]
+    fn test_fail_term_borrow() {
+        let (mut app, addr) = success_borrow_setup();
+
+        let now = app.block_info().time;
+
+        assert_eq!(
+            term_borrow(&mut app, addr.clone(), now),
+            Some("Invalid Maturity".to_string())
+        );
+
+        assert_eq!(
+            app.execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::RepayTermLoan { loan_id: 0 },
+                &coins(TERM_BORROW_AMOUNT_ETH, "eth"),
+            )
+            .err()
+            .map(|error| error.root_cause().to_string()),
+            Some("Term Loan Not Found".to_string())
+        );
+
+        term_borrow(&mut app, addr.clone(), now.plus_seconds(DAY_IN_SECONDS));
+
+        // funds in another token are rejected
+        assert_eq!(
+            app.execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::RepayTermLoan { loan_id: 0 },
+                &coins(TERM_BORROW_AMOUNT_ETH, "atom"),
+            )
+            .err()
+            .map(|error| error.root_cause().to_string()),
+            Some("InvalidFunds: Funds must be in the token of the loan".to_string())
+        );
+    }
+}
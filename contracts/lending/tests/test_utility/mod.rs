@@ -1,4 +1,5 @@
 mod test_add_markets;
+mod test_get_account_snapshot;
 mod test_get_all_user_with_borrow;
 mod test_get_available_liquidity_by_token;
 mod test_get_available_to_borrow;
@@ -17,7 +17,7 @@ mod tests {
     const OPTIMAL_UTILISATION_RATIO: u128 = 80 * 10u128.pow(PERCENT_DECIMALS);
 
     use crate::utils::success_deposit_of_one_token_setup;
-    use cosmwasm_std::{Addr, Uint128};
+    use cosmwasm_std::Addr;
     use cw_multi_test::Executor;
     use lending::msg::{
         ExecuteMsg, GetReserveConfigurationResponse, GetSupportedTokensResponse, QueryMsg,
@@ -56,13 +56,22 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("owner"),
             addr.clone(),
-            &ExecuteMsg::UpdateAdmin {
-                admin: "admin".to_string(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "admin".to_string(),
+                expiry: None,
             },
             &[],
         )
         .unwrap();
 
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            addr.clone(),
+            &ExecuteMsg::AcceptOwnership {},
+            &[],
+        )
+        .unwrap();
+
         let new_admin: String = app
             .wrap()
             .query_wasm_smart(addr.clone(), &QueryMsg::GetAdmin {})
@@ -70,25 +79,29 @@ mod tests {
 
         assert_eq!(new_admin, "admin");
 
-        app.execute_contract(
-            Addr::unchecked("admin"),
-            addr.clone(),
-            &ExecuteMsg::AddMarkets {
-                denom: "eth".to_string(),
-                name: "ethereum".to_string(),
-                symbol: "ETH".to_string(),
-                decimals: TOKENS_DECIMALS as u128,
-                cw20_address: None,
-                loan_to_value_ratio: LTV_TIA,
-                liquidation_threshold: LIQUIDATION_THRESHOLD_TIA,
-                min_interest_rate: MIN_INTEREST_RATE,
-                safe_borrow_max_rate: SAFE_BORROW_MAX_RATE,
-                rate_growth_factor: RATE_GROWTH_FACTOR,
-                optimal_utilisation_ratio: OPTIMAL_UTILISATION_RATIO,
-            },
-            &[],
-        )
-        .unwrap();
+        // a listed market isn't overwritten
+        let error = app
+            .execute_contract(
+                Addr::unchecked("admin"),
+                addr.clone(),
+                &ExecuteMsg::AddMarkets {
+                    denom: "eth".to_string(),
+                    name: "ethereum".to_string(),
+                    symbol: "ETH".to_string(),
+                    decimals: TOKENS_DECIMALS as u128,
+                    cw20_address: None,
+                    loan_to_value_ratio: LTV_TIA,
+                    liquidation_threshold: LIQUIDATION_THRESHOLD_TIA,
+                    min_interest_rate: MIN_INTEREST_RATE,
+                    safe_borrow_max_rate: SAFE_BORROW_MAX_RATE,
+                    rate_growth_factor: RATE_GROWTH_FACTOR,
+                    optimal_utilisation_ratio: OPTIMAL_UTILISATION_RATIO,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Market Already Exists: eth");
 
         let reserve_configuration_response: GetReserveConfigurationResponse = app
             .wrap()
@@ -96,9 +109,9 @@ mod tests {
             .unwrap();
 
         for el in reserve_configuration_response.reserve_configuration {
-            if el.denom == "eth".to_string() {
-                assert_eq!(el.loan_to_value_ratio, LTV_TIA);
-                assert_eq!(el.liquidation_threshold, LIQUIDATION_THRESHOLD_TIA);
+            if el.denom == "eth" {
+                assert_eq!(el.loan_to_value_ratio, LTV_ETH);
+                assert_eq!(el.liquidation_threshold, LIQUIDATION_THRESHOLD_ETH);
             }
         }
     }
@@ -110,13 +123,22 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("owner"),
             addr.clone(),
-            &ExecuteMsg::UpdateAdmin {
-                admin: "admin".to_string(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "admin".to_string(),
+                expiry: None,
             },
             &[],
         )
         .unwrap();
 
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            addr.clone(),
+            &ExecuteMsg::AcceptOwnership {},
+            &[],
+        )
+        .unwrap();
+
         let new_admin: String = app
             .wrap()
             .query_wasm_smart(addr.clone(), &QueryMsg::GetAdmin {})
@@ -149,9 +171,6 @@ mod tests {
             .query_wasm_smart(addr.clone(), &QueryMsg::GetSupportedTokens {})
             .unwrap();
 
-        println!(
-            "{}",
-            format!("{:?}", supported_tokens_response.supported_tokens)
-        );
+        println!("{:?}", supported_tokens_response.supported_tokens);
     }
 }
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::success_borrow_setup;
+    use cosmwasm_std::{BlockInfo, Timestamp, Uint128};
+    use lending::msg::{AccountSnapshot, QueryMsg};
+
+    #[test]
+    fn test_get_account_snapshot() {
+        const YEAR_IN_SECONDS: u64 = 31536000;
+
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        // user borrowed 50 ETH
+        let (mut app, addr) = success_borrow_setup();
+
+        let block_time = app.block_info().time.seconds();
+
+        app.set_block(BlockInfo {
+            height: 542,
+            time: Timestamp::from_seconds(block_time + YEAR_IN_SECONDS), // after 1 year
+            chain_id: "custom_chain_id".to_string(),
+        });
+
+        let account_snapshot: AccountSnapshot = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetAccountSnapshot {
+                    address: "user".to_string(),
+                },
+            )
+            .unwrap();
+
+        let query_uint128 =
+            |msg: QueryMsg| -> Uint128 { app.wrap().query_wasm_smart(addr.clone(), &msg).unwrap() };
+
+        // the snapshot matches the separate queries
+        assert_eq!(
+            account_snapshot.collateral_usd,
+            query_uint128(QueryMsg::GetUserCollateralUsd {
+                address: "user".to_string(),
+            })
+        );
+        assert_eq!(
+            account_snapshot.borrowed_usd,
+            query_uint128(QueryMsg::GetUserBorrowedUsd {
+                address: "user".to_string(),
+            })
+        );
+        assert_eq!(
+            account_snapshot.deposited_usd,
+            query_uint128(QueryMsg::GetUserDepositedUsd {
+                address: "user".to_string(),
+            })
+        );
+        assert_eq!(
+            account_snapshot.utilization_rate,
+            query_uint128(QueryMsg::GetUserUtilizationRate {
+                address: "user".to_string(),
+            })
+        );
+        assert_eq!(
+            account_snapshot.liquidation_threshold,
+            query_uint128(QueryMsg::GetUserLiquidationThreshold {
+                address: "user".to_string(),
+            })
+        );
+        assert_eq!(
+            account_snapshot.borrow_capacity_usd,
+            query_uint128(QueryMsg::GetUserMaxAllowedBorrowAmountUsd {
+                address: "user".to_string(),
+            })
+        );
+
+        assert_eq!(account_snapshot.markets.len(), 2);
+
+        let market_eth = account_snapshot
+            .markets
+            .iter()
+            .find(|market| market.denom == "eth")
+            .unwrap();
+
+        assert!(market_eth.use_as_collateral);
+        assert_eq!(market_eth.price.u128(), 200000000000); // 2000$
+        assert_eq!(market_eth.loan_to_value_ratio, 8500000); // 85%
+        assert_eq!(market_eth.liquidation_threshold, 9000000); // 90%
+        assert_eq!(
+            market_eth.borrowed,
+            query_uint128(QueryMsg::GetUserBorrowAmountWithInterest {
+                address: "user".to_string(),
+                denom: "eth".to_string(),
+            })
+        );
+
+        // position is healthy: health factor is above 100%
+        let health_factor = account_snapshot.health_factor.unwrap().u128();
+        assert!(health_factor > 10000000);
+        assert_eq!(
+            health_factor,
+            account_snapshot.liquidation_threshold_usd.u128() * 10000000
+                / account_snapshot.borrowed_usd.u128()
+        );
+        assert_eq!(
+            account_snapshot.distance_to_liquidation_usd.u128(),
+            account_snapshot.liquidation_threshold_usd.u128()
+                - account_snapshot.borrowed_usd.u128()
+        );
+
+        let liquidator_snapshot: AccountSnapshot = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetAccountSnapshot {
+                    address: "liquidator".to_string(),
+                },
+            )
+            .unwrap();
+
+        // no borrows - no health factor
+        assert_eq!(liquidator_snapshot.health_factor, None);
+        assert_eq!(liquidator_snapshot.borrowed_usd.u128(), 0);
+    }
+}
@@ -12,9 +12,6 @@ mod tests {
             .query_wasm_smart(addr.clone(), &QueryMsg::GetSupportedTokens {})
             .unwrap();
 
-        println!(
-            "{}",
-            format!("{:?}", supported_tokens_response.supported_tokens)
-        );
+        println!("{:?}", supported_tokens_response.supported_tokens);
     }
 }
@@ -43,16 +43,24 @@ mod tests {
 
         // user hasn't borrowed anything yet
         assert_eq!(total_borrow_data_eth.denom, "eth");
-        assert_eq!(total_borrow_data_eth.total_borrowed_amount, 0);
-        assert_eq!(total_borrow_data_eth.expected_annual_interest_income, 0);
-        assert_eq!(total_borrow_data_eth.average_interest_rate, 0);
+        assert_eq!(total_borrow_data_eth.total_borrowed_amount.u128(), 0);
+        assert_eq!(
+            total_borrow_data_eth.expected_annual_interest_income.u128(),
+            0
+        );
+        assert_eq!(total_borrow_data_eth.average_interest_rate.u128(), 0);
         assert!(total_borrow_data_eth.timestamp < Timestamp::from_seconds(now));
 
         // user hasn't borrowed anything yet
         assert_eq!(total_borrow_data_atom.denom, "atom");
-        assert_eq!(total_borrow_data_atom.total_borrowed_amount, 0);
-        assert_eq!(total_borrow_data_atom.expected_annual_interest_income, 0);
-        assert_eq!(total_borrow_data_atom.average_interest_rate, 0);
+        assert_eq!(total_borrow_data_atom.total_borrowed_amount.u128(), 0);
+        assert_eq!(
+            total_borrow_data_atom
+                .expected_annual_interest_income
+                .u128(),
+            0
+        );
+        assert_eq!(total_borrow_data_atom.average_interest_rate.u128(), 0);
         assert!(total_borrow_data_atom.timestamp < Timestamp::from_seconds(now));
 
         app.set_block(BlockInfo {
@@ -67,6 +75,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "eth".to_string(),
                 amount: Uint128::from(BORROW_AMOUNT_ETH),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -78,6 +89,9 @@ mod tests {
             &ExecuteMsg::Borrow {
                 denom: "atom".to_string(),
                 amount: Uint128::from(BORROW_AMOUNT_ATOM),
+                rate_mode: None,
+                recipient: None,
+                on_behalf_of: None,
             },
             &[],
         )
@@ -105,15 +119,15 @@ mod tests {
 
         assert_eq!(total_borrow_data_eth.denom, "eth");
         assert_eq!(
-            total_borrow_data_eth.total_borrowed_amount,
+            total_borrow_data_eth.total_borrowed_amount.u128(),
             50000000000000000000
         ); // 50 ETH
         assert_eq!(
-            total_borrow_data_eth.expected_annual_interest_income,
+            total_borrow_data_eth.expected_annual_interest_income.u128(),
             2500000000000000000
         ); // 2.5 ETH (5% borrow APY)
         assert_eq!(
-            total_borrow_data_eth.average_interest_rate,
+            total_borrow_data_eth.average_interest_rate.u128(),
             5000000000000000000
         ); // 5%
         assert_eq!(
@@ -123,15 +137,17 @@ mod tests {
 
         assert_eq!(total_borrow_data_atom.denom, "atom");
         assert_eq!(
-            total_borrow_data_atom.total_borrowed_amount,
+            total_borrow_data_atom.total_borrowed_amount.u128(),
             200000000000000000000
         ); // 200 ETH
         assert_eq!(
-            total_borrow_data_atom.expected_annual_interest_income,
+            total_borrow_data_atom
+                .expected_annual_interest_income
+                .u128(),
             10000000000000000000
         ); // 10 ETH (5% borrow APY)
         assert_eq!(
-            total_borrow_data_atom.average_interest_rate,
+            total_borrow_data_atom.average_interest_rate.u128(),
             5000000000000000000
         ); // 5%
         assert_eq!(
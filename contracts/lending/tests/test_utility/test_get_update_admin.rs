@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::utils::success_deposit_of_one_token_setup;
-    use cosmwasm_std::{Addr, Uint128};
+    use cosmwasm_std::Addr;
     use cw_multi_test::Executor;
     use lending::msg::{ExecuteMsg, QueryMsg};
 
@@ -19,13 +19,22 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("owner"),
             addr.clone(),
-            &ExecuteMsg::UpdateAdmin {
-                admin: "new_admin".to_string(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "new_admin".to_string(),
+                expiry: None,
             },
             &[],
         )
         .unwrap();
 
+        app.execute_contract(
+            Addr::unchecked("new_admin"),
+            addr.clone(),
+            &ExecuteMsg::AcceptOwnership {},
+            &[],
+        )
+        .unwrap();
+
         let new_admin: String = app
             .wrap()
             .query_wasm_smart(addr.clone(), &QueryMsg::GetAdmin {})
@@ -3,10 +3,8 @@ mod tests {
     const TOKENS_DECIMALS: u32 = 18;
     const PERCENT_DECIMALS: u32 = 5;
 
-    const LTV_ETH: u128 = 85 * 10u128.pow(PERCENT_DECIMALS);
     const LTV_TIA: u128 = 75 * 10u128.pow(PERCENT_DECIMALS);
 
-    const LIQUIDATION_THRESHOLD_ETH: u128 = 90 * 10u128.pow(PERCENT_DECIMALS);
     const LIQUIDATION_THRESHOLD_TIA: u128 = 90 * 10u128.pow(PERCENT_DECIMALS);
 
     const INTEREST_RATE_DECIMALS: u32 = 18;
@@ -17,12 +15,10 @@ mod tests {
     const OPTIMAL_UTILISATION_RATIO: u128 = 80 * 10u128.pow(PERCENT_DECIMALS);
 
     use crate::utils::success_deposit_of_one_token_setup;
-    use cosmwasm_std::{coins, Addr, Uint128};
+    use cosmwasm_std::Addr;
     use cw_multi_test::Executor;
     use lending::msg::QueryMsg::GetPriceFeedIds;
-    use lending::msg::{
-        ExecuteMsg, GetReserveConfigurationResponse, GetSupportedTokensResponse, QueryMsg,
-    };
+    use lending::msg::{ExecuteMsg, QueryMsg};
     use pyth_sdk_cw::PriceIdentifier;
 
     #[test]
@@ -32,13 +28,22 @@ mod tests {
         app.execute_contract(
             Addr::unchecked("owner"),
             addr.clone(),
-            &ExecuteMsg::UpdateAdmin {
-                admin: "admin".to_string(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "admin".to_string(),
+                expiry: None,
             },
             &[],
         )
         .unwrap();
 
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            addr.clone(),
+            &ExecuteMsg::AcceptOwnership {},
+            &[],
+        )
+        .unwrap();
+
         let new_admin: String = app
             .wrap()
             .query_wasm_smart(addr.clone(), &QueryMsg::GetAdmin {})
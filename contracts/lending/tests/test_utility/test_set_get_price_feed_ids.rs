@@ -3,14 +3,14 @@ mod tests {
     //     use super::*;
     use crate::utils::success_deposit_of_one_token_setup;
     //     use cosmwasm_schema::serde::__private::de::IdentifierDeserializer;
-    use cosmwasm_std::{Addr, Uint128};
+    use cosmwasm_std::Addr;
     use cw_multi_test::Executor;
     use lending::msg::{ExecuteMsg, QueryMsg};
     use pyth_sdk_cw::PriceIdentifier;
 
     #[test]
     fn test_get_pyth_contract() {
-        let (mut app, addr) = success_deposit_of_one_token_setup();
+        let (app, addr) = success_deposit_of_one_token_setup();
 
         let initial_price_feed_ids = vec![
             (
@@ -36,10 +36,6 @@ mod tests {
             ),
         ];
 
-        const PRICE_DECIMALS: u32 = 8;
-        const PRICE_ETH: u128 = 2000u128 * 10u128.pow(PRICE_DECIMALS);
-        const PRICE_ATOM: u128 = 10u128 * 10u128.pow(PRICE_DECIMALS);
-
         let price_feed_ids: Vec<(String, PriceIdentifier)> = app
             .wrap()
             .query_wasm_smart(addr.clone(), &QueryMsg::GetPriceFeedIds {})
@@ -76,11 +72,7 @@ mod tests {
             ),
         ];
 
-        const PRICE_DECIMALS: u32 = 8;
-        const PRICE_ETH: u128 = 2000u128 * 10u128.pow(PRICE_DECIMALS);
-        const PRICE_ATOM: u128 = 10u128 * 10u128.pow(PRICE_DECIMALS);
-
-        let mut price_feed_ids: Vec<(String, PriceIdentifier)> = app
+        let price_feed_ids: Vec<(String, PriceIdentifier)> = app
             .wrap()
             .query_wasm_smart(addr.clone(), &QueryMsg::GetPriceFeedIds {})
             .unwrap();
@@ -3,7 +3,7 @@ mod tests {
     //     use super::*;
     use crate::utils::success_deposit_of_one_token_setup;
     //     use cosmwasm_schema::serde::__private::de::IdentifierDeserializer;
-    use cosmwasm_std::{Addr, Uint128};
+    use cosmwasm_std::Addr;
     use cw_multi_test::Executor;
     use lending::msg::{ExecuteMsg, QueryMsg};
 
@@ -17,10 +17,6 @@ mod tests {
             "inj1z60tg0tekdzcasenhuuwq3htjcd5slmgf7gpez".to_string();
         let second_pyth_contract: String = "whatever-address-works".to_string();
 
-        const PRICE_DECIMALS: u32 = 8;
-        const PRICE_ETH: u128 = 2000u128 * 10u128.pow(PRICE_DECIMALS);
-        const PRICE_ATOM: u128 = 10u128 * 10u128.pow(PRICE_DECIMALS);
-
         let pyth_contract: String = app
             .wrap()
             .query_wasm_smart(addr.clone(), &QueryMsg::GetPythContract {})
@@ -0,0 +1,495 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::{success_deposit_of_diff_token_with_prices, PRICE_ID_ATOM};
+    use cosmwasm_std::{Addr, BlockInfo, Uint128};
+    use cw_multi_test::{BasicApp, Executor};
+    use lending::msg::{
+        EModeCategory, ExecuteMsg, GetReserveConfigurationResponse, IsolationConfiguration,
+        LiquidationConfiguration, OracleConfiguration, PriceSource, QueryMsg, QueuedAction, Role,
+    };
+    use pyth_sdk_cw::PriceIdentifier;
+
+    const PERCENT_DECIMALS: u32 = 5;
+    const LTV_ETH: u128 = 85 * 10u128.pow(PERCENT_DECIMALS); // 85%
+    const NEW_LTV_ETH: u128 = 70 * 10u128.pow(PERCENT_DECIMALS); // 70%
+    const LIQUIDATION_THRESHOLD_ETH: u128 = 90 * 10u128.pow(PERCENT_DECIMALS); // 90%
+    const TIMELOCK_DELAY: u64 = 2 * 24 * 60 * 60; // 2 days
+
+    fn set_timelock_delay(app: &mut BasicApp, addr: Addr) {
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::SetTimelockDelay {
+                delay: TIMELOCK_DELAY,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn get_ltv_eth(app: &BasicApp, addr: Addr) -> u128 {
+        let reserve_configuration_response: GetReserveConfigurationResponse = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::GetReserveConfiguration {})
+            .unwrap();
+
+        reserve_configuration_response
+            .reserve_configuration
+            .into_iter()
+            .find(|reserve_configuration| reserve_configuration.denom == "eth")
+            .unwrap()
+            .loan_to_value_ratio
+    }
+
+    fn skip_time(app: &mut BasicApp, seconds: u64) {
+        app.set_block(BlockInfo {
+            height: app.block_info().height + 1,
+            time: app.block_info().time.plus_seconds(seconds),
+            chain_id: app.block_info().chain_id,
+        });
+    }
+
+    #[test]
+    fn test_success_execute_queued_action() {
+        // contract reserves: 1000 ETH and 1000 ATOM
+        // user deposited 200 ETH and 300 ATOM
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_timelock_delay(&mut app, addr.clone());
+
+        let set_reserve_configuration = ExecuteMsg::SetReserveConfiguration {
+            denom: "eth".to_string(),
+            loan_to_value_ratio: NEW_LTV_ETH,
+            liquidation_threshold: LIQUIDATION_THRESHOLD_ETH,
+            supply_cap: None,
+            borrow_cap: None,
+        };
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &set_reserve_configuration,
+            &[],
+        )
+        .unwrap();
+
+        // the change is only queued
+        assert_eq!(get_ltv_eth(&app, addr.clone()), LTV_ETH);
+
+        let pending_actions: Vec<QueuedAction> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPendingActions {})
+            .unwrap();
+
+        assert_eq!(
+            pending_actions,
+            vec![QueuedAction {
+                id: 0,
+                msg: set_reserve_configuration,
+                proposer: Addr::unchecked("owner"),
+                eta: app.block_info().time.plus_seconds(TIMELOCK_DELAY),
+            }]
+        );
+
+        skip_time(&mut app, TIMELOCK_DELAY - 1);
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::ExecuteQueuedAction { id: 0 },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Queued Action Is Not Ready");
+
+        skip_time(&mut app, 1);
+
+        // anyone can execute the action once the delay has passed
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::ExecuteQueuedAction { id: 0 },
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(get_ltv_eth(&app, addr.clone()), NEW_LTV_ETH);
+
+        let pending_actions: Vec<QueuedAction> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPendingActions {})
+            .unwrap();
+
+        assert!(pending_actions.is_empty());
+    }
+
+    #[test]
+    fn test_success_queue_risk_parameter_actions() {
+        const USD_DECIMALS: u32 = 8;
+        const PRICE_ETH: u128 = 2000 * 10u128.pow(USD_DECIMALS); // 2000$
+        const NEW_PRICE_ETH: u128 = 2100 * 10u128.pow(USD_DECIMALS); // 2100$
+        const CLOSE_FACTOR: u128 = 40 * 10u128.pow(PERCENT_DECIMALS); // 40%
+        const LIQUIDATION_BONUS: u128 = 8 * 10u128.pow(PERCENT_DECIMALS); // 8%
+
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_timelock_delay(&mut app, addr.clone());
+
+        let actions = vec![
+            ExecuteMsg::SetLiquidationConfiguration {
+                denom: "eth".to_string(),
+                close_factor: CLOSE_FACTOR,
+                liquidation_bonus: LIQUIDATION_BONUS,
+            },
+            ExecuteMsg::SetEModeCategory {
+                id: 1,
+                label: "ETH".to_string(),
+                loan_to_value_ratio: LTV_ETH,
+                liquidation_threshold: LIQUIDATION_THRESHOLD_ETH,
+                price_source: None,
+                denoms: vec!["eth".to_string()],
+            },
+            ExecuteMsg::SetPriceSources {
+                denom: "eth".to_string(),
+                primary: PriceSource::Fixed {
+                    price: Uint128::from(NEW_PRICE_ETH),
+                },
+                secondary: None,
+                max_deviation: 0,
+            },
+        ];
+
+        for action in actions.iter() {
+            app.execute_contract(Addr::unchecked("owner"), addr.clone(), action, &[])
+                .unwrap();
+        }
+
+        let pending_actions: Vec<QueuedAction> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPendingActions {})
+            .unwrap();
+
+        assert_eq!(
+            pending_actions
+                .into_iter()
+                .map(|pending_action| pending_action.msg)
+                .collect::<Vec<_>>(),
+            actions
+        );
+
+        // none of the changes is applied before the delay
+        let price_eth: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(price_eth.u128(), PRICE_ETH);
+
+        let e_mode_categories: Vec<EModeCategory> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetEModeCategories {})
+            .unwrap();
+
+        assert!(e_mode_categories.is_empty());
+
+        skip_time(&mut app, TIMELOCK_DELAY);
+
+        for id in 0..actions.len() as u64 {
+            app.execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::ExecuteQueuedAction { id },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let liquidation_configuration: LiquidationConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetLiquidationConfiguration {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(liquidation_configuration.close_factor, CLOSE_FACTOR);
+
+        let price_eth: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetPrice {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(price_eth.u128(), NEW_PRICE_ETH);
+    }
+
+    #[test]
+    fn test_success_queue_oracle_and_isolation_actions() {
+        const MAX_CONFIDENCE_RATIO: u128 = 10 * 10u128.pow(PERCENT_DECIMALS); // 10%
+        const MAX_PRICE_AGE: u64 = 600;
+        const DEBT_CEILING: u128 = 1000 * 10u128.pow(8); // 1000$
+
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_timelock_delay(&mut app, addr.clone());
+
+        let price_feed_ids: Vec<(String, PriceIdentifier)> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPriceFeedIds {})
+            .unwrap();
+
+        let actions = [
+            ExecuteMsg::SetOracleConfiguration {
+                denom: "eth".to_string(),
+                max_confidence_ratio: MAX_CONFIDENCE_RATIO,
+                max_price_age: MAX_PRICE_AGE,
+            },
+            ExecuteMsg::SetIsolationConfiguration {
+                denom: "atom".to_string(),
+                is_isolated: true,
+                debt_ceiling: Uint128::from(DEBT_CEILING),
+                borrowable_in_isolation: false,
+            },
+            // the feed of a listed market is repointed
+            ExecuteMsg::AddPriceFeedIds {
+                price_ids: vec![(
+                    "eth".to_string(),
+                    PriceIdentifier::from_hex(PRICE_ID_ATOM).unwrap(),
+                )],
+            },
+            ExecuteMsg::RemovePriceFeedId {
+                denom: "atom".to_string(),
+            },
+        ];
+
+        for action in actions.iter() {
+            app.execute_contract(Addr::unchecked("owner"), addr.clone(), action, &[])
+                .unwrap();
+        }
+
+        let pending_actions: Vec<QueuedAction> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPendingActions {})
+            .unwrap();
+
+        assert_eq!(pending_actions.len(), actions.len());
+
+        // none of the changes is applied before the delay
+        let oracle_configuration: OracleConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetOracleConfiguration {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_ne!(oracle_configuration.max_price_age, MAX_PRICE_AGE);
+
+        let isolation_configuration: IsolationConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetIsolationConfiguration {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(!isolation_configuration.is_isolated);
+
+        let pending_price_feed_ids: Vec<(String, PriceIdentifier)> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPriceFeedIds {})
+            .unwrap();
+
+        assert_eq!(pending_price_feed_ids, price_feed_ids);
+
+        skip_time(&mut app, TIMELOCK_DELAY);
+
+        for id in 0..actions.len() as u64 {
+            app.execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::ExecuteQueuedAction { id },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let oracle_configuration: OracleConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetOracleConfiguration {
+                    denom: "eth".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(oracle_configuration.max_price_age, MAX_PRICE_AGE);
+
+        let isolation_configuration: IsolationConfiguration = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::GetIsolationConfiguration {
+                    denom: "atom".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(isolation_configuration.is_isolated);
+
+        let price_feed_ids: Vec<(String, PriceIdentifier)> = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPriceFeedIds {})
+            .unwrap();
+
+        assert!(price_feed_ids.contains(&(
+            "eth".to_string(),
+            PriceIdentifier::from_hex(PRICE_ID_ATOM).unwrap()
+        )));
+        assert!(!price_feed_ids.iter().any(|(denom, _)| denom == "atom"));
+    }
+
+    #[test]
+    fn test_success_cancel_queued_action() {
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        set_timelock_delay(&mut app, addr.clone());
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::GrantRole {
+                role: Role::PauseGuardian,
+                address: "guardian".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::UpdatePythContract {
+                pyth_contract_addr: "new_pyth".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::CancelQueuedAction { id: 0 },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Pause Guardian Only"
+        );
+
+        app.execute_contract(
+            Addr::unchecked("guardian"),
+            addr.clone(),
+            &ExecuteMsg::CancelQueuedAction { id: 0 },
+            &[],
+        )
+        .unwrap();
+
+        skip_time(&mut app, TIMELOCK_DELAY);
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::ExecuteQueuedAction { id: 0 },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Queued Action Not Found");
+
+        let pyth_contract: String = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetPythContract {})
+            .unwrap();
+
+        assert_ne!(pyth_contract, "new_pyth");
+    }
+
+    #[test]
+    fn test_fail_queue_action() {
+        let (mut app, addr) = success_deposit_of_diff_token_with_prices();
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::SetTimelockDelay {
+                    delay: 31 * 24 * 60 * 60,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(error.root_cause().to_string(), "Invalid Timelock Delay");
+
+        set_timelock_delay(&mut app, addr.clone());
+
+        let error = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::SetReserveConfiguration {
+                    denom: "eth".to_string(),
+                    loan_to_value_ratio: NEW_LTV_ETH,
+                    liquidation_threshold: LIQUIDATION_THRESHOLD_ETH,
+                    supply_cap: None,
+                    borrow_cap: None,
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Allowed for Admin or Risk Manager Only"
+        );
+
+        // changing the delay waits for the current one
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetTimelockDelay { delay: 0 },
+            &[],
+        )
+        .unwrap();
+
+        let delay: u64 = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::GetTimelockDelay {})
+            .unwrap();
+
+        assert_eq!(delay, TIMELOCK_DELAY);
+    }
+}
@@ -6,7 +6,7 @@ mod tests {
     use lending::msg::ExecuteMsg;
 
     #[test]
-    #[should_panic(expected = "Allowed for Admin Only")]
+    #[should_panic(expected = "Allowed for Admin or Risk Manager Only")]
     fn test_fail_set_tokens_interest_rate_model_params_if_caller_is_not_owner() {
         const PERCENT_DECIMALS: u32 = 5;
 
@@ -58,11 +58,8 @@ mod tests {
                 .unwrap();
 
         println!(
-            "{}",
-            format!(
-                "{:?}",
-                tokens_interest_rate_model_params_response.tokens_interest_rate_model_params
-            )
+            "{:?}",
+            tokens_interest_rate_model_params_response.tokens_interest_rate_model_params
         );
 
         assert_eq!(
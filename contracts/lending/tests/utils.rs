@@ -1,19 +1,26 @@
-use cosmwasm_std::{coin, coins, Addr, BlockInfo, Timestamp};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coin, coins, from_json, to_json_binary, Addr, BankMsg, Binary, BlockInfo, Deps, DepsMut, Empty,
+    Env, MessageInfo, Response, StdError, StdResult, Timestamp, Uint256, WasmMsg,
+};
 use cw_multi_test::{App, BasicApp, ContractWrapper, Executor};
+use cw_storage_plus::{Item, Map};
 use std::vec;
 
 use cosmwasm_std::Uint128;
 use cw20_base::contract::{
     execute as execute_cw20, instantiate as instantiate_cw20, query as query_cw20,
 };
-use cw20_base::msg::{
-    ExecuteMsg as ExecuteMsgCW20, InstantiateMsg as InstantiateMsgCW20, QueryMsg as QueryMsgCW20,
+use cw20_base::msg::InstantiateMsg as InstantiateMsgCW20;
+use lending::msg::{
+    ExecuteMsg, FlashLoanReceiverMsg, GetBalanceResponse, InstantiateMsg, QueryMsg,
 };
-use lending::msg::{ExecuteMsg, GetBalanceResponse, InstantiateMsg, QueryMsg};
-use lending::{execute, instantiate, query};
+use lending::oracle::{BandQueryMsg, BandReferenceData};
+use lending::{execute, instantiate, migrate, query, reply};
+use mm_token::msg::InstantiateMsg as InstantiateMsgMmToken;
 
 use cw20::{BalanceResponse, Cw20Coin, Cw20QueryMsg, MinterResponse};
-use pyth_sdk_cw::PriceIdentifier;
+use pyth_sdk_cw::{Price, PriceFeed, PriceFeedResponse, PriceIdentifier, QueryMsg as PythQueryMsg};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn success_deposit_of_one_token_setup() -> (BasicApp, Addr) {
@@ -71,7 +78,7 @@ pub fn success_deposit_of_one_token_setup() -> (BasicApp, Addr) {
             .unwrap();
     });
 
-    let code = ContractWrapper::new(execute, instantiate, query);
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     let code_id = app.store_code(Box::new(code));
 
     let addr = app
@@ -159,7 +166,7 @@ pub fn success_deposit_of_one_token_setup() -> (BasicApp, Addr) {
     app.execute_contract(
         Addr::unchecked("user"),
         addr.clone(),
-        &ExecuteMsg::Deposit {},
+        &ExecuteMsg::Deposit { on_behalf_of: None },
         &coins(FIRST_DEPOSIT_AMOUNT_ETH, "eth"),
     )
     .unwrap();
@@ -201,7 +208,7 @@ pub fn success_deposit_of_one_token_setup() -> (BasicApp, Addr) {
     app.execute_contract(
         Addr::unchecked("user"),
         addr.clone(),
-        &ExecuteMsg::Deposit {},
+        &ExecuteMsg::Deposit { on_behalf_of: None },
         &coins(SECOND_DEPOSIT_AMOUNT_ETH, "eth"),
     )
     .unwrap();
@@ -313,7 +320,7 @@ pub fn success_deposit_of_diff_token_with_prices() -> (BasicApp, Addr) {
             .unwrap();
     });
 
-    let code = ContractWrapper::new(execute, instantiate, query);
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     let code_id = app.store_code(Box::new(code));
 
     let addr = app
@@ -446,7 +453,7 @@ pub fn success_deposit_of_diff_token_with_prices() -> (BasicApp, Addr) {
     app.execute_contract(
         Addr::unchecked("user"),
         addr.clone(),
-        &ExecuteMsg::Deposit {},
+        &ExecuteMsg::Deposit { on_behalf_of: None },
         &coins(DEPOSIT_AMOUNT_ETH, "eth"),
     )
     .unwrap();
@@ -485,7 +492,7 @@ pub fn success_deposit_of_diff_token_with_prices() -> (BasicApp, Addr) {
     app.execute_contract(
         Addr::unchecked("user"),
         addr.clone(),
-        &ExecuteMsg::Deposit {},
+        &ExecuteMsg::Deposit { on_behalf_of: None },
         &coins(DEPOSIT_AMOUNT_ATOM, "atom"),
     )
     .unwrap();
@@ -644,7 +651,7 @@ pub fn success_borrow_setup() -> (BasicApp, Addr) {
             .unwrap();
     });
 
-    let code = ContractWrapper::new(execute, instantiate, query);
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     let code_id = app.store_code(Box::new(code));
 
     let addr = app
@@ -829,7 +836,7 @@ pub fn success_borrow_setup() -> (BasicApp, Addr) {
     app.execute_contract(
         Addr::unchecked("user"),
         addr.clone(),
-        &ExecuteMsg::Deposit {},
+        &ExecuteMsg::Deposit { on_behalf_of: None },
         &coins(DEPOSIT_AMOUNT_ETH, "eth"),
     )
     .unwrap();
@@ -885,7 +892,7 @@ pub fn success_borrow_setup() -> (BasicApp, Addr) {
     app.execute_contract(
         Addr::unchecked("user"),
         addr.clone(),
-        &ExecuteMsg::Deposit {},
+        &ExecuteMsg::Deposit { on_behalf_of: None },
         &coins(DEPOSIT_AMOUNT_ATOM, "atom"),
     )
     .unwrap();
@@ -939,6 +946,9 @@ pub fn success_borrow_setup() -> (BasicApp, Addr) {
         &ExecuteMsg::Borrow {
             denom: "eth".to_string(),
             amount: Uint128::from(BORROW_AMOUNT_ETH),
+            rate_mode: None,
+            recipient: None,
+            on_behalf_of: None,
         },
         &[],
     )
@@ -1008,7 +1018,7 @@ pub fn success_native_and_cw20_setup() -> (BasicApp, Addr, Addr) {
             .unwrap();
     });
 
-    let code = ContractWrapper::new(execute, instantiate, query);
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     let code_id = app.store_code(Box::new(code));
 
     let lending_addr = app
@@ -1186,7 +1196,7 @@ pub fn success_native_and_cw20_setup() -> (BasicApp, Addr, Addr) {
     app.execute_contract(
         Addr::unchecked("user"),
         lending_addr.clone(),
-        &ExecuteMsg::Deposit {},
+        &ExecuteMsg::Deposit { on_behalf_of: None },
         &coins(FIRST_DEPOSIT_AMOUNT_ETH, "eth"),
     )
     .unwrap();
@@ -1227,7 +1237,7 @@ pub fn success_native_and_cw20_setup() -> (BasicApp, Addr, Addr) {
     app.execute_contract(
         Addr::unchecked("user"),
         lending_addr.clone(),
-        &ExecuteMsg::Deposit {},
+        &ExecuteMsg::Deposit { on_behalf_of: None },
         &coins(SECOND_DEPOSIT_AMOUNT_ETH, "eth"),
     )
     .unwrap();
@@ -1268,3 +1278,509 @@ pub fn success_native_and_cw20_setup() -> (BasicApp, Addr, Addr) {
 
     (app, lending_addr, cw20_token_addr)
 }
+
+pub fn mm_token_setup(app: &mut BasicApp, lending_addr: Addr, denom: &str) -> Addr {
+    const TOKENS_DECIMALS: u8 = 18;
+
+    let mm_token_code =
+        ContractWrapper::new(mm_token::execute, mm_token::instantiate, mm_token::query);
+    let mm_token_code_id = app.store_code(Box::new(mm_token_code));
+
+    let mm_token_addr = app
+        .instantiate_contract(
+            mm_token_code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsgMmToken {
+                name: format!("iLend {}", denom),
+                symbol: format!("mm{}", denom.to_uppercase()),
+                decimals: TOKENS_DECIMALS,
+                lending_contract: lending_addr.to_string(),
+                denom: denom.to_string(),
+            },
+            &[],
+            "mmToken",
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        lending_addr,
+        &ExecuteMsg::RegisterMmToken {
+            denom: denom.to_string(),
+            mm_token_address: mm_token_addr.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    mm_token_addr
+}
+
+pub const PRICE_ID_ETH: &str = "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace";
+pub const PRICE_ID_ATOM: &str = "b00b60f88b03a6a625a8d1c048c3f66653edf217439983d037e7222c4e612819";
+
+#[cw_serde]
+pub enum MockPythExecuteMsg {
+    SetPriceFeed { price_feed: PriceFeed },
+}
+
+const MOCK_PYTH_PRICE_FEEDS: Map<String, PriceFeed> = Map::new("price_feeds");
+
+fn mock_pyth_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn mock_pyth_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockPythExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockPythExecuteMsg::SetPriceFeed { price_feed } => {
+            MOCK_PYTH_PRICE_FEEDS.save(deps.storage, price_feed.id.to_hex(), &price_feed)?;
+
+            Ok(Response::default())
+        }
+    }
+}
+
+fn mock_pyth_query(deps: Deps, _env: Env, msg: PythQueryMsg) -> StdResult<Binary> {
+    match msg {
+        PythQueryMsg::PriceFeed { id } => to_json_binary(&PriceFeedResponse {
+            price_feed: MOCK_PYTH_PRICE_FEEDS.load(deps.storage, id.to_hex())?,
+        }),
+        _ => Err(StdError::generic_err("Not supported by mock Pyth")),
+    }
+}
+
+pub fn set_pyth_price(app: &mut BasicApp, pyth_addr: Addr, price_id: &str, price: Price) {
+    let price_feed = PriceFeed::new(PriceIdentifier::from_hex(price_id).unwrap(), price, price);
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        pyth_addr,
+        &MockPythExecuteMsg::SetPriceFeed { price_feed },
+        &[],
+    )
+    .unwrap();
+}
+
+#[cw_serde]
+pub enum MockBandExecuteMsg {
+    SetReferenceData {
+        base_symbol: String,
+        rate: Uint256,
+        last_updated: u64,
+    },
+}
+
+const MOCK_BAND_REFERENCE_DATA: Map<String, BandReferenceData> = Map::new("reference_data");
+
+fn mock_band_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockBandExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockBandExecuteMsg::SetReferenceData {
+            base_symbol,
+            rate,
+            last_updated,
+        } => {
+            MOCK_BAND_REFERENCE_DATA.save(
+                deps.storage,
+                base_symbol,
+                &BandReferenceData {
+                    rate,
+                    last_updated_base: last_updated,
+                    last_updated_quote: last_updated,
+                },
+            )?;
+
+            Ok(Response::default())
+        }
+    }
+}
+
+fn mock_band_query(deps: Deps, _env: Env, msg: BandQueryMsg) -> StdResult<Binary> {
+    match msg {
+        BandQueryMsg::GetReferenceData { base_symbol, .. } => {
+            to_json_binary(&MOCK_BAND_REFERENCE_DATA.load(deps.storage, base_symbol)?)
+        }
+    }
+}
+
+pub fn band_setup(app: &mut BasicApp) -> Addr {
+    let band_code = ContractWrapper::new(mock_band_execute, mock_pyth_instantiate, mock_band_query);
+    let band_code_id = app.store_code(Box::new(band_code));
+
+    app.instantiate_contract(
+        band_code_id,
+        Addr::unchecked("owner"),
+        &Empty {},
+        &[],
+        "Band",
+        None,
+    )
+    .unwrap()
+}
+
+pub fn set_band_price(app: &mut BasicApp, band_addr: Addr, base_symbol: &str, rate: Uint256) {
+    let last_updated = app.block_info().time.seconds();
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        band_addr,
+        &MockBandExecuteMsg::SetReferenceData {
+            base_symbol: base_symbol.to_string(),
+            rate,
+            last_updated,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[cw_serde]
+pub enum MockFlashLoanAction {
+    RepayWithFee {},
+    RepayPrincipal {},
+    Deposit {},
+}
+
+fn mock_flash_loan_receiver_execute(
+    _deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: FlashLoanReceiverMsg,
+) -> StdResult<Response> {
+    match msg {
+        FlashLoanReceiverMsg::ExecuteOperation {
+            assets, fees, msg, ..
+        } => match from_json(msg)? {
+            MockFlashLoanAction::RepayWithFee {} => {
+                Ok(Response::new().add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: assets
+                        .into_iter()
+                        .zip(fees)
+                        .map(|(asset, fee)| coin((asset.amount + fee.amount).u128(), asset.denom))
+                        .collect(),
+                }))
+            }
+            MockFlashLoanAction::RepayPrincipal {} => {
+                Ok(Response::new().add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount: assets,
+                }))
+            }
+            // pool must not be usable with the borrowed funds
+            MockFlashLoanAction::Deposit {} => Ok(Response::new().add_message(WasmMsg::Execute {
+                contract_addr: info.sender.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Deposit { on_behalf_of: None })?,
+                funds: assets,
+            })),
+        },
+    }
+}
+
+pub fn flash_loan_receiver_setup(app: &mut BasicApp) -> Addr {
+    let receiver_code = ContractWrapper::new(
+        mock_flash_loan_receiver_execute,
+        mock_pyth_instantiate,
+        mock_pyth_query,
+    );
+    let receiver_code_id = app.store_code(Box::new(receiver_code));
+
+    app.instantiate_contract(
+        receiver_code_id,
+        Addr::unchecked("owner"),
+        &Empty {},
+        &[],
+        "Flash Loan Receiver",
+        None,
+    )
+    .unwrap()
+}
+
+#[cw_serde]
+pub struct MockLegacyInstantiateMsg {
+    pub contract_name: String,
+    pub contract_version: String,
+}
+
+// storage layout of the contract before versioned storage
+#[cw_serde]
+pub struct MockLegacyUserBorrowingInfo {
+    pub borrowed_amount: Uint128,
+    pub average_interest_rate: Uint128,
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct MockLegacyTotalBorrowData {
+    pub denom: String,
+    pub total_borrowed_amount: u128,
+    pub expected_annual_interest_income: u128,
+    pub average_interest_rate: u128,
+    pub timestamp: Timestamp,
+}
+
+const MOCK_LEGACY_USER_BORROWING_INFO: Map<(String, String), MockLegacyUserBorrowingInfo> =
+    Map::new("user_borrowing_info");
+const MOCK_LEGACY_TOTAL_BORROW_DATA: Map<String, MockLegacyTotalBorrowData> =
+    Map::new("total_borrow_data");
+
+pub const LEGACY_BORROWED_AMOUNT: u128 = 1000 * 10u128.pow(18); // 1000 ETH
+pub const LEGACY_INTEREST_RATE: u128 = 5 * 10u128.pow(16); // 5%
+pub const LEGACY_ANNUAL_INTEREST_INCOME: u128 = 50 * 10u128.pow(36);
+pub const LEGACY_TIMESTAMP: u64 = 1_700_000_000;
+
+fn mock_legacy_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockLegacyInstantiateMsg,
+) -> StdResult<Response> {
+    cw2::set_contract_version(deps.storage, msg.contract_name, msg.contract_version)?;
+
+    MOCK_LEGACY_USER_BORROWING_INFO.save(
+        deps.storage,
+        ("user".to_string(), "eth".to_string()),
+        &MockLegacyUserBorrowingInfo {
+            borrowed_amount: Uint128::from(LEGACY_BORROWED_AMOUNT),
+            average_interest_rate: Uint128::from(LEGACY_INTEREST_RATE),
+            timestamp: Timestamp::from_seconds(LEGACY_TIMESTAMP),
+        },
+    )?;
+
+    MOCK_LEGACY_TOTAL_BORROW_DATA.save(
+        deps.storage,
+        "eth".to_string(),
+        &MockLegacyTotalBorrowData {
+            denom: "eth".to_string(),
+            total_borrowed_amount: LEGACY_BORROWED_AMOUNT,
+            expected_annual_interest_income: LEGACY_ANNUAL_INTEREST_INCOME,
+            average_interest_rate: LEGACY_INTEREST_RATE,
+            timestamp: Timestamp::from_seconds(LEGACY_TIMESTAMP),
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cw_serde]
+pub enum MockLegacyExecuteMsg {
+    // simulates the storage written by a newer version of the contract
+    SetStorageVersion { version: u64 },
+}
+
+const MOCK_LEGACY_STORAGE_VERSION: Item<u64> = Item::new("storage_version");
+
+fn mock_legacy_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockLegacyExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockLegacyExecuteMsg::SetStorageVersion { version } => {
+            MOCK_LEGACY_STORAGE_VERSION.save(deps.storage, &version)?;
+
+            Ok(Response::default())
+        }
+    }
+}
+
+/// Instantiates a contract with the legacy storage layout and stores
+/// the current lending code to migrate it to
+pub fn legacy_lending_setup(contract_name: &str, contract_version: &str) -> (BasicApp, Addr, u64) {
+    let mut app = App::default();
+
+    let legacy_code = ContractWrapper::new(
+        mock_legacy_execute,
+        mock_legacy_instantiate,
+        mock_pyth_query,
+    );
+    let legacy_code_id = app.store_code(Box::new(legacy_code));
+
+    let addr = app
+        .instantiate_contract(
+            legacy_code_id,
+            Addr::unchecked("owner"),
+            &MockLegacyInstantiateMsg {
+                contract_name: contract_name.to_string(),
+                contract_version: contract_version.to_string(),
+            },
+            &[],
+            "Legacy Lending",
+            Some("owner".to_string()),
+        )
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query)
+        .with_reply(reply)
+        .with_migrate(migrate);
+    let code_id = app.store_code(Box::new(code));
+
+    (app, addr, code_id)
+}
+
+pub fn success_pyth_price_setup() -> (BasicApp, Addr, Addr) {
+    const TOKENS_DECIMALS: u32 = 18;
+
+    const PERCENT_DECIMALS: u32 = 5;
+    const LTV_ETH: u128 = 85 * 10u128.pow(PERCENT_DECIMALS); // 85%
+    const LIQUIDATION_THRESHOLD_ETH: u128 = 90 * 10u128.pow(PERCENT_DECIMALS); // 90%
+    const LTV_ATOM: u128 = 75 * 10u128.pow(PERCENT_DECIMALS); // 75%
+    const LIQUIDATION_THRESHOLD_ATOM: u128 = 80 * 10u128.pow(PERCENT_DECIMALS); // 80%
+
+    const INTEREST_RATE_DECIMALS: u32 = 18;
+    const MIN_INTEREST_RATE: u128 = 5 * 10u128.pow(INTEREST_RATE_DECIMALS);
+    const SAFE_BORROW_MAX_RATE: u128 = 30 * 10u128.pow(INTEREST_RATE_DECIMALS);
+    const RATE_GROWTH_FACTOR: u128 = 70 * 10u128.pow(INTEREST_RATE_DECIMALS);
+    const OPTIMAL_UTILISATION_RATIO: u128 = 80 * 10u128.pow(PERCENT_DECIMALS);
+
+    const INIT_BALANCE_ETH: u128 = 10_000 * 10u128.pow(TOKENS_DECIMALS); // 10_000 ETH
+    const INIT_BALANCE_ATOM: u128 = 10_000 * 10u128.pow(TOKENS_DECIMALS); // 10_000 ATOM
+
+    const CONTRACT_RESERVES_ETH: u128 = 1000 * 10u128.pow(TOKENS_DECIMALS);
+    const CONTRACT_RESERVES_ATOM: u128 = 1000 * 10u128.pow(TOKENS_DECIMALS);
+
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked("user"),
+                vec![
+                    coin(INIT_BALANCE_ETH, "eth"),
+                    coin(INIT_BALANCE_ATOM, "atom"),
+                ],
+            )
+            .unwrap();
+
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked("owner"),
+                vec![
+                    coin(CONTRACT_RESERVES_ETH, "eth"),
+                    coin(CONTRACT_RESERVES_ATOM, "atom"),
+                ],
+            )
+            .unwrap();
+    });
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    app.set_block(BlockInfo {
+        height: 0,
+        time: Timestamp::from_seconds(now),
+        chain_id: "custom_chain_id".to_string(),
+    });
+
+    let pyth_code = ContractWrapper::new(mock_pyth_execute, mock_pyth_instantiate, mock_pyth_query);
+    let pyth_code_id = app.store_code(Box::new(pyth_code));
+
+    let pyth_addr = app
+        .instantiate_contract(
+            pyth_code_id,
+            Addr::unchecked("owner"),
+            &Empty {},
+            &[],
+            "Pyth",
+            None,
+        )
+        .unwrap();
+
+    let code = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    let code_id = app.store_code(Box::new(code));
+
+    let addr = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                is_testing: false,
+                admin: "owner".to_string(),
+                supported_tokens: vec![
+                    (
+                        "eth".to_string(),
+                        "ethereum".to_string(),
+                        "ETH".to_string(),
+                        None,
+                        TOKENS_DECIMALS as u128,
+                    ),
+                    (
+                        "atom".to_string(),
+                        "atom".to_string(),
+                        "ATOM".to_string(),
+                        None,
+                        TOKENS_DECIMALS as u128,
+                    ),
+                ],
+                reserve_configuration: vec![
+                    ("eth".to_string(), LTV_ETH, LIQUIDATION_THRESHOLD_ETH),
+                    ("atom".to_string(), LTV_ATOM, LIQUIDATION_THRESHOLD_ATOM),
+                ],
+                tokens_interest_rate_model_params: vec![
+                    (
+                        "eth".to_string(),
+                        MIN_INTEREST_RATE,
+                        SAFE_BORROW_MAX_RATE,
+                        RATE_GROWTH_FACTOR,
+                        OPTIMAL_UTILISATION_RATIO,
+                    ),
+                    (
+                        "atom".to_string(),
+                        MIN_INTEREST_RATE,
+                        SAFE_BORROW_MAX_RATE,
+                        RATE_GROWTH_FACTOR,
+                        OPTIMAL_UTILISATION_RATIO,
+                    ),
+                ],
+                price_ids: vec![
+                    (
+                        "eth".to_string(),
+                        PriceIdentifier::from_hex(PRICE_ID_ETH).unwrap(),
+                    ),
+                    (
+                        "atom".to_string(),
+                        PriceIdentifier::from_hex(PRICE_ID_ATOM).unwrap(),
+                    ),
+                ],
+                pyth_contract_addr: pyth_addr.to_string(),
+                price_updater_addr: "price_updater".to_string(),
+            },
+            &[],
+            "Contract",
+            Some("owner".to_string()), // contract that can execute migrations
+        )
+        .unwrap();
+
+    // funding contract with reserves
+    app.send_tokens(
+        Addr::unchecked("owner"),
+        addr.clone(),
+        &[
+            coin(CONTRACT_RESERVES_ETH, "eth"),
+            coin(CONTRACT_RESERVES_ATOM, "atom"),
+        ],
+    )
+    .unwrap();
+
+    (app, addr, pyth_addr)
+}
@@ -0,0 +1,393 @@
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, LendingExecuteMsg, MmTokenInfo, QueryMsg},
+    state::{ALLOWANCES, TOKEN_INFO},
+};
+use cosmwasm_std::{
+    attr, ensure, to_json_binary, wasm_execute, Addr, Binary, BlockInfo, Deps, DepsMut, Env,
+    MessageInfo, Response, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw20::{AllowanceResponse, Cw20ReceiveMsg, Expiration};
+
+use crate::contract::query::{get_allowance, get_balance, get_mm_token_info, get_token_info};
+
+const CONTRACT_NAME: &str = "crates.io:mm-token";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    TOKEN_INFO.save(
+        deps.storage,
+        &MmTokenInfo {
+            name: msg.name,
+            symbol: msg.symbol,
+            decimals: msg.decimals,
+            lending_contract: deps.api.addr_validate(&msg.lending_contract)?,
+            denom: msg.denom,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("method", "instantiate"))
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Transfer { recipient, amount } => {
+            execute_transfer(deps, env, info, recipient, amount)
+        }
+        ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => execute_send(deps, env, info, contract, amount, msg),
+        ExecuteMsg::IncreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => execute_increase_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::DecreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => execute_transfer_from(deps, env, info, owner, recipient, amount),
+        ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            msg,
+        } => execute_send_from(deps, env, info, owner, contract, amount, msg),
+    }
+}
+
+// balances are moved by the lending contract after its health checks
+fn transfer_mm_token_msg(
+    token_info: &MmTokenInfo,
+    sender: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<WasmMsg> {
+    wasm_execute(
+        token_info.lending_contract.to_string(),
+        &LendingExecuteMsg::TransferMmToken {
+            sender: sender.to_string(),
+            recipient: recipient.to_string(),
+            amount,
+        },
+        vec![],
+    )
+}
+
+pub fn execute_transfer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure!(!amount.is_zero(), ContractError::InvalidZeroAmount {});
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+
+    let transfer_msg = transfer_mm_token_msg(&token_info, &info.sender, &recipient, amount)?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attributes(vec![
+            attr("action", "transfer"),
+            attr("from", info.sender),
+            attr("to", recipient),
+            attr("amount", amount),
+        ]))
+}
+
+pub fn execute_send(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    ensure!(!amount.is_zero(), ContractError::InvalidZeroAmount {});
+
+    let contract = deps.api.addr_validate(&contract)?;
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+
+    let transfer_msg = transfer_mm_token_msg(&token_info, &info.sender, &contract, amount)?;
+
+    let receive_msg = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount,
+        msg,
+    }
+    .into_cosmos_msg(contract.clone())?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_message(receive_msg)
+        .add_attributes(vec![
+            attr("action", "send"),
+            attr("from", info.sender),
+            attr("to", contract),
+            attr("amount", amount),
+        ]))
+}
+
+pub fn execute_increase_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    ensure!(
+        spender != info.sender,
+        ContractError::CannotSetOwnAccount {}
+    );
+
+    let allowance = ALLOWANCES.update(
+        deps.storage,
+        (&info.sender, &spender),
+        |allowance| -> Result<_, ContractError> {
+            let mut allowance = allowance.unwrap_or_default();
+            if let Some(expires) = expires {
+                ensure!(
+                    !expires.is_expired(&env.block),
+                    ContractError::InvalidExpiration {}
+                );
+                allowance.expires = expires;
+            }
+            allowance.allowance += amount;
+            Ok(allowance)
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "increase_allowance"),
+        attr("owner", info.sender),
+        attr("spender", spender),
+        attr("amount", amount),
+        attr("allowance", allowance.allowance),
+    ]))
+}
+
+pub fn execute_decrease_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    ensure!(
+        spender != info.sender,
+        ContractError::CannotSetOwnAccount {}
+    );
+
+    let key = (&info.sender, &spender);
+    let mut allowance = ALLOWANCES
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::NoAllowance {})?;
+
+    // an allowance decreased to zero is removed
+    if amount < allowance.allowance {
+        allowance.allowance -= amount;
+        if let Some(expires) = expires {
+            ensure!(
+                !expires.is_expired(&env.block),
+                ContractError::InvalidExpiration {}
+            );
+            allowance.expires = expires;
+        }
+        ALLOWANCES.save(deps.storage, key, &allowance)?;
+    } else {
+        allowance.allowance = Uint128::zero();
+        ALLOWANCES.remove(deps.storage, key);
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "decrease_allowance"),
+        attr("owner", info.sender),
+        attr("spender", spender),
+        attr("amount", amount),
+        attr("allowance", allowance.allowance),
+    ]))
+}
+
+fn deduct_allowance(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<AllowanceResponse, ContractError> {
+    ALLOWANCES.update(storage, (owner, spender), |allowance| {
+        let mut allowance = allowance.ok_or(ContractError::NoAllowance {})?;
+        ensure!(
+            !allowance.expires.is_expired(block),
+            ContractError::Expired {}
+        );
+        allowance.allowance = allowance
+            .allowance
+            .checked_sub(amount)
+            .map_err(|_| ContractError::NoAllowance {})?;
+        Ok(allowance)
+    })
+}
+
+pub fn execute_transfer_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure!(!amount.is_zero(), ContractError::InvalidZeroAmount {});
+
+    let owner = deps.api.addr_validate(&owner)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+
+    deduct_allowance(deps.storage, &owner, &info.sender, &env.block, amount)?;
+
+    // the lending contract checks the health of the owner, not of the spender
+    let transfer_msg = transfer_mm_token_msg(&token_info, &owner, &recipient, amount)?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attributes(vec![
+            attr("action", "transfer_from"),
+            attr("from", owner),
+            attr("to", recipient),
+            attr("by", info.sender),
+            attr("amount", amount),
+        ]))
+}
+
+pub fn execute_send_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    ensure!(!amount.is_zero(), ContractError::InvalidZeroAmount {});
+
+    let owner = deps.api.addr_validate(&owner)?;
+    let contract = deps.api.addr_validate(&contract)?;
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+
+    deduct_allowance(deps.storage, &owner, &info.sender, &env.block, amount)?;
+
+    let transfer_msg = transfer_mm_token_msg(&token_info, &owner, &contract, amount)?;
+
+    let receive_msg = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount,
+        msg,
+    }
+    .into_cosmos_msg(contract.clone())?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_message(receive_msg)
+        .add_attributes(vec![
+            attr("action", "send_from"),
+            attr("from", owner),
+            attr("to", contract),
+            attr("by", info.sender),
+            attr("amount", amount),
+        ]))
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Balance { address } => to_json_binary(&get_balance(deps, env, address)?),
+        QueryMsg::TokenInfo {} => to_json_binary(&get_token_info(deps, env)?),
+        QueryMsg::MmTokenInfo {} => to_json_binary(&get_mm_token_info(deps)?),
+        QueryMsg::Allowance { owner, spender } => {
+            to_json_binary(&get_allowance(deps, env, owner, spender)?)
+        }
+    }
+}
+
+pub mod query {
+    use super::*;
+    use crate::msg::LendingQueryMsg;
+    use cw20::{BalanceResponse, TokenInfoResponse};
+
+    pub fn get_balance(deps: Deps, _env: Env, address: String) -> StdResult<BalanceResponse> {
+        let token_info = TOKEN_INFO.load(deps.storage)?;
+
+        let balance: Uint128 = deps.querier.query_wasm_smart(
+            token_info.lending_contract,
+            &LendingQueryMsg::GetMmTokenBalance {
+                address,
+                denom: token_info.denom,
+            },
+        )?;
+
+        Ok(BalanceResponse { balance })
+    }
+
+    pub fn get_token_info(deps: Deps, _env: Env) -> StdResult<TokenInfoResponse> {
+        let token_info = TOKEN_INFO.load(deps.storage)?;
+
+        // supply always matches the sum of mmToken balances in the lending market
+        let total_supply: Uint128 = deps.querier.query_wasm_smart(
+            token_info.lending_contract,
+            &LendingQueryMsg::GetTotalMmTokenSupply {
+                denom: token_info.denom,
+            },
+        )?;
+
+        Ok(TokenInfoResponse {
+            name: token_info.name,
+            symbol: token_info.symbol,
+            decimals: token_info.decimals,
+            total_supply,
+        })
+    }
+
+    pub fn get_mm_token_info(deps: Deps) -> StdResult<MmTokenInfo> {
+        TOKEN_INFO.load(deps.storage)
+    }
+
+    pub fn get_allowance(
+        deps: Deps,
+        _env: Env,
+        owner: String,
+        spender: String,
+    ) -> StdResult<AllowanceResponse> {
+        let owner = deps.api.addr_validate(&owner)?;
+        let spender = deps.api.addr_validate(&spender)?;
+
+        Ok(ALLOWANCES
+            .may_load(deps.storage, (&owner, &spender))?
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,23 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Invalid Zero Amount")]
+    InvalidZeroAmount {},
+
+    #[error("Cannot Set Allowance To Own Account")]
+    CannotSetOwnAccount {},
+
+    #[error("Invalid Expiration")]
+    InvalidExpiration {},
+
+    #[error("Allowance Is Expired")]
+    Expired {},
+
+    #[error("No Allowance For This Account")]
+    NoAllowance {},
+}
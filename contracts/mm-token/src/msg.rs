@@ -0,0 +1,90 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cw20::{AllowanceResponse, BalanceResponse, Expiration, TokenInfoResponse};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    // lending contract that keeps mmToken balances
+    pub lending_contract: String,
+    // denom of the lending market
+    pub denom: String,
+}
+
+// subset of Cw20ExecuteMsg, every transfer is checked by the lending contract
+#[cw_serde]
+pub enum ExecuteMsg {
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(BalanceResponse)]
+    Balance { address: String },
+
+    #[returns(TokenInfoResponse)]
+    TokenInfo {},
+
+    #[returns(AllowanceResponse)]
+    Allowance { owner: String, spender: String },
+
+    #[returns(MmTokenInfo)]
+    MmTokenInfo {},
+}
+
+#[cw_serde]
+pub struct MmTokenInfo {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub lending_contract: Addr,
+    pub denom: String,
+}
+
+// messages of the lending contract used by the receipt token
+#[cw_serde]
+pub enum LendingExecuteMsg {
+    TransferMmToken {
+        sender: String,
+        recipient: String,
+        amount: Uint128,
+    },
+}
+
+#[cw_serde]
+pub enum LendingQueryMsg {
+    GetMmTokenBalance { address: String, denom: String },
+    GetTotalMmTokenSupply { denom: String },
+}
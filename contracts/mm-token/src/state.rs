@@ -0,0 +1,16 @@
+use crate::msg::MmTokenInfo;
+use cosmwasm_std::Addr;
+use cw20::AllowanceResponse;
+use cw_storage_plus::{Item, Map};
+
+pub const TOKEN_INFO: Item<MmTokenInfo> = Item::new("token_info");
+/*
+TOKEN_INFO that describes the receipt token and the lending market it belongs to.
+Balances are not stored here, they are read from the lending contract.
+*/
+
+pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowances");
+/*
+ALLOWANCES STORAGE
+Key: (owner, spender) -> Value: AllowanceResponse (amount, expires)
+*/